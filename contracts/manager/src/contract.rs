@@ -350,6 +350,7 @@ mod tests {
                 update: Strategy {
                     owner: info.sender.clone(),
                     action: Action::Many(vec![]),
+                    definitions: vec![],
                     state: Json
                 }
             },
@@ -367,6 +368,7 @@ mod tests {
                 update: Strategy {
                     owner: info.sender.clone(),
                     action: Action::Many(vec![]),
+                    definitions: vec![],
                     state: Json
                 }
             },
@@ -501,6 +503,7 @@ mod tests {
                 update: Strategy {
                     owner: info.sender.clone(),
                     action: Action::Many(vec![]),
+                    definitions: vec![],
                     state: Json,
                 },
             },
@@ -1,8 +1,12 @@
-use std::vec;
+use std::{
+    hash::{DefaultHasher, Hasher},
+    time::Duration,
+    vec,
+};
 
 use calc_rs::{
-    conditions::Condition,
-    constants::LOG_ERRORS_REPLY_ID,
+    conditions::{CompositeCondition, Condition, ErrorPolicy},
+    constants::{JITTER_SECONDS_PER_BLOCK, LOG_ERRORS_REPLY_ID},
     core::{Contract, ContractError, ContractResult},
     scheduler::{SchedulerExecuteMsg, SchedulerInstantiateMsg, SchedulerQueryMsg, Trigger},
 };
@@ -10,8 +14,8 @@ use cosmwasm_schema::cw_serde;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, BankMsg, Binary, Coin, Coins, Deps, DepsMut, Env, MessageInfo, Reply, Response,
-    StdError, StdResult, SubMsg, SubMsgResult,
+    to_json_binary, BankMsg, Binary, Coin, Coins, Deps, DepsMut, Env, Event, MessageInfo, Reply,
+    Response, StdError, StdResult, SubMsg, SubMsgResult, Uint64,
 };
 use rujira_rs::fin::{ConfigResponse, ExecuteMsg, OrderResponse, Price, QueryMsg};
 
@@ -43,6 +47,7 @@ pub fn execute(
     msg: SchedulerExecuteMsg,
 ) -> ContractResult {
     let mut sub_messages = vec![];
+    let mut events = vec![];
 
     match msg {
         SchedulerExecuteMsg::Create(create_command) => {
@@ -57,6 +62,16 @@ pub fn execute(
                 }));
             }
 
+            create_command.condition.validate()?;
+
+            if reads_local_strategy_state(&create_command.condition) {
+                return Err(ContractError::generic_err(
+                    "Strategy state conditions must name the strategy they read from",
+                ));
+            }
+
+            resolve_queries(deps.as_ref(), &env, &create_command.condition)?;
+
             let mut execution_rebate = Coins::try_from(info.funds)?;
 
             if let Condition::LimitOrderFilled {
@@ -95,12 +110,18 @@ pub fn execute(
                 deps.storage,
                 &Trigger {
                     id: trigger_id,
-                    condition: create_command.condition,
+                    condition: with_jitter(
+                        create_command.condition,
+                        create_command.jitter,
+                        &env,
+                        trigger_id,
+                    ),
                     msg: create_command.msg,
                     contract_address: create_command.contract_address,
                     executors: create_command.executors,
                     execution_rebate: execution_rebate.to_vec(),
                     jitter: create_command.jitter,
+                    on_error: create_command.on_error,
                 },
             )?;
         }
@@ -114,13 +135,36 @@ pub fn execute(
                     continue;
                 }
 
-                if let Ok(trigger_is_satisfied) =
-                    trigger.condition.is_satisfied(deps.as_ref(), &env)
-                {
-                    if !trigger_is_satisfied {
-                        continue;
-                    }
+                let (trigger_is_satisfied, error_event) =
+                    match trigger
+                        .condition
+                        .evaluate(deps.as_ref(), &env, &trigger.on_error)
+                    {
+                        Ok(outcome) => outcome,
+                        // One failing trigger must not fail the rest of the batch
+                        Err(err) => {
+                            events.push(
+                                Event::new("condition_evaluation_failed")
+                                    .add_attribute("policy", format!("{:?}", ErrorPolicy::Fail))
+                                    .add_attribute("error", err.to_string())
+                                    .add_attribute("trigger_id", id.to_string()),
+                            );
+                            continue;
+                        }
+                    };
+
+                let evaluated = error_event.is_none();
+
+                if let Some(event) = error_event {
+                    events.push(event.add_attribute("trigger_id", id.to_string()));
+                }
 
+                if !trigger_is_satisfied {
+                    continue;
+                }
+
+                // A limit order that could not be queried is not withdrawn
+                if evaluated {
                     if let Condition::LimitOrderFilled {
                         pair_address,
                         side,
@@ -183,7 +227,90 @@ pub fn execute(
         }
     };
 
-    Ok(Response::default().add_submessages(sub_messages))
+    Ok(Response::default()
+        .add_submessages(sub_messages)
+        .add_events(events))
+}
+
+/// The scheduler holds no price history, statistics or heartbeat
+/// of its own, so triggers have to read them from a strategy contract.
+fn reads_local_strategy_state(condition: &Condition) -> bool {
+    match condition {
+        Condition::PriceChange { strategy, .. }
+        | Condition::MovingAverageCross { strategy, .. }
+        | Condition::TotalDebited { strategy, .. }
+        | Condition::TotalCredited { strategy, .. }
+        | Condition::ExecutionCount { strategy, .. }
+        | Condition::OwnerInactive { strategy, .. } => strategy.is_none(),
+        Condition::Not(condition) => reads_local_strategy_state(condition),
+        Condition::Composite(composite) => {
+            composite.conditions.iter().any(reads_local_strategy_state)
+        }
+        _ => false,
+    }
+}
+
+/// Evaluates each query the condition makes once, so a query against
+/// a missing contract or along a path that does not resolve is rejected
+/// when the trigger is created rather than when it is next checked.
+fn resolve_queries(deps: Deps, env: &Env, condition: &Condition) -> StdResult<()> {
+    match condition {
+        Condition::Query { .. } => condition.is_satisfied(deps, env).map(|_| ()),
+        Condition::Not(condition) => resolve_queries(deps, env, condition),
+        Condition::Composite(composite) => composite
+            .conditions
+            .iter()
+            .try_for_each(|condition| resolve_queries(deps, env, condition)),
+        _ => Ok(()),
+    }
+}
+
+/// Delays timestamp and block height conditions by a pseudo-random
+/// offset within the jitter window, so execution can't be predicted
+/// (and front-run) to the block. The offset is fixed when the trigger
+/// is created, so it holds for both evaluation and the trigger indexes.
+fn with_jitter(
+    condition: Condition,
+    jitter: Option<Duration>,
+    env: &Env,
+    trigger_id: Uint64,
+) -> Condition {
+    let window = match jitter {
+        Some(jitter) if jitter.as_secs() > 0 => jitter.as_secs(),
+        _ => return condition,
+    };
+
+    let mut hash = DefaultHasher::new();
+    hash.write_u64(trigger_id.u64());
+    hash.write_u64(env.block.height);
+    hash.write_u64(env.block.time.nanos());
+
+    delay(condition, hash.finish() % (window + 1))
+}
+
+/// Delays every timestamp and block height the condition waits on,
+/// including those nested in composite and negated conditions.
+fn delay(condition: Condition, seconds: u64) -> Condition {
+    match condition {
+        Condition::TimestampElapsed(timestamp) => {
+            Condition::TimestampElapsed(timestamp.plus_seconds(seconds))
+        }
+        Condition::BlocksCompleted(height) => {
+            Condition::BlocksCompleted(height + seconds / JITTER_SECONDS_PER_BLOCK)
+        }
+        Condition::Not(condition) => Condition::Not(Box::new(delay(*condition, seconds))),
+        Condition::Composite(CompositeCondition {
+            conditions,
+            threshold,
+        }) => Condition::Composite(CompositeCondition {
+            conditions: conditions
+                .into_iter()
+                .map(|condition| delay(condition, seconds))
+                .collect(),
+            threshold,
+        }),
+        condition => condition,
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -193,12 +320,20 @@ pub fn query(deps: Deps, env: Env, msg: SchedulerQueryMsg) -> StdResult<Binary>
             let filtered = TRIGGERS.filtered(deps.storage, filter, limit)?;
             to_json_binary(&filtered)
         }
-        SchedulerQueryMsg::CanExecute(id) => to_json_binary(
+        SchedulerQueryMsg::CanExecute(id) => {
+            let trigger = TRIGGERS.load(deps.storage, id.into())?;
+            let (can_execute, _) = trigger.condition.evaluate(deps, &env, &trigger.on_error)?;
+            to_json_binary(&can_execute)
+        }
+        SchedulerQueryMsg::TraceTrigger(id) => to_json_binary(
             &TRIGGERS
                 .load(deps.storage, id.into())?
                 .condition
-                .is_satisfied(deps, &env)?,
+                .trace(deps, &env),
         ),
+        SchedulerQueryMsg::TraceCondition { condition } => {
+            to_json_binary(&condition.trace(deps, &env))
+        }
     }
 }
 
@@ -215,12 +350,18 @@ pub fn reply(_deps: DepsMut, _env: Env, reply: Reply) -> ContractResult {
 
 #[cfg(test)]
 mod create_trigger_tests {
+    use std::time::Duration;
+
     use super::*;
     use calc_rs::{
-        conditions::Condition,
+        actions::limit_order::Direction,
+        conditions::{CompositeCondition, Condition, ErrorPolicy},
+        core::Threshold,
+        query::Comparator,
         scheduler::{ConditionFilter, CreateTriggerMsg, Trigger},
     };
     use cosmwasm_std::{
+        from_json,
         testing::{message_info, mock_dependencies, mock_env},
         Addr, Coin, ContractResult as ContractQueryResult, Decimal, SystemResult,
     };
@@ -241,6 +382,7 @@ mod create_trigger_tests {
             contract_address: owner.clone(),
             executors: vec![],
             jitter: None,
+            on_error: ErrorPolicy::default(),
         };
 
         execute(
@@ -271,7 +413,8 @@ mod create_trigger_tests {
                 condition: condition.clone(),
                 execution_rebate: info.funds.clone(),
                 executors: vec![],
-                jitter: None
+                jitter: None,
+                on_error: ErrorPolicy::default()
             }]
         );
     }
@@ -291,6 +434,7 @@ mod create_trigger_tests {
             contract_address: owner.clone(),
             executors: vec![],
             jitter: None,
+            on_error: ErrorPolicy::default(),
         };
 
         execute(
@@ -320,6 +464,7 @@ mod create_trigger_tests {
                 msg: Binary::default(),
                 executors: vec![],
                 jitter: None,
+                on_error: ErrorPolicy::default(),
                 condition: condition.clone(),
                 execution_rebate: info.funds.clone(),
             }]
@@ -333,6 +478,7 @@ mod create_trigger_tests {
             contract_address: owner.clone(),
             executors: vec![],
             jitter: None,
+            on_error: ErrorPolicy::default(),
         };
 
         execute(
@@ -362,6 +508,7 @@ mod create_trigger_tests {
                 msg: Binary::default(),
                 executors: vec![],
                 jitter: None,
+                on_error: ErrorPolicy::default(),
                 condition: condition.clone(),
                 execution_rebate: updated_info.funds.clone(),
             }]
@@ -407,7 +554,8 @@ mod create_trigger_tests {
                 msg: Binary::default(),
                 contract_address: owner.clone(),
                 executors: vec![],
-                jitter: None
+                jitter: None,
+                on_error: ErrorPolicy::default()
             }),
         )
         .unwrap_err()
@@ -423,7 +571,8 @@ mod create_trigger_tests {
                 msg: Binary::default(),
                 contract_address: owner.clone(),
                 executors: vec![],
-                jitter: None
+                jitter: None,
+                on_error: ErrorPolicy::default()
             }),
         )
         .unwrap_err()
@@ -456,7 +605,8 @@ mod create_trigger_tests {
                 msg: Binary::default(),
                 contract_address: owner.clone(),
                 executors: vec![],
-                jitter: None
+                jitter: None,
+                on_error: ErrorPolicy::default()
             }),
         )
         .unwrap_err()
@@ -508,6 +658,7 @@ mod create_trigger_tests {
                 contract_address: owner.clone(),
                 executors: vec![],
                 jitter: None,
+                on_error: ErrorPolicy::default(),
             }),
         )
         .unwrap();
@@ -570,6 +721,7 @@ mod create_trigger_tests {
             contract_address: owner.clone(),
             executors: vec![],
             jitter: None,
+            on_error: ErrorPolicy::default(),
         };
 
         execute(
@@ -600,18 +752,326 @@ mod create_trigger_tests {
                 msg: Binary::default(),
                 executors: vec![],
                 jitter: None,
+                on_error: ErrorPolicy::default(),
                 condition: condition.clone(),
                 execution_rebate: vec![Coin::new(1234_u128, "eth-eth")],
             }]
         );
     }
+
+    #[test]
+    fn fails_to_create_trigger_with_unreachable_threshold() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = deps.api.addr_make("creator");
+        let info = message_info(&owner, &[]);
+
+        assert!(execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            SchedulerExecuteMsg::Create(CreateTriggerMsg {
+                condition: Condition::Composite(CompositeCondition {
+                    conditions: vec![
+                        Condition::BlocksCompleted(env.block.height + 10),
+                        Condition::TimestampElapsed(env.block.time.plus_seconds(10)),
+                    ],
+                    threshold: Threshold::Exactly(3),
+                }),
+                msg: Binary::default(),
+                contract_address: owner.clone(),
+                executors: vec![],
+                jitter: None,
+                on_error: ErrorPolicy::default()
+            }),
+        )
+        .unwrap_err()
+        .to_string()
+        .contains("cannot be met by 2 conditions"));
+    }
+
+    #[test]
+    fn fails_to_create_execution_count_trigger_without_strategy() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = deps.api.addr_make("creator");
+        let info = message_info(&owner, &[]);
+
+        assert!(execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            SchedulerExecuteMsg::Create(CreateTriggerMsg {
+                condition: Condition::ExecutionCount {
+                    min: Some(10),
+                    max: None,
+                    strategy: None,
+                },
+                msg: Binary::default(),
+                contract_address: owner.clone(),
+                executors: vec![],
+                jitter: None,
+                on_error: ErrorPolicy::default()
+            }),
+        )
+        .unwrap_err()
+        .to_string()
+        .contains("Strategy state conditions must name the strategy they read from"));
+    }
+
+    #[test]
+    fn fails_to_create_price_change_trigger_without_strategy() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = deps.api.addr_make("creator");
+        let info = message_info(&owner, &[]);
+
+        let condition = Condition::Not(Box::new(Condition::PriceChange {
+            asset: "btc-btc".to_string(),
+            window: Duration::from_secs(3_600),
+            direction: Direction::Below,
+            bps: 500,
+            strategy: None,
+        }));
+
+        assert!(execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            SchedulerExecuteMsg::Create(CreateTriggerMsg {
+                condition,
+                msg: Binary::default(),
+                contract_address: owner.clone(),
+                executors: vec![],
+                jitter: None,
+                on_error: ErrorPolicy::default()
+            }),
+        )
+        .unwrap_err()
+        .to_string()
+        .contains("Strategy state conditions must name the strategy they read from"));
+    }
+
+    #[test]
+    fn fails_to_create_trigger_with_unresolvable_query_condition() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = deps.api.addr_make("creator");
+        let info = message_info(&owner, &[]);
+
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractQueryResult::Ok(Binary::from(br#"{"value":"1"}"#)))
+        });
+
+        let create_trigger_msg = |path: &str| CreateTriggerMsg {
+            condition: Condition::Composite(CompositeCondition {
+                conditions: vec![
+                    Condition::BlocksCompleted(env.block.height + 10),
+                    Condition::Not(Box::new(Condition::Query {
+                        contract: Addr::unchecked("contract"),
+                        msg: "{}".to_string(),
+                        path: path.to_string(),
+                        comparator: Comparator::Equal,
+                        value: "1".to_string(),
+                        max_response_bytes: None,
+                    })),
+                ],
+                threshold: Threshold::All,
+            }),
+            msg: Binary::default(),
+            contract_address: owner.clone(),
+            executors: vec![],
+            jitter: None,
+            on_error: ErrorPolicy::default(),
+        };
+
+        assert!(execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            SchedulerExecuteMsg::Create(create_trigger_msg("/missing")),
+        )
+        .is_err());
+
+        assert!(execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            SchedulerExecuteMsg::Create(create_trigger_msg("/value")),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn delays_triggers_within_jitter_window() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let owner = deps.api.addr_make("creator");
+        let info = message_info(&owner.clone(), &[]);
+
+        let create = |condition: Condition| CreateTriggerMsg {
+            condition,
+            msg: Binary::default(),
+            contract_address: owner.clone(),
+            executors: vec![],
+            jitter: Some(Duration::from_secs(600)),
+            on_error: ErrorPolicy::default(),
+        };
+
+        let time_trigger_msg = create(Condition::TimestampElapsed(env.block.time));
+        let block_trigger_msg = create(Condition::BlocksCompleted(env.block.height));
+
+        for msg in [time_trigger_msg.clone(), block_trigger_msg.clone()] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                SchedulerExecuteMsg::Create(msg),
+            )
+            .unwrap();
+        }
+
+        let time_trigger = TRIGGERS
+            .load(deps.as_ref().storage, time_trigger_msg.id().unwrap())
+            .unwrap();
+
+        assert_eq!(time_trigger.jitter, Some(Duration::from_secs(600)));
+
+        let delayed_until = match time_trigger.condition {
+            Condition::TimestampElapsed(timestamp) => timestamp,
+            condition => panic!("unexpected condition {condition:?}"),
+        };
+
+        assert!(delayed_until >= env.block.time);
+        assert!(delayed_until <= env.block.time.plus_seconds(600));
+
+        let block_trigger = TRIGGERS
+            .load(deps.as_ref().storage, block_trigger_msg.id().unwrap())
+            .unwrap();
+
+        match block_trigger.condition {
+            Condition::BlocksCompleted(height) => {
+                assert!(height >= env.block.height);
+                assert!(height <= env.block.height + 600 / JITTER_SECONDS_PER_BLOCK);
+            }
+            condition => panic!("unexpected condition {condition:?}"),
+        };
+
+        let triggers = TRIGGERS
+            .filtered(
+                deps.as_ref().storage,
+                ConditionFilter::Timestamp {
+                    start: Some(delayed_until),
+                    end: Some(delayed_until),
+                },
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(triggers, vec![time_trigger.clone()]);
+
+        let can_execute = |env: &Env| {
+            from_json::<bool>(
+                query(
+                    deps.as_ref(),
+                    env.clone(),
+                    SchedulerQueryMsg::CanExecute(time_trigger.id),
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        env.block.time = delayed_until.minus_seconds(1);
+        assert!(!can_execute(&env));
+
+        env.block.time = delayed_until;
+        assert!(can_execute(&env));
+    }
+
+    #[test]
+    fn jitter_is_deterministic_for_the_same_block() {
+        let env = mock_env();
+
+        let condition = Condition::TimestampElapsed(env.block.time);
+        let jitter = Some(Duration::from_secs(3_600));
+        let id = Uint64::from(42u64);
+
+        assert_eq!(
+            with_jitter(condition.clone(), jitter, &env, id),
+            with_jitter(condition.clone(), jitter, &env, id)
+        );
+
+        assert_eq!(with_jitter(condition.clone(), None, &env, id), condition);
+
+        let other = Condition::OraclePrice {
+            asset: "BTC-BTC".to_string(),
+            direction: Direction::Above,
+            rate: Decimal::one(),
+        };
+
+        assert_eq!(with_jitter(other.clone(), jitter, &env, id), other);
+    }
+
+    #[test]
+    fn jitter_delays_nested_conditions() {
+        let env = mock_env();
+
+        let jitter = Some(Duration::from_secs(3_600));
+        let id = Uint64::from(42u64);
+
+        let offset = match with_jitter(
+            Condition::TimestampElapsed(env.block.time),
+            jitter,
+            &env,
+            id,
+        ) {
+            Condition::TimestampElapsed(timestamp) => {
+                timestamp.seconds() - env.block.time.seconds()
+            }
+            condition => panic!("unexpected condition {condition:?}"),
+        };
+
+        let other = Condition::OraclePrice {
+            asset: "BTC-BTC".to_string(),
+            direction: Direction::Above,
+            rate: Decimal::one(),
+        };
+
+        assert_eq!(
+            with_jitter(
+                Condition::Composite(CompositeCondition {
+                    conditions: vec![
+                        Condition::TimestampElapsed(env.block.time),
+                        Condition::Not(Box::new(Condition::BlocksCompleted(env.block.height))),
+                        other.clone(),
+                    ],
+                    threshold: Threshold::All,
+                }),
+                jitter,
+                &env,
+                id,
+            ),
+            Condition::Composite(CompositeCondition {
+                conditions: vec![
+                    Condition::TimestampElapsed(env.block.time.plus_seconds(offset)),
+                    Condition::Not(Box::new(Condition::BlocksCompleted(
+                        env.block.height + offset / JITTER_SECONDS_PER_BLOCK
+                    ))),
+                    other,
+                ],
+                threshold: Threshold::All,
+            })
+        );
+    }
 }
 
 #[cfg(test)]
 mod execute_trigger_tests {
     use super::*;
-    use calc_rs::conditions::Condition;
+    use calc_rs::conditions::{Condition, ErrorPolicy};
     use calc_rs::manager::ManagerExecuteMsg;
+    use calc_rs::query::Comparator;
     use calc_rs::scheduler::{ConditionFilter, CreateTriggerMsg};
     use cosmwasm_std::testing::message_info;
     use cosmwasm_std::{from_json, Addr, Decimal, Uint128, Uint64, WasmMsg, WasmQuery};
@@ -657,6 +1117,7 @@ mod execute_trigger_tests {
             contract_address: owner.clone(),
             executors: vec![],
             jitter: None,
+            on_error: ErrorPolicy::default(),
         };
 
         execute(
@@ -742,6 +1203,7 @@ mod execute_trigger_tests {
                     execution_rebate: vec![remaining_rebate.clone()],
                     executors: vec![],
                     jitter: None,
+                    on_error: ErrorPolicy::default(),
                 },
             )
             .unwrap();
@@ -810,6 +1272,7 @@ mod execute_trigger_tests {
             contract_address: manager.clone(),
             executors: vec![],
             jitter: None,
+            on_error: ErrorPolicy::default(),
         };
 
         execute(
@@ -861,6 +1324,7 @@ mod execute_trigger_tests {
             .unwrap(),
             executors: vec![],
             jitter: None,
+            on_error: ErrorPolicy::default(),
         };
 
         execute(
@@ -915,6 +1379,7 @@ mod execute_trigger_tests {
             contract_address: owner.clone(),
             executors: vec![],
             jitter: None,
+            on_error: ErrorPolicy::default(),
         };
 
         execute(
@@ -959,6 +1424,162 @@ mod execute_trigger_tests {
 
         assert!(triggers.is_empty());
     }
+
+    #[test]
+    fn applies_error_policy_if_trigger_condition_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = deps.api.addr_make("creator");
+        let executor = deps.api.addr_make("executor");
+
+        // Resolves when the triggers are created, but not once they are executed
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(CosmosContractResult::Ok(Binary::from(br#"{"value":"0"}"#)))
+        });
+
+        let create_trigger_msg = |on_error: ErrorPolicy| CreateTriggerMsg {
+            condition: Condition::Query {
+                contract: Addr::unchecked("missing"),
+                msg: "{}".to_string(),
+                path: "/value".to_string(),
+                comparator: Comparator::Equal,
+                value: "1".to_string(),
+                max_response_bytes: None,
+            },
+            msg: Binary::default(),
+            contract_address: owner.clone(),
+            executors: vec![],
+            jitter: None,
+            on_error,
+        };
+
+        for policy in [
+            ErrorPolicy::TreatAsFalse,
+            ErrorPolicy::TreatAsTrue,
+            ErrorPolicy::Fail,
+        ] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&owner, &[]),
+                SchedulerExecuteMsg::Create(create_trigger_msg(policy.clone())),
+            )
+            .unwrap();
+        }
+
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(CosmosContractResult::Err("unavailable".to_string()))
+        });
+
+        let response = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&executor, &[]),
+            SchedulerExecuteMsg::Execute(vec![create_trigger_msg(ErrorPolicy::TreatAsFalse)
+                .id()
+                .unwrap()]),
+        )
+        .unwrap();
+
+        assert!(response.messages.is_empty());
+        assert!(response.events[0]
+            .attributes
+            .iter()
+            .any(|attribute| attribute.key == "policy" && attribute.value == "TreatAsFalse"));
+        assert!(TRIGGERS
+            .load(
+                deps.as_ref().storage,
+                create_trigger_msg(ErrorPolicy::TreatAsFalse)
+                    .id()
+                    .unwrap()
+                    .into()
+            )
+            .is_ok());
+
+        let failing_id = create_trigger_msg(ErrorPolicy::Fail).id().unwrap();
+        let treated_as_true_id = create_trigger_msg(ErrorPolicy::TreatAsTrue).id().unwrap();
+
+        // The failing trigger is skipped without failing the rest of the batch
+        let response = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&executor, &[]),
+            SchedulerExecuteMsg::Execute(vec![failing_id, treated_as_true_id]),
+        )
+        .unwrap();
+
+        assert_eq!(response.messages.len(), 1);
+        assert!(response.events[0]
+            .attributes
+            .iter()
+            .any(|attribute| attribute.key == "trigger_id"
+                && attribute.value == failing_id.to_string()));
+        assert!(response.events[0]
+            .attributes
+            .iter()
+            .any(|attribute| attribute.key == "policy" && attribute.value == "Fail"));
+        assert!(response.events[1]
+            .attributes
+            .iter()
+            .any(|attribute| attribute.key == "policy" && attribute.value == "TreatAsTrue"));
+        assert!(TRIGGERS
+            .load(deps.as_ref().storage, failing_id.into())
+            .is_ok());
+        assert!(TRIGGERS
+            .load(deps.as_ref().storage, treated_as_true_id.into())
+            .is_err());
+
+        // Evaluated directly, the failing trigger reports its error
+        assert!(query(
+            deps.as_ref(),
+            env.clone(),
+            SchedulerQueryMsg::CanExecute(failing_id)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn executes_legacy_trigger_if_trigger_condition_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let owner = deps.api.addr_make("owner");
+
+        // Stored before triggers had an error policy
+        let trigger = from_json::<Trigger>(format!(
+            r#"{{
+                "id": "1",
+                "condition": {{
+                    "query": {{
+                        "contract": "missing",
+                        "msg": "{{}}",
+                        "path": "/value",
+                        "comparator": "equal",
+                        "value": "1"
+                    }}
+                }},
+                "msg": "",
+                "contract_address": "{owner}",
+                "executors": [],
+                "execution_rebate": [],
+                "jitter": null
+            }}"#
+        ))
+        .unwrap();
+
+        assert_eq!(trigger.on_error, ErrorPolicy::TreatAsTrue);
+
+        TRIGGERS.save(deps.as_mut().storage, &trigger).unwrap();
+
+        let response = execute(
+            deps.as_mut(),
+            env,
+            message_info(&owner, &[]),
+            SchedulerExecuteMsg::Execute(vec![Uint64::new(1)]),
+        )
+        .unwrap();
+
+        assert_eq!(response.messages.len(), 1);
+    }
 }
 
 #[cfg(test)]
@@ -968,7 +1589,10 @@ mod filtered_triggers_tests {
     use super::*;
 
     use calc_rs::{
-        conditions::Condition,
+        actions::limit_order::Direction,
+        conditions::{CompositeCondition, Condition, ErrorPolicy},
+        core::Threshold,
+        price::BookSide,
         scheduler::{ConditionFilter, Trigger},
     };
     use cosmwasm_std::{
@@ -995,6 +1619,7 @@ mod filtered_triggers_tests {
                         execution_rebate: vec![],
                         executors: vec![],
                         jitter: None,
+                        on_error: ErrorPolicy::default(),
                     },
                 )
                 .unwrap();
@@ -1026,7 +1651,8 @@ mod filtered_triggers_tests {
                     condition: Condition::TimestampElapsed(env.block.time.plus_seconds(i * 10),),
                     execution_rebate: vec![],
                     executors: vec![],
-                    jitter: None
+                    jitter: None,
+                    on_error: ErrorPolicy::default()
                 })
                 .collect::<Vec<_>>()
         );
@@ -1049,6 +1675,7 @@ mod filtered_triggers_tests {
                         execution_rebate: vec![],
                         executors: vec![],
                         jitter: None,
+                        on_error: ErrorPolicy::default(),
                     },
                 )
                 .unwrap();
@@ -1080,7 +1707,8 @@ mod filtered_triggers_tests {
                     msg: Binary::default(),
                     execution_rebate: vec![],
                     executors: vec![],
-                    jitter: None
+                    jitter: None,
+                    on_error: ErrorPolicy::default()
                 })
                 .collect::<Vec<_>>()
         );
@@ -1103,6 +1731,7 @@ mod filtered_triggers_tests {
                         execution_rebate: vec![],
                         executors: vec![],
                         jitter: None,
+                        on_error: ErrorPolicy::default(),
                     },
                 )
                 .unwrap();
@@ -1134,7 +1763,8 @@ mod filtered_triggers_tests {
                     condition: Condition::BlocksCompleted(env.block.height + i * 10,),
                     execution_rebate: vec![],
                     executors: vec![],
-                    jitter: None
+                    jitter: None,
+                    on_error: ErrorPolicy::default()
                 })
                 .collect::<Vec<_>>()
         );
@@ -1157,6 +1787,7 @@ mod filtered_triggers_tests {
                         execution_rebate: vec![],
                         executors: vec![],
                         jitter: None,
+                        on_error: ErrorPolicy::default(),
                     },
                 )
                 .unwrap();
@@ -1188,7 +1819,8 @@ mod filtered_triggers_tests {
                     msg: Binary::default(),
                     execution_rebate: vec![],
                     executors: vec![],
-                    jitter: None
+                    jitter: None,
+                    on_error: ErrorPolicy::default()
                 })
                 .collect::<Vec<_>>()
         );
@@ -1216,6 +1848,7 @@ mod filtered_triggers_tests {
                         execution_rebate: vec![],
                         executors: vec![],
                         jitter: None,
+                        on_error: ErrorPolicy::default(),
                     },
                 )
                 .unwrap();
@@ -1256,6 +1889,7 @@ mod filtered_triggers_tests {
                         execution_rebate: vec![],
                         executors: vec![],
                         jitter: None,
+                        on_error: ErrorPolicy::default(),
                     }
                 })
                 .collect::<Vec<_>>()
@@ -1284,6 +1918,7 @@ mod filtered_triggers_tests {
                         execution_rebate: vec![],
                         executors: vec![],
                         jitter: None,
+                        on_error: ErrorPolicy::default(),
                     },
                 )
                 .unwrap();
@@ -1327,6 +1962,7 @@ mod filtered_triggers_tests {
                         execution_rebate: vec![],
                         executors: vec![],
                         jitter: None,
+                        on_error: ErrorPolicy::default(),
                     }
                 })
                 .collect::<Vec<_>>()
@@ -1355,6 +1991,7 @@ mod filtered_triggers_tests {
                         execution_rebate: vec![],
                         executors: vec![],
                         jitter: None,
+                        on_error: ErrorPolicy::default(),
                     },
                 )
                 .unwrap();
@@ -1398,6 +2035,7 @@ mod filtered_triggers_tests {
                         execution_rebate: vec![],
                         executors: vec![],
                         jitter: None,
+                        on_error: ErrorPolicy::default(),
                     }
                 })
                 .collect::<Vec<_>>()
@@ -1426,6 +2064,7 @@ mod filtered_triggers_tests {
                         execution_rebate: vec![],
                         executors: vec![],
                         jitter: None,
+                        on_error: ErrorPolicy::default(),
                     },
                 )
                 .unwrap();
@@ -1469,9 +2108,275 @@ mod filtered_triggers_tests {
                         execution_rebate: vec![],
                         executors: vec![],
                         jitter: None,
+                        on_error: ErrorPolicy::default(),
                     }
                 })
                 .collect::<Vec<_>>()
         );
     }
+
+    #[test]
+    fn fetches_triggers_with_pair_and_start_after_fin_price_filter() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let fin_price_trigger = |i: u64| Trigger {
+            id: Uint64::from(i),
+            contract_address: Addr::unchecked("manager"),
+            msg: Binary::default(),
+            condition: Condition::FinPrice {
+                pair_address: Addr::unchecked(format!("pair-{}", i % 2)),
+                side: BookSide::Mid,
+                direction: Direction::Above,
+                price: Decimal::from_str(&i.to_string()).unwrap(),
+            },
+            execution_rebate: vec![],
+            executors: vec![],
+            jitter: None,
+            on_error: ErrorPolicy::default(),
+        };
+
+        for i in 1..=10 {
+            TRIGGERS
+                .save(deps.as_mut().storage, &fin_price_trigger(i))
+                .unwrap();
+        }
+
+        TRIGGERS
+            .save(
+                deps.as_mut().storage,
+                &Trigger {
+                    id: Uint64::from(11u64),
+                    contract_address: Addr::unchecked("manager"),
+                    msg: Binary::default(),
+                    condition: Condition::LimitOrderFilled {
+                        owner: Addr::unchecked("owner"),
+                        pair_address: Addr::unchecked("pair-0"),
+                        side: Side::Base,
+                        price: Decimal::one(),
+                    },
+                    execution_rebate: vec![],
+                    executors: vec![],
+                    jitter: None,
+                    on_error: ErrorPolicy::default(),
+                },
+            )
+            .unwrap();
+
+        let response = from_json::<Vec<Trigger>>(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                SchedulerQueryMsg::Filtered {
+                    filter: ConditionFilter::FinPrice {
+                        pair_address: Addr::unchecked("pair-0"),
+                        start_after: Some(4),
+                    },
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            response,
+            (3..=5)
+                .map(|i| fin_price_trigger(i * 2))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn fetches_triggers_with_asset_and_start_after_oracle_price_filter() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let oracle_price_trigger = |i: u64| Trigger {
+            id: Uint64::from(i),
+            contract_address: Addr::unchecked("manager"),
+            msg: Binary::default(),
+            condition: Condition::OraclePrice {
+                asset: format!("asset-{}", i % 2),
+                direction: Direction::Below,
+                rate: Decimal::from_str(&i.to_string()).unwrap(),
+            },
+            execution_rebate: vec![],
+            executors: vec![],
+            jitter: None,
+            on_error: ErrorPolicy::default(),
+        };
+
+        for i in 1..=10 {
+            TRIGGERS
+                .save(deps.as_mut().storage, &oracle_price_trigger(i))
+                .unwrap();
+        }
+
+        TRIGGERS
+            .save(
+                deps.as_mut().storage,
+                &Trigger {
+                    id: Uint64::from(11u64),
+                    contract_address: Addr::unchecked("manager"),
+                    msg: Binary::default(),
+                    condition: Condition::ThorchainTradingAvailable {
+                        asset: "asset-0".to_string(),
+                        max_outbound_delay: None,
+                    },
+                    execution_rebate: vec![],
+                    executors: vec![],
+                    jitter: None,
+                    on_error: ErrorPolicy::default(),
+                },
+            )
+            .unwrap();
+
+        let response = from_json::<Vec<Trigger>>(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                SchedulerQueryMsg::Filtered {
+                    filter: ConditionFilter::OraclePrice {
+                        asset: "asset-0".to_string(),
+                        start_after: Some(4),
+                    },
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            response,
+            (3..=5)
+                .map(|i| oracle_price_trigger(i * 2))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn fetches_triggers_with_nested_oracle_price_conditions() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let oracle_price = |asset: &str| Condition::OraclePrice {
+            asset: asset.to_string(),
+            direction: Direction::Above,
+            rate: Decimal::one(),
+        };
+
+        let trigger = Trigger {
+            id: Uint64::from(1u64),
+            contract_address: Addr::unchecked("manager"),
+            msg: Binary::default(),
+            condition: Condition::Composite(CompositeCondition {
+                conditions: vec![
+                    Condition::Not(Box::new(oracle_price("asset-0"))),
+                    oracle_price("asset-1"),
+                ],
+                threshold: Threshold::All,
+            }),
+            execution_rebate: vec![],
+            executors: vec![],
+            jitter: None,
+            on_error: ErrorPolicy::default(),
+        };
+
+        TRIGGERS.save(deps.as_mut().storage, &trigger).unwrap();
+
+        let filtered = |deps: Deps, asset: &str| {
+            from_json::<Vec<Trigger>>(
+                query(
+                    deps,
+                    env.clone(),
+                    SchedulerQueryMsg::Filtered {
+                        filter: ConditionFilter::OraclePrice {
+                            asset: asset.to_string(),
+                            start_after: None,
+                        },
+                        limit: None,
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(filtered(deps.as_ref(), "asset-0"), vec![trigger.clone()]);
+        assert_eq!(filtered(deps.as_ref(), "asset-1"), vec![trigger.clone()]);
+        assert_eq!(filtered(deps.as_ref(), "asset-2"), vec![]);
+
+        TRIGGERS
+            .delete(deps.as_mut().storage, trigger.id.into())
+            .unwrap();
+
+        assert_eq!(filtered(deps.as_ref(), "asset-0"), vec![]);
+        assert_eq!(filtered(deps.as_ref(), "asset-1"), vec![]);
+    }
+}
+
+#[cfg(test)]
+mod trace_trigger_tests {
+    use super::*;
+
+    use calc_rs::{
+        conditions::{Condition, ConditionTrace, ErrorPolicy},
+        scheduler::Trigger,
+    };
+    use cosmwasm_std::{
+        from_json,
+        testing::{mock_dependencies, mock_env},
+        Addr, Uint64,
+    };
+
+    #[test]
+    fn traces_trigger_condition() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        TRIGGERS
+            .save(
+                deps.as_mut().storage,
+                &Trigger {
+                    id: Uint64::from(1u64),
+                    contract_address: Addr::unchecked("manager"),
+                    msg: Binary::default(),
+                    condition: Condition::Not(Box::new(Condition::BlocksCompleted(
+                        env.block.height + 10,
+                    ))),
+                    execution_rebate: vec![],
+                    executors: vec![],
+                    jitter: None,
+                    on_error: ErrorPolicy::default(),
+                },
+            )
+            .unwrap();
+
+        let trace = from_json::<ConditionTrace>(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                SchedulerQueryMsg::TraceTrigger(Uint64::from(1u64)),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            trace,
+            ConditionTrace {
+                satisfied: true,
+                error: None,
+                observed: vec![],
+                children: vec![ConditionTrace {
+                    satisfied: false,
+                    error: None,
+                    observed: vec![("block_height".to_string(), env.block.height.to_string())],
+                    children: vec![],
+                }],
+            }
+        );
+    }
 }
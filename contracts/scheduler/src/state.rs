@@ -1,9 +1,9 @@
 use calc_rs::{
-    conditions::Condition,
+    conditions::{CompositeCondition, Condition},
     scheduler::{ConditionFilter, Trigger},
 };
-use cosmwasm_std::{Addr, Decimal, Order, StdResult, Storage, Uint64};
-use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, MultiIndex};
+use cosmwasm_std::{Addr, Decimal, Empty, Order, StdResult, Storage, Uint64};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 pub const MANAGER: Item<Addr> = Item::new("manager");
 
@@ -12,6 +12,7 @@ pub struct TriggerIndexes<'a> {
     pub block_height: MultiIndex<'a, u64, Trigger, u64>,
     pub limit_order_pair: MultiIndex<'a, Addr, Trigger, u64>,
     pub limit_order_pair_price: MultiIndex<'a, (Addr, String), Trigger, u64>,
+    pub fin_price_pair: MultiIndex<'a, Addr, Trigger, u64>,
 }
 
 impl<'a> IndexList<Trigger> for TriggerIndexes<'a> {
@@ -21,6 +22,7 @@ impl<'a> IndexList<Trigger> for TriggerIndexes<'a> {
             &self.block_height,
             &self.limit_order_pair,
             &self.limit_order_pair_price,
+            &self.fin_price_pair,
         ];
         Box::new(v.into_iter())
     }
@@ -28,10 +30,20 @@ impl<'a> IndexList<Trigger> for TriggerIndexes<'a> {
 
 pub struct TriggerStore<'a> {
     triggers: IndexedMap<u64, Trigger, TriggerIndexes<'a>>,
+    // A trigger can watch several oracle prices (e.g. inside a composite
+    // condition), which a single-keyed MultiIndex can't express
+    oracle_price_assets: Map<(String, u64), Empty>,
 }
 
 impl TriggerStore<'_> {
     pub fn save(&self, storage: &mut dyn Storage, trigger: &Trigger) -> StdResult<()> {
+        self.remove_oracle_price_assets(storage, trigger.id.into())?;
+
+        for asset in oracle_price_assets(&trigger.condition) {
+            self.oracle_price_assets
+                .save(storage, (asset, trigger.id.into()), &Empty {})?;
+        }
+
         self.triggers.save(storage, trigger.id.into(), trigger)
     }
 
@@ -89,6 +101,29 @@ impl TriggerStore<'_> {
                         Order::Ascending,
                     ),
             },
+            ConditionFilter::FinPrice {
+                pair_address,
+                start_after,
+            } => self.triggers.idx.fin_price_pair.prefix(pair_address).range(
+                storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            ),
+            ConditionFilter::OraclePrice { asset, start_after } => Box::new(
+                self.oracle_price_assets
+                    .prefix(asset)
+                    .keys(
+                        storage,
+                        start_after.map(Bound::exclusive),
+                        None,
+                        Order::Ascending,
+                    )
+                    .map(|id| -> StdResult<(u64, Trigger)> {
+                        let id = id?;
+                        Ok((id, self.triggers.load(storage, id)?))
+                    }),
+            ),
         }
         .take(limit.unwrap_or(30))
         .flat_map(|r| r.map(|(_, v)| v))
@@ -98,8 +133,32 @@ impl TriggerStore<'_> {
     }
 
     pub fn delete(&self, storage: &mut dyn Storage, id: u64) -> StdResult<()> {
+        self.remove_oracle_price_assets(storage, id)?;
         self.triggers.remove(storage, id)
     }
+
+    fn remove_oracle_price_assets(&self, storage: &mut dyn Storage, id: u64) -> StdResult<()> {
+        if let Some(trigger) = self.triggers.may_load(storage, id)? {
+            for asset in oracle_price_assets(&trigger.condition) {
+                self.oracle_price_assets.remove(storage, (asset, id));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Every asset whose oracle price the condition compares against,
+/// including those nested in composite and negated conditions.
+fn oracle_price_assets(condition: &Condition) -> Vec<String> {
+    match condition {
+        Condition::OraclePrice { asset, .. } => vec![asset.clone()],
+        Condition::Not(condition) => oracle_price_assets(condition),
+        Condition::Composite(CompositeCondition { conditions, .. }) => {
+            conditions.iter().flat_map(oracle_price_assets).collect()
+        }
+        _ => vec![],
+    }
 }
 
 pub const TRIGGERS: TriggerStore<'static> = TriggerStore {
@@ -142,6 +201,15 @@ pub const TRIGGERS: TriggerStore<'static> = TriggerStore {
                 "triggers",
                 "triggers__limit_order_pair_price",
             ),
+            fin_price_pair: MultiIndex::new(
+                |_, t| match t.condition.clone() {
+                    Condition::FinPrice { pair_address, .. } => pair_address,
+                    _ => Addr::unchecked(""),
+                },
+                "triggers",
+                "triggers__fin_price_pair",
+            ),
         },
     ),
+    oracle_price_assets: Map::new("triggers__oracle_price_assets"),
 };
@@ -3,7 +3,11 @@ use std::collections::HashSet;
 use calc_rs::{
     constants::{LOG_ERRORS_REPLY_ID, PROCESS_PAYLOAD_REPLY_ID},
     core::{Contract, ContractError, ContractResult},
+    events::DomainEvent,
+    heartbeat::{record_heartbeat, HEARTBEAT},
     manager::StrategyStatus,
+    price_history::{load_price_history, record_prices},
+    statistics::Statistics,
     strategy::{
         Indexed, Strategy, StrategyConfig, StrategyExecuteMsg, StrategyMsgPayload, StrategyQueryMsg,
     },
@@ -13,7 +17,7 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
     from_json, to_json_binary, BankMsg, Binary, Coins, Deps, DepsMut, Env, MessageInfo, Reply,
-    Response, StdError, StdResult, SubMsg, SubMsgResult,
+    Response, StdError, StdResult, Storage, SubMsg, SubMsgResult,
 };
 
 use crate::state::{ACTIVE_STRATEGY, CONFIG, DENOMS, ESCROWED, STATE, STATS};
@@ -35,6 +39,9 @@ pub fn instantiate(
     let denoms = strategy.denoms(deps.as_ref(), &env)?;
     let escrowed = strategy.escrowed(deps.as_ref(), &env)?;
 
+    // Creating the strategy is the owner's first sign of life
+    record_heartbeat(deps.storage, &env)?;
+
     let response = strategy.init(&mut deps, &env, |storage, strategy| {
         CONFIG.init(
             storage,
@@ -58,7 +65,13 @@ pub fn instantiate(
 pub struct MigrateMsg {}
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, StdError> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, StdError> {
+    // Strategies created before heartbeats existed have none stored, so
+    // start the owner's inactivity clock from the migration
+    if HEARTBEAT.may_load(deps.storage)?.is_none() {
+        record_heartbeat(deps.storage, &env)?;
+    }
+
     Ok(Response::default())
 }
 
@@ -90,6 +103,9 @@ pub fn execute(
                 return Err(ContractError::Unauthorized {});
             }
 
+            // Sample prices before execution so conditions see the latest history
+            record_prices(deps.branch(), &env, config.strategy.price_sources())?;
+
             let execute_strategy_response = config
                 .strategy
                 .activate()
@@ -98,6 +114,9 @@ pub fn execute(
                     ACTIVE_STRATEGY.save(store, &strategy)
                 })?;
 
+            // Counted after conditions are evaluated, so they see completed executions
+            record_execution(deps.storage)?;
+
             execute_strategy_response
         }
         StrategyExecuteMsg::Update(update) => {
@@ -232,6 +251,8 @@ pub fn execute(
 
             match status {
                 StrategyStatus::Active => {
+                    record_prices(deps.branch(), &env, config.strategy.price_sources())?;
+
                     let execute_strategy_response = config
                         .strategy
                         .activate()
@@ -240,6 +261,7 @@ pub fn execute(
                             ACTIVE_STRATEGY.save(store, &strategy)
                         })?;
 
+                    // Resuming is not an execution, so it is not counted as one
                     execute_strategy_response
                 }
                 // Paused & Archived are no different in terms of execution,
@@ -281,6 +303,22 @@ pub fn execute(
             // Avoid sending another clear state message
             return Ok(Response::default());
         }
+        StrategyExecuteMsg::Heartbeat => {
+            if info.sender != config.strategy.owner {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            record_heartbeat(deps.storage, &env)?;
+
+            // Nothing is executed, so there is no state to clear
+            return Ok(
+                Response::default().add_event(DomainEvent::HeartbeatRecorded {
+                    contract_address: env.contract.address,
+                    owner: info.sender,
+                    time: env.block.time,
+                }),
+            );
+        }
     };
 
     let clear_state_msg = SubMsg::reply_always(
@@ -292,6 +330,15 @@ pub fn execute(
     Ok(response.add_submessage(clear_state_msg))
 }
 
+fn record_execution(storage: &mut dyn Storage) -> StdResult<Statistics> {
+    STATS.update(storage, |stats| {
+        Ok(Statistics {
+            executions: stats.executions + 1,
+            ..stats
+        })
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> ContractResult {
     let response = Response::new().add_attribute("reply_id", reply.id.to_string());
@@ -348,6 +395,13 @@ pub fn query(deps: Deps, env: Env, msg: StrategyQueryMsg) -> StdResult<Binary> {
 
             to_json_binary(&balances.to_vec())
         }
+        StrategyQueryMsg::PriceHistory { source } => {
+            to_json_binary(&load_price_history(deps, &source)?.series())
+        }
+        StrategyQueryMsg::Heartbeat {} => to_json_binary(&HEARTBEAT.load(deps.storage)?),
+        StrategyQueryMsg::TraceCondition { condition } => {
+            to_json_binary(&condition.trace(deps, &env))
+        }
     }
 }
 
@@ -374,6 +428,7 @@ mod tests {
         let strategy = Strategy {
             owner: owner.clone(),
             action: Action::Many(vec![]),
+            definitions: vec![],
             state: Indexed {
                 contract_address: env.contract.address.clone(),
             },
@@ -387,6 +442,7 @@ mod tests {
                     strategy: Strategy {
                         owner: strategy.owner.clone(),
                         action: strategy.action.clone(),
+                        definitions: strategy.definitions.clone(),
                         state: Committed,
                     },
                     denoms: HashSet::new(),
@@ -436,6 +492,7 @@ mod tests {
         let strategy = Strategy {
             owner: owner.clone(),
             action: Action::Many(vec![]),
+            definitions: vec![],
             state: Indexed {
                 contract_address: env.contract.address.clone(),
             },
@@ -449,6 +506,7 @@ mod tests {
                     strategy: Strategy {
                         owner: strategy.owner.clone(),
                         action: strategy.action.clone(),
+                        definitions: strategy.definitions.clone(),
                         state: Committed,
                     },
                     denoms: HashSet::new(),
@@ -504,6 +562,7 @@ mod tests {
         let strategy = Strategy {
             owner: owner.clone(),
             action: Action::Many(vec![]),
+            definitions: vec![],
             state: Indexed {
                 contract_address: env.contract.address.clone(),
             },
@@ -517,6 +576,7 @@ mod tests {
                     strategy: Strategy {
                         owner: strategy.owner.clone(),
                         action: strategy.action.clone(),
+                        definitions: strategy.definitions.clone(),
                         state: Committed,
                     },
                     denoms: HashSet::new(),
@@ -562,6 +622,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_only_owner_can_invoke_heartbeat() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+        let owner = deps.api.addr_make("owner");
+        let manager = Addr::unchecked("manager");
+
+        CONFIG
+            .init(
+                deps.as_mut().storage,
+                StrategyConfig {
+                    manager: manager.clone(),
+                    strategy: Strategy {
+                        owner: owner.clone(),
+                        action: Action::Many(vec![]),
+                        definitions: vec![],
+                        state: Committed,
+                    },
+                    denoms: HashSet::new(),
+                    escrowed: HashSet::new(),
+                },
+            )
+            .unwrap();
+
+        record_heartbeat(deps.as_mut().storage, &env).unwrap();
+
+        env.block.time = env.block.time.plus_seconds(3_600);
+
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&manager, &[]),
+                StrategyExecuteMsg::Heartbeat
+            ),
+            Err(ContractError::Unauthorized {})
+        );
+
+        assert_eq!(
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                message_info(&env.contract.address, &[]),
+                StrategyExecuteMsg::Heartbeat
+            ),
+            Err(ContractError::Unauthorized {})
+        );
+
+        assert_eq!(
+            HEARTBEAT.load(deps.as_ref().storage).unwrap(),
+            env.block.time.minus_seconds(3_600)
+        );
+
+        assert!(execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&owner, &[]),
+            StrategyExecuteMsg::Heartbeat
+        )
+        .is_ok());
+
+        assert_eq!(
+            HEARTBEAT.load(deps.as_ref().storage).unwrap(),
+            env.block.time
+        );
+    }
+
+    #[test]
+    fn test_migrate_seeds_missing_heartbeat() {
+        let mut deps = mock_dependencies();
+        let mut env = mock_env();
+
+        assert!(HEARTBEAT.may_load(deps.as_ref().storage).unwrap().is_none());
+
+        migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+        assert_eq!(
+            HEARTBEAT.load(deps.as_ref().storage).unwrap(),
+            env.block.time
+        );
+
+        let seeded_at = env.block.time;
+        env.block.time = env.block.time.plus_seconds(3_600);
+
+        migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+        assert_eq!(HEARTBEAT.load(deps.as_ref().storage).unwrap(), seeded_at);
+    }
+
     #[test]
     fn test_only_manager_can_invoke_update_status() {
         let mut deps = mock_dependencies();
@@ -572,6 +721,7 @@ mod tests {
         let strategy = Strategy {
             owner: owner.clone(),
             action: Action::Many(vec![]),
+            definitions: vec![],
             state: Indexed {
                 contract_address: env.contract.address.clone(),
             },
@@ -585,6 +735,7 @@ mod tests {
                     strategy: Strategy {
                         owner: strategy.owner.clone(),
                         action: strategy.action.clone(),
+                        definitions: strategy.definitions.clone(),
                         state: Committed,
                     },
                     denoms: HashSet::new(),
@@ -642,6 +793,7 @@ mod tests {
         let strategy = Strategy {
             owner: owner.clone(),
             action: Action::Many(vec![]),
+            definitions: vec![],
             state: Indexed {
                 contract_address: env.contract.address.clone(),
             },
@@ -655,6 +807,7 @@ mod tests {
                     strategy: Strategy {
                         owner: strategy.owner.clone(),
                         action: strategy.action.clone(),
+                        definitions: strategy.definitions.clone(),
                         state: Committed,
                     },
                     denoms: HashSet::new(),
@@ -669,6 +822,7 @@ mod tests {
                 &Strategy {
                     owner: strategy.owner.clone(),
                     action: strategy.action.clone(),
+                    definitions: strategy.definitions.clone(),
                     state: Active,
                 },
             )
@@ -723,6 +877,7 @@ mod tests {
         let strategy = Strategy {
             owner: owner.clone(),
             action: Action::Many(vec![]),
+            definitions: vec![],
             state: Indexed {
                 contract_address: env.contract.address.clone(),
             },
@@ -736,6 +891,7 @@ mod tests {
                     strategy: Strategy {
                         owner: strategy.owner.clone(),
                         action: strategy.action.clone(),
+                        definitions: strategy.definitions.clone(),
                         state: Committed,
                     },
                     denoms: HashSet::new(),
@@ -813,6 +969,7 @@ mod tests {
         let strategy = Strategy {
             owner: owner.clone(),
             action: Action::Many(vec![]),
+            definitions: vec![],
             state: Indexed {
                 contract_address: env.contract.address.clone(),
             },
@@ -826,6 +983,7 @@ mod tests {
                     strategy: Strategy {
                         owner: strategy.owner.clone(),
                         action: strategy.action.clone(),
+                        definitions: strategy.definitions.clone(),
                         state: Committed,
                     },
                     denoms: HashSet::new(),
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use calc_rs::{
-    statistics::Statistics,
+    statistics::{Statistics, STATISTICS},
     strategy::{Active, Committed, Strategy, StrategyConfig, StrategyExecuteMsg},
 };
 use cosmwasm_schema::cw_serde;
@@ -11,7 +11,7 @@ use cw_storage_plus::Item;
 pub const DENOMS: Item<HashSet<String>> = Item::new("denoms");
 pub const ESCROWED: Item<HashSet<String>> = Item::new("escrowed");
 pub const STATE: Item<StrategyExecuteMsg> = Item::new("state");
-pub const STATS: Item<Statistics> = Item::new("stats");
+pub const STATS: Item<Statistics> = STATISTICS;
 
 pub const ACTIVE_STRATEGY: Item<Strategy<Active>> = Item::new("active_strategy");
 
@@ -79,6 +79,22 @@ pub fn mock_pool(request: Binary) -> Result<Binary, Error> {
     }
 }
 
+pub fn mock_mimir(request: Binary) -> Result<Binary, Error> {
+    let req = proto::types::QueryMimirWithKeyRequest::decode(request.as_slice()).unwrap();
+
+    // ETH trading is halted from genesis, everything else is live
+    let value = match req.key.as_str() {
+        "HALTETHTRADING" => 1,
+        _ => 0,
+    };
+
+    let mut buf = Vec::new();
+    proto::types::QueryMimirWithKeyResponse { value }
+        .encode(&mut buf)
+        .unwrap();
+    Ok(buf.into())
+}
+
 pub fn mock_quote_response() -> AnyResult<Binary> {
     let quote = QueryQuoteSwapResponse {
         inbound_address: "sthor17pfp4qvy5vrmtjar7kntachm0cfm9m9azl3jka".to_string(),
@@ -1,14 +1,17 @@
 use std::{collections::HashSet, vec};
 
 use calc_rs::{
+    conditions::{Condition, ConditionTrace},
     manager::{Affiliate, ManagerConfig, ManagerExecuteMsg, ManagerQueryMsg, StrategyHandle},
+    price::PriceSource,
+    price_history::PriceSample,
     scheduler::{
         ConditionFilter, SchedulerExecuteMsg, SchedulerInstantiateMsg, SchedulerQueryMsg, Trigger,
     },
     statistics::Statistics,
     strategy::{Json, Strategy, StrategyConfig, StrategyExecuteMsg, StrategyQueryMsg},
 };
-use cosmwasm_std::{Addr, Coin, Decimal, StdError, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, StdError, Timestamp, Uint128};
 use cw_multi_test::{error::AnyResult, AppResponse, BasicAppBuilder, ContractWrapper, Executor};
 use rujira_rs::fin::{
     ConfigResponse, Denoms, ExecuteMsg, InstantiateMsg, OrdersResponse, Price, QueryMsg, Side, Tick,
@@ -342,6 +345,48 @@ impl CalcTestApp {
             .unwrap()
     }
 
+    pub fn query_price_history(
+        &self,
+        strategy_addr: &Addr,
+        source: PriceSource,
+    ) -> Vec<PriceSample> {
+        self.app
+            .wrap()
+            .query_wasm_smart(strategy_addr, &StrategyQueryMsg::PriceHistory { source })
+            .unwrap()
+    }
+
+    pub fn query_heartbeat(&self, strategy_addr: &Addr) -> Timestamp {
+        self.app
+            .wrap()
+            .query_wasm_smart(strategy_addr, &StrategyQueryMsg::Heartbeat {})
+            .unwrap()
+    }
+
+    pub fn query_strategy_condition_trace(
+        &self,
+        strategy_addr: &Addr,
+        condition: Condition,
+    ) -> ConditionTrace {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                strategy_addr,
+                &StrategyQueryMsg::TraceCondition { condition },
+            )
+            .unwrap()
+    }
+
+    pub fn query_scheduler_condition_trace(&self, condition: Condition) -> ConditionTrace {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.scheduler_addr,
+                &SchedulerQueryMsg::TraceCondition { condition },
+            )
+            .unwrap()
+    }
+
     pub fn query_strategy_balances(
         &self,
         strategy_addr: &Addr,
@@ -407,4 +452,13 @@ impl CalcTestApp {
             &[],
         )
     }
+
+    pub fn heartbeat(&mut self, sender: &Addr, strategy_addr: &Addr) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            strategy_addr.clone(),
+            &StrategyExecuteMsg::Heartbeat,
+            &[],
+        )
+    }
 }
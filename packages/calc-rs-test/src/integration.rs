@@ -2,15 +2,31 @@
 mod integration_tests {
     use calc_rs::{
         actions::{
+            basket::{Basket, BasketLeg},
+            call::{Call, CallFunds},
             distribution::{Destination, Distribution, Recipient},
+            grid::Grid,
             limit_order::{Direction, Offset, StaleOrder},
-            swaps::{fin::FinRoute, thor::ThorchainRoute},
+            liquidity::{Liquidity, LiquidityOperation},
+            market_maker::MarketMaker,
+            protective_exit::{ProtectiveExit, TakeProfit},
+            rebalance::Rebalance,
+            reference::Ref,
+            swaps::{
+                fin::FinRoute,
+                thor::{ThorchainRoute, TradingCheck},
+            },
         },
-        conditions::CompositeCondition,
+        conditions::{CompositeCondition, ErrorPolicy, OutboundDelay},
         constants::BASE_FEE_BPS,
         core::Threshold,
         manager::Affiliate,
-        scheduler::{ConditionFilter, CreateTriggerMsg, SchedulerExecuteMsg},
+        price::{BookSide, PriceSource, Valuation},
+        price_history::PriceSample,
+        query::Comparator,
+        scheduler::{
+            ConditionFilter, CreateTriggerMsg, SchedulerExecuteMsg, SchedulerQueryMsg, Trigger,
+        },
         strategy::Committed,
     };
 
@@ -23,13 +39,14 @@ mod integration_tests {
             schedule::Schedule,
             swaps::swap::{Swap, SwapAmountAdjustment, SwapRoute},
         },
-        cadence::Cadence,
+        cadence::{Cadence, Moment},
         conditions::Condition,
         statistics::Statistics,
         strategy::{Strategy, StrategyConfig},
     };
-    use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, Decimal, Uint128};
-    use rujira_rs::fin::{Price, Side};
+    use cosmwasm_std::{to_json_binary, to_json_string, Addr, Binary, Coin, Decimal, Uint128};
+    use cw_multi_test::AppResponse;
+    use rujira_rs::fin::{ExecuteMsg as FinExecuteMsg, Price, Side};
 
     use calc_rs::actions::limit_order::{LimitOrder, OrderPriceStrategy};
     use calc_rs::manager::StrategyStatus;
@@ -61,6 +78,7 @@ mod integration_tests {
                 affiliate_code: Some("rj".to_string()),
                 affiliate_bps: Some(10),
                 latest_swap: None,
+                trading_check: None,
             })],
             swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
             minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
@@ -94,15 +112,110 @@ mod integration_tests {
         }
     }
 
+    fn default_market_maker_action(harness: &CalcTestApp) -> MarketMaker {
+        MarketMaker {
+            pair_address: harness.fin_addr.clone(),
+            spread_bps: 1_000,
+            size: Uint128::new(10_000),
+            max_skew_bps: 2_000,
+            tolerance_bps: 100,
+            bid: None,
+            ask: None,
+        }
+    }
+
+    fn default_grid_action(harness: &CalcTestApp) -> Grid {
+        Grid {
+            pair_address: harness.fin_addr.clone(),
+            lower_price: Decimal::percent(90),
+            upper_price: Decimal::percent(110),
+            levels: 5,
+            size: Uint128::new(10_000),
+            orders: vec![],
+            realised_profit: Uint128::zero(),
+        }
+    }
+
+    fn default_rebalance_action(harness: &CalcTestApp) -> Rebalance {
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+        Rebalance {
+            targets: vec![
+                (fin_pair.denoms.base().to_string(), Decimal::percent(50)),
+                (fin_pair.denoms.quote().to_string(), Decimal::percent(50)),
+            ],
+            valuation_denom: fin_pair.denoms.quote().to_string(),
+            drift_tolerance_bps: 100,
+            maximum_slippage_bps: 101,
+            routes: vec![SwapRoute::Fin(FinRoute {
+                pair_address: harness.fin_addr.clone(),
+            })],
+        }
+    }
+
+    fn default_basket_action(harness: &CalcTestApp) -> Basket {
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+        Basket {
+            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
+            maximum_slippage_bps: 101,
+            legs: vec![
+                BasketLeg {
+                    weight: Decimal::percent(50),
+                    minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
+                    routes: vec![SwapRoute::Fin(FinRoute {
+                        pair_address: harness.fin_addr.clone(),
+                    })],
+                },
+                BasketLeg {
+                    weight: Decimal::percent(50),
+                    minimum_receive_amount: Coin::new(1u128, "btc-btc"),
+                    routes: vec![SwapRoute::Thorchain(ThorchainRoute {
+                        streaming_interval: None,
+                        max_streaming_quantity: None,
+                        affiliate_code: None,
+                        affiliate_bps: None,
+                        latest_swap: None,
+                        trading_check: None,
+                    })],
+                },
+            ],
+        }
+    }
+
+    fn default_protective_exit_action(harness: &CalcTestApp) -> ProtectiveExit {
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+        ProtectiveExit {
+            source: PriceSource::Fin {
+                pair_address: harness.fin_addr.clone(),
+            },
+            sell_denom: fin_pair.denoms.base().to_string(),
+            receive_denom: fin_pair.denoms.quote().to_string(),
+            entry_price: None,
+            stop_loss_bps: Some(1_000),
+            trailing: false,
+            take_profits: vec![],
+            maximum_slippage_bps: 101,
+            routes: vec![SwapRoute::Fin(FinRoute {
+                pair_address: harness.fin_addr.clone(),
+            })],
+            highest_price: None,
+            exited: false,
+        }
+    }
+
     fn default_schedule_action(harness: &CalcTestApp) -> Schedule {
         Schedule {
             scheduler: harness.scheduler_addr.clone(),
             contract_address: harness.manager_addr.clone(),
             msg: None,
             execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
             cadence: Cadence::Blocks {
                 interval: 5,
                 previous: None,
+                jitter: None,
             },
             action: Box::new(Action::LimitOrder(default_limit_order_action(harness))),
         }
@@ -115,6 +228,7 @@ mod integration_tests {
             condition: Condition::StrategyBalanceAvailable {
                 amount: Coin::new(1000u128, fin_pair.denoms.base()),
             },
+            on_error: ErrorPolicy::default(),
         }
     }
 
@@ -150,6 +264,7 @@ mod integration_tests {
             strategy: Strategy {
                 owner: owner.clone(),
                 action: Action::Swap(swap_action.clone()),
+                definitions: vec![],
                 state: Committed,
             },
             denoms: HashSet::from([
@@ -198,7 +313,9 @@ mod integration_tests {
                     amount: Coin::new(1000u128, fin_pair.denoms.base()),
                 },
                 action: Box::new(Action::Swap(default_swap_action(&harness))),
+                on_error: ErrorPolicy::default(),
             })),
+            on_error: ErrorPolicy::default(),
         };
 
         assert!(StrategyBuilder::new(&mut harness)
@@ -222,9 +339,14 @@ mod integration_tests {
                 msg: None,
                 cadence: Cadence::Blocks {
                     interval: 10,
-                    previous: None
+                    previous: None,
+                    jitter: None,
                 },
                 execution_rebate: vec![],
+                until: None,
+                max_runs: None,
+                runs: 0,
+                completed: false,
                 action: Box::new(Action::Schedule(nested_schedule_action)),
             }))
             .try_instantiate(&[])
@@ -378,6 +500,7 @@ mod integration_tests {
                 affiliate_code: Some("rj".to_string()),
                 affiliate_bps: Some(10),
                 latest_swap: None,
+                trading_check: None,
             })],
             ..default_swap
         };
@@ -401,6 +524,7 @@ mod integration_tests {
                 affiliate_code: Some("rj".to_string()),
                 affiliate_bps: Some(10),
                 latest_swap: None,
+                trading_check: None,
             })],
             ..default_swap
         };
@@ -424,6 +548,7 @@ mod integration_tests {
                 affiliate_code: Some("rj".to_string()),
                 affiliate_bps: Some(10),
                 latest_swap: None,
+                trading_check: None,
             })],
             ..default_swap
         };
@@ -450,6 +575,59 @@ mod integration_tests {
         strategy.assert_swapped(vec![swap_action.swap_amount]);
     }
 
+    #[test]
+    fn test_execute_thor_swap_action_with_trading_check_swaps_when_available() {
+        let mut harness = CalcTestApp::setup();
+        let default_swap = default_swap_action_thor(&harness);
+
+        let swap_action = Swap {
+            minimum_receive_amount: Coin::new(1u128, "btc-btc"),
+            routes: vec![SwapRoute::Thorchain(ThorchainRoute {
+                streaming_interval: Some(2),
+                max_streaming_quantity: Some(1000),
+                affiliate_code: Some("rj".to_string()),
+                affiliate_bps: Some(10),
+                latest_swap: None,
+                trading_check: Some(TradingCheck {
+                    max_outbound_delay: Some(Duration::from_secs(600)),
+                }),
+            })],
+            ..default_swap
+        };
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Swap(swap_action.clone()))
+            .instantiate(&[swap_action.swap_amount.clone()])
+            .assert_swapped(vec![swap_action.swap_amount]);
+    }
+
+    #[test]
+    fn test_execute_thor_swap_action_with_trading_check_skips_when_halted() {
+        let mut harness = CalcTestApp::setup();
+        let default_swap = default_swap_action_thor(&harness);
+
+        // ETH trading is halted in the mocked mimir
+        let swap_action = Swap {
+            routes: vec![SwapRoute::Thorchain(ThorchainRoute {
+                streaming_interval: Some(2),
+                max_streaming_quantity: Some(1000),
+                affiliate_code: Some("rj".to_string()),
+                affiliate_bps: Some(10),
+                latest_swap: None,
+                trading_check: Some(TradingCheck {
+                    max_outbound_delay: None,
+                }),
+            })],
+            ..default_swap
+        };
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Swap(swap_action.clone()))
+            .instantiate(&[swap_action.swap_amount.clone()])
+            .assert_swapped(vec![])
+            .assert_bank_balances(vec![swap_action.swap_amount]);
+    }
+
     #[test]
     fn test_execute_thor_swap_action_with_swap_amount_scaled_to_zero_skips() {
         let mut harness = CalcTestApp::setup();
@@ -699,6 +877,7 @@ mod integration_tests {
                 strategy: Strategy {
                     owner: owner.clone(),
                     action: Action::Swap(swap_action.clone()),
+                    definitions: vec![],
                     state: Committed,
                 },
                 denoms: HashSet::from([
@@ -1025,6 +1204,7 @@ mod integration_tests {
                     affiliate_code: None,
                     affiliate_bps: None,
                     latest_swap: None,
+                    trading_check: None,
                 }),
             ],
         };
@@ -1681,6 +1861,7 @@ mod integration_tests {
                     owner,
                     // asserts that we remove the current order
                     action: Action::LimitOrder(order_action),
+                    definitions: vec![],
                     state: Committed,
                 },
                 denoms: HashSet::from([
@@ -1784,1164 +1965,3511 @@ mod integration_tests {
             .assert_bank_balance(&Coin::new(0u128, order_action.bid_denom.clone()));
     }
 
-    // Many Action tests
+    // MarketMaker Action tests
 
     #[test]
-    fn test_instantiate_empty_many_action_fails() {
+    fn test_instantiate_market_maker_action_with_invalid_spread_fails() {
         let mut harness = CalcTestApp::setup();
 
+        let market_maker_action = MarketMaker {
+            spread_bps: 0,
+            ..default_market_maker_action(&harness)
+        };
+
         let result = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Many(vec![]))
+            .with_action(Action::MarketMaker(market_maker_action))
             .try_instantiate(&[]);
 
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_instantiate_many_action_with_too_many_actions_fails() {
+    fn test_instantiate_market_maker_action_with_preset_orders_fails() {
         let mut harness = CalcTestApp::setup();
 
-        let actions = (1..=11)
-            .map(|_| Action::LimitOrder(default_limit_order_action(&harness)))
-            .collect::<Vec<_>>();
+        let market_maker_action = MarketMaker {
+            bid: Some(StaleOrder {
+                price: Decimal::one(),
+            }),
+            ..default_market_maker_action(&harness)
+        };
 
         let result = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Many(actions))
+            .with_action(Action::MarketMaker(market_maker_action))
             .try_instantiate(&[]);
 
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_instantiate_many_action_succeeds() {
+    fn test_instantiate_market_maker_action_quotes_both_sides_around_mid_price() {
         let mut harness = CalcTestApp::setup();
         let pair = harness.query_fin_config(&harness.fin_addr);
-
-        let actions = vec![
-            Action::Swap(default_swap_action(&harness)),
-            Action::Swap(default_swap_action(&harness)),
-        ];
-
-        let manager = harness.manager_addr.clone();
-        let owner = harness.owner.clone();
+        let market_maker_action = default_market_maker_action(&harness);
 
         let mut strategy = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Many(actions.clone()))
-            .instantiate(&[]);
+            .with_action(Action::MarketMaker(market_maker_action.clone()))
+            .instantiate(&[
+                Coin::new(1_000_000u128, pair.denoms.base()),
+                Coin::new(1_000_000u128, pair.denoms.quote()),
+            ]);
 
-        strategy.assert_config(StrategyConfig {
-            manager,
-            strategy: Strategy {
-                action: Action::Many(actions),
-                state: Committed,
-                owner,
-            },
-            denoms: HashSet::from([
-                pair.denoms.quote().to_string(),
-                pair.denoms.base().to_string(),
-            ]),
-            escrowed: HashSet::from([pair.denoms.quote().to_string()]),
-        });
+        strategy
+            .assert_fin_orders(
+                &market_maker_action.pair_address,
+                vec![
+                    (
+                        Side::Base,
+                        Decimal::percent(105), // price
+                        Uint128::new(10_000),  // offer
+                        Uint128::new(10_000),  // remaining
+                        Uint128::zero(),       // filled
+                    ),
+                    (
+                        Side::Quote,
+                        Decimal::percent(95), // price
+                        Uint128::new(9_500),  // offer
+                        Uint128::new(9_500),  // remaining
+                        Uint128::zero(),      // filled
+                    ),
+                ],
+            )
+            .assert_strategy_balance(&Coin::new(1_000_000u128, pair.denoms.base()))
+            .assert_strategy_balance(&Coin::new(1_000_000u128, pair.denoms.quote()))
+            .execute()
+            .assert_fin_orders(
+                &market_maker_action.pair_address,
+                vec![
+                    (
+                        Side::Base,
+                        Decimal::percent(105), // price
+                        Uint128::new(10_000),  // offer
+                        Uint128::new(10_000),  // remaining
+                        Uint128::zero(),       // filled
+                    ),
+                    (
+                        Side::Quote,
+                        Decimal::percent(95), // price
+                        Uint128::new(9_500),  // offer
+                        Uint128::new(9_500),  // remaining
+                        Uint128::zero(),      // filled
+                    ),
+                ],
+            );
     }
 
     #[test]
-    fn test_execute_many_action_executes_actions_in_order() {
+    fn test_execute_market_maker_action_with_skewed_inventory_only_quotes_one_side() {
         let mut harness = CalcTestApp::setup();
-        let pair_address = harness.fin_addr.clone();
-
-        let fin_swap_action = default_swap_action(&harness);
-        let limit_order_action = default_limit_order_action(&harness);
-
-        let mut strategy = StrategyBuilder::new(&mut harness)
-            .with_action(
-                Action::Many(vec![
-                    Action::Swap(fin_swap_action.clone()),
-                    Action::LimitOrder(limit_order_action.clone()),
-                ])
-                .clone(),
-            )
-            .instantiate(&[fin_swap_action.swap_amount.clone()]);
-
-        strategy
-            .assert_bank_balance(&Coin::new(0u128, fin_swap_action.swap_amount.denom.clone()))
-            .assert_fin_orders(&pair_address, vec![])
-            .assert_stats(Statistics {
-                debited: vec![fin_swap_action.swap_amount.clone()],
-                ..Statistics::default()
-            });
+        let pair = harness.query_fin_config(&harness.fin_addr);
+        let market_maker_action = default_market_maker_action(&harness);
 
         let mut strategy = StrategyBuilder::new(&mut harness)
-            .with_action(
-                Action::Many(vec![
-                    Action::LimitOrder(limit_order_action.clone()),
-                    Action::Swap(fin_swap_action.clone()),
-                ])
-                .clone(),
-            )
-            .instantiate(&[fin_swap_action.swap_amount.clone()]);
+            .with_action(Action::MarketMaker(market_maker_action.clone()))
+            .instantiate(&[Coin::new(1_000_000u128, pair.denoms.base())]);
 
-        strategy
-            .assert_bank_balance(&Coin::new(0u128, fin_swap_action.swap_amount.denom.clone()))
-            .assert_fin_orders(
-                &pair_address,
-                vec![(
-                    limit_order_action.side,
-                    Decimal::one(),                     // price
-                    fin_swap_action.swap_amount.amount, // offer
-                    fin_swap_action.swap_amount.amount, // remaining
-                    Uint128::zero(),                    // filled
-                )],
-            )
-            .assert_stats(Statistics::default());
+        strategy.assert_fin_orders(
+            &market_maker_action.pair_address,
+            vec![(
+                Side::Base,
+                Decimal::percent(105), // price
+                Uint128::new(10_000),  // offer
+                Uint128::new(10_000),  // remaining
+                Uint128::zero(),       // filled
+            )],
+        );
     }
 
     #[test]
-    fn test_instantiate_many_action_with_nested_many_action_succeeds() {
+    fn test_pause_market_maker_action_withdraws_both_sides() {
         let mut harness = CalcTestApp::setup();
         let pair = harness.query_fin_config(&harness.fin_addr);
-
-        let actions = vec![
-            Action::Swap(default_swap_action(&harness)),
-            Action::Many(vec![Action::LimitOrder(default_limit_order_action(
-                &harness,
-            ))]),
-        ];
-
-        let manager = harness.manager_addr.clone();
-        let owner = harness.owner.clone();
+        let market_maker_action = default_market_maker_action(&harness);
 
         let mut strategy = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Many(actions.clone()))
-            .instantiate(&[]);
+            .with_action(Action::MarketMaker(market_maker_action.clone()))
+            .instantiate(&[
+                Coin::new(1_000_000u128, pair.denoms.base()),
+                Coin::new(1_000_000u128, pair.denoms.quote()),
+            ]);
 
-        strategy.assert_config(StrategyConfig {
-            manager,
-            strategy: Strategy {
-                action: Action::Many(actions),
-                state: Committed,
-                owner,
-            },
-            denoms: HashSet::from([
-                pair.denoms.quote().to_string(),
-                pair.denoms.base().to_string(),
-            ]),
-            escrowed: HashSet::from([pair.denoms.quote().to_string()]),
-        });
+        strategy
+            .pause()
+            .assert_fin_orders(&market_maker_action.pair_address, vec![])
+            .assert_bank_balance(&Coin::new(1_000_000u128, pair.denoms.base()))
+            .assert_bank_balance(&Coin::new(1_000_000u128, pair.denoms.quote()));
     }
 
-    // Distribution Action tests
+    // Grid Action tests
 
     #[test]
-    fn test_instantiate_distribution_with_empty_denoms_fails() {
+    fn test_instantiate_grid_action_with_inverted_price_range_fails() {
         let mut harness = CalcTestApp::setup();
-        let distribution_action = Distribution {
-            denoms: vec![],
-            ..default_distribution_action(&harness)
+
+        let grid_action = Grid {
+            lower_price: Decimal::percent(110),
+            upper_price: Decimal::percent(90),
+            ..default_grid_action(&harness)
         };
 
         let result = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Distribute(distribution_action))
+            .with_action(Action::Grid(grid_action))
             .try_instantiate(&[]);
 
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_instantiate_distribution_with_empty_destinations_fails() {
+    fn test_instantiate_grid_action_with_too_few_levels_fails() {
         let mut harness = CalcTestApp::setup();
-        let distribution_action = Distribution {
-            destinations: vec![],
-            ..default_distribution_action(&harness)
+
+        let grid_action = Grid {
+            levels: 1,
+            ..default_grid_action(&harness)
         };
 
         let result = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Distribute(distribution_action))
+            .with_action(Action::Grid(grid_action))
             .try_instantiate(&[]);
 
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_instantiate_distribution_with_zero_shares_destination_fails() {
+    fn test_instantiate_grid_action_places_orders_either_side_of_mid_price() {
         let mut harness = CalcTestApp::setup();
-        let distribution_action = Distribution {
-            destinations: vec![
-                Destination {
-                    recipient: Recipient::Bank {
-                        address: harness.owner.clone(),
-                    },
-                    shares: Uint128::new(10_000),
-                    label: None,
-                },
-                Destination {
-                    recipient: Recipient::Bank {
-                        address: harness.owner.clone(),
-                    },
-                    shares: Uint128::zero(),
-                    label: None,
-                },
-            ],
-            ..default_distribution_action(&harness)
-        };
-
-        let result = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Distribute(distribution_action))
-            .try_instantiate(&[]);
+        let pair = harness.query_fin_config(&harness.fin_addr);
+        let grid_action = default_grid_action(&harness);
 
-        assert!(result.is_err());
-    }
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Grid(grid_action.clone()))
+            .instantiate(&[
+                Coin::new(1_000_000u128, pair.denoms.base()),
+                Coin::new(1_000_000u128, pair.denoms.quote()),
+            ]);
 
-    #[test]
-    fn test_instantiate_distribution_with_invalid_bank_recipient_fails() {
-        let mut harness = CalcTestApp::setup();
-        let distribution_action = Distribution {
-            destinations: vec![Destination {
-                recipient: Recipient::Bank {
-                    address: Addr::unchecked("test_invalid_recipient"),
-                },
-                shares: Uint128::new(10_000),
-                label: None,
-            }],
-            ..default_distribution_action(&harness)
+        strategy
+            .assert_fin_orders(
+                &grid_action.pair_address,
+                vec![
+                    (
+                        Side::Base,
+                        Decimal::percent(105), // price
+                        Uint128::new(10_000),  // offer
+                        Uint128::new(10_000),  // remaining
+                        Uint128::zero(),       // filled
+                    ),
+                    (
+                        Side::Base,
+                        Decimal::percent(110), // price
+                        Uint128::new(10_000),  // offer
+                        Uint128::new(10_000),  // remaining
+                        Uint128::zero(),       // filled
+                    ),
+                    (
+                        Side::Quote,
+                        Decimal::percent(90), // price
+                        Uint128::new(9_000),  // offer
+                        Uint128::new(9_000),  // remaining
+                        Uint128::zero(),      // filled
+                    ),
+                    (
+                        Side::Quote,
+                        Decimal::percent(95), // price
+                        Uint128::new(9_500),  // offer
+                        Uint128::new(9_500),  // remaining
+                        Uint128::zero(),      // filled
+                    ),
+                ],
+            )
+            .assert_strategy_balance(&Coin::new(1_000_000u128, pair.denoms.base()))
+            .assert_strategy_balance(&Coin::new(1_000_000u128, pair.denoms.quote()));
+    }
+
+    #[test]
+    fn test_instantiate_grid_action_with_insufficient_balance_skips_levels() {
+        let mut harness = CalcTestApp::setup();
+        let pair = harness.query_fin_config(&harness.fin_addr);
+        let grid_action = default_grid_action(&harness);
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Grid(grid_action.clone()))
+            .instantiate(&[Coin::new(15_000u128, pair.denoms.base())]);
+
+        strategy.assert_fin_orders(
+            &grid_action.pair_address,
+            vec![(
+                Side::Base,
+                Decimal::percent(105), // price
+                Uint128::new(10_000),  // offer
+                Uint128::new(10_000),  // remaining
+                Uint128::zero(),       // filled
+            )],
+        );
+    }
+
+    #[test]
+    fn test_pause_grid_action_withdraws_all_levels() {
+        let mut harness = CalcTestApp::setup();
+        let pair = harness.query_fin_config(&harness.fin_addr);
+        let grid_action = default_grid_action(&harness);
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Grid(grid_action.clone()))
+            .instantiate(&[
+                Coin::new(1_000_000u128, pair.denoms.base()),
+                Coin::new(1_000_000u128, pair.denoms.quote()),
+            ]);
+
+        strategy
+            .pause()
+            .assert_fin_orders(&grid_action.pair_address, vec![])
+            .assert_bank_balance(&Coin::new(1_000_000u128, pair.denoms.base()))
+            .assert_bank_balance(&Coin::new(1_000_000u128, pair.denoms.quote()));
+    }
+
+    #[test]
+    fn test_execute_grid_action_tops_up_partially_filled_level() {
+        let mut harness = CalcTestApp::setup();
+        let pair = harness.query_fin_config(&harness.fin_addr);
+        let owner = harness.owner.clone();
+        let unknown = harness.unknown.clone();
+
+        let grid_action = Grid {
+            lower_price: Decimal::percent(95),
+            upper_price: Decimal::percent(105),
+            levels: 2,
+            ..default_grid_action(&harness)
         };
 
-        let result = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Distribute(distribution_action))
-            .try_instantiate(&[]);
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Grid(grid_action.clone()))
+            .instantiate(&[
+                Coin::new(1_000_000u128, pair.denoms.base()),
+                Coin::new(1_000_000u128, pair.denoms.quote()),
+            ]);
 
-        assert!(result.is_err());
+        // Clear the seeded book so only the grid orders can be matched
+        strategy
+            .harness
+            .set_fin_orders(
+                &owner,
+                &grid_action.pair_address,
+                vec![
+                    (
+                        Side::Base,
+                        Price::Fixed(Decimal::percent(101)),
+                        Some(Uint128::zero()),
+                    ),
+                    (
+                        Side::Quote,
+                        Price::Fixed(Decimal::percent(99)),
+                        Some(Uint128::zero()),
+                    ),
+                ],
+                &[],
+            )
+            .unwrap();
+
+        // Partially fill the sell at 1.05
+        strategy
+            .harness
+            .set_fin_orders(
+                &unknown,
+                &grid_action.pair_address,
+                vec![(
+                    Side::Quote,
+                    Price::Fixed(Decimal::percent(105)),
+                    Some(Uint128::new(4_200)),
+                )],
+                &[Coin::new(4_200u128, pair.denoms.quote())],
+            )
+            .unwrap();
+
+        // Completely fill the buy at 0.95
+        strategy
+            .harness
+            .set_fin_orders(
+                &unknown,
+                &grid_action.pair_address,
+                vec![(
+                    Side::Base,
+                    Price::Fixed(Decimal::percent(95)),
+                    Some(Uint128::new(10_000)),
+                )],
+                &[Coin::new(10_000u128, pair.denoms.base())],
+            )
+            .unwrap();
+
+        strategy
+            .assert_fin_orders(
+                &grid_action.pair_address,
+                vec![
+                    (
+                        Side::Base,
+                        Decimal::percent(105), // price
+                        Uint128::new(10_000),  // offer
+                        Uint128::new(6_000),   // remaining
+                        Uint128::new(4_200),   // filled
+                    ),
+                    (
+                        Side::Quote,
+                        Decimal::percent(95), // price
+                        Uint128::new(9_500),  // offer
+                        Uint128::zero(),      // remaining
+                        Uint128::new(10_000), // filled
+                    ),
+                ],
+            )
+            .execute()
+            .assert_fin_orders(
+                &grid_action.pair_address,
+                vec![(
+                    Side::Base,
+                    Decimal::percent(105), // price
+                    Uint128::new(16_000),  // offer
+                    Uint128::new(16_000),  // remaining
+                    Uint128::zero(),       // filled
+                )],
+            );
     }
 
+    // Rebalance Action tests
+
     #[test]
-    fn test_instantiate_distribution_with_invalid_contract_recipient_fails() {
+    fn test_instantiate_rebalance_action_with_weights_not_summing_to_one_fails() {
         let mut harness = CalcTestApp::setup();
-        let distribution_action = Distribution {
-            destinations: vec![Destination {
-                recipient: Recipient::Contract {
-                    address: Addr::unchecked("test_invalid_recipient"),
-                    msg: Binary::default(),
-                },
-                shares: Uint128::new(10_000),
-                label: None,
-            }],
-            ..default_distribution_action(&harness)
+        let pair = harness.query_fin_config(&harness.fin_addr);
+
+        let rebalance_action = Rebalance {
+            targets: vec![
+                (pair.denoms.base().to_string(), Decimal::percent(50)),
+                (pair.denoms.quote().to_string(), Decimal::percent(40)),
+            ],
+            ..default_rebalance_action(&harness)
         };
 
         let result = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Distribute(distribution_action))
+            .with_action(Action::Rebalance(rebalance_action))
             .try_instantiate(&[]);
 
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_instantiate_distribution_with_native_denom_and_deposit_destination_fails() {
+    fn test_instantiate_rebalance_action_with_duplicate_targets_fails() {
         let mut harness = CalcTestApp::setup();
-        let distribution_action = Distribution {
-            destinations: vec![Destination {
-                recipient: Recipient::Deposit {
-                    memo: "-secure:eth-usdc".to_string(),
-                },
-                shares: Uint128::new(10_000),
-                label: None,
-            }],
-            denoms: vec!["x/ruji".to_string()],
+        let pair = harness.query_fin_config(&harness.fin_addr);
+
+        let rebalance_action = Rebalance {
+            targets: vec![
+                (pair.denoms.base().to_string(), Decimal::percent(50)),
+                (pair.denoms.base().to_string(), Decimal::percent(50)),
+            ],
+            ..default_rebalance_action(&harness)
         };
 
         let result = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Distribute(distribution_action))
+            .with_action(Action::Rebalance(rebalance_action))
             .try_instantiate(&[]);
 
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_instantiate_distribution_with_native_denom_and_non_deposit_recipients_succeeds() {
+    fn test_execute_rebalance_action_within_drift_tolerance_does_nothing() {
         let mut harness = CalcTestApp::setup();
-        let scheduler = harness.scheduler_addr.clone();
-        let fee_collector = harness.fee_collector_addr.clone();
-
-        let destinations = vec![
-            Destination {
-                recipient: Recipient::Bank {
-                    address: harness.app.api().addr_make(&"test1"),
-                },
-                shares: Uint128::new(5_000),
-                label: None,
-            },
-            Destination {
-                recipient: Recipient::Bank {
-                    address: harness.app.api().addr_make(&"test2"),
-                },
-                shares: Uint128::new(10_000),
-                label: None,
-            },
-            Destination {
-                recipient: Recipient::Contract {
-                    address: scheduler.clone(),
-                    msg: to_json_binary(&SchedulerExecuteMsg::Create(CreateTriggerMsg {
-                        condition: Condition::BlocksCompleted(100),
-                        msg: Binary::default(),
-                        contract_address: Addr::unchecked("test_contract"),
-                        executors: vec![],
-                        jitter: None,
-                    }))
-                    .unwrap(),
-                },
-                shares: Uint128::new(5_000),
-                label: None,
-            },
-        ];
-
-        let total_shares_with_fees = destinations
-            .iter()
-            .fold(Uint128::zero(), |acc, d| acc + d.shares)
-            .mul_ceil(Decimal::bps(10_000 + BASE_FEE_BPS));
+        let pair = harness.query_fin_config(&harness.fin_addr);
+        let rebalance_action = default_rebalance_action(&harness);
 
-        let fee_collector_destination = Destination {
-            recipient: Recipient::Bank {
-                address: fee_collector.clone(),
-            },
-            shares: total_shares_with_fees.mul_floor(Decimal::bps(BASE_FEE_BPS)),
-            label: None,
-        };
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Rebalance(rebalance_action))
+            .instantiate(&[
+                Coin::new(10_000u128, pair.denoms.base()),
+                Coin::new(10_000u128, pair.denoms.quote()),
+            ]);
 
-        let distribution_action = Distribution {
-            denoms: vec!["x/ruji".to_string()],
-            destinations: destinations.clone(),
-        };
+        strategy
+            .execute()
+            .assert_bank_balance(&Coin::new(10_000u128, pair.denoms.base()))
+            .assert_bank_balance(&Coin::new(10_000u128, pair.denoms.quote()))
+            .assert_stats(Statistics::default());
+    }
 
-        let starting_balances = vec![Coin::new(120_000u128, "x/ruji")];
+    #[test]
+    fn test_execute_rebalance_action_outside_drift_tolerance_swaps_back_to_targets() {
+        let mut harness = CalcTestApp::setup();
+        let pair = harness.query_fin_config(&harness.fin_addr);
+        let rebalance_action = default_rebalance_action(&harness);
 
         let mut strategy = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Distribute(distribution_action))
-            .instantiate(&starting_balances);
+            .with_action(Action::Rebalance(rebalance_action))
+            .instantiate(&[Coin::new(20_000u128, pair.denoms.base())]);
 
         strategy
-            .assert_bank_balance(&Coin::new(0u128, "x/ruji"))
+            .assert_bank_balance(&Coin::new(10_000u128, pair.denoms.base()))
+            .assert_bank_balance(&Coin::new(9_900u128, pair.denoms.quote()))
             .assert_stats(Statistics {
-                credited: [destinations, vec![fee_collector_destination]]
-                    .concat()
-                    .iter()
-                    .map(|d| {
-                        (
-                            d.recipient.clone(),
-                            starting_balances
-                                .iter()
-                                .map(|b| {
-                                    Coin::new(
-                                        b.amount.mul_floor(Decimal::from_ratio(
-                                            d.shares,
-                                            total_shares_with_fees,
-                                        )),
-                                        b.denom.clone(),
-                                    )
-                                })
-                                .collect(),
-                        )
-                    })
-                    .collect::<Vec<_>>(),
+                debited: vec![Coin::new(10_000u128, pair.denoms.base())],
                 ..Statistics::default()
             });
     }
 
     #[test]
-    fn test_instantiate_distribution_with_secured_denom_and_all_recipient_types_succeeds() {
+    fn test_execute_rebalance_action_only_caches_the_route_used() {
         let mut harness = CalcTestApp::setup();
-        let owner = harness.owner.clone();
-        let scheduler = harness.scheduler_addr.clone();
-        let fee_collector = harness.fee_collector_addr.clone();
+        let pair = harness.query_fin_config(&harness.fin_addr);
 
-        let destinations = vec![
-            Destination {
-                recipient: Recipient::Deposit {
-                    memo: "-secure:eth-usdc".to_string(),
-                },
-                shares: Uint128::new(10_000),
-                label: None,
-            },
-            Destination {
-                recipient: Recipient::Bank {
-                    address: owner.clone(),
-                },
-                shares: Uint128::new(10_000),
-                label: None,
-            },
-            Destination {
-                recipient: Recipient::Contract {
-                    address: scheduler.clone(),
-                    msg: to_json_binary(&SchedulerExecuteMsg::Create(CreateTriggerMsg {
-                        condition: Condition::BlocksCompleted(100),
-                        msg: Binary::default(),
-                        contract_address: Addr::unchecked("test_contract"),
-                        executors: vec![],
-                        jitter: None,
-                    }))
-                    .unwrap(),
-                },
-                shares: Uint128::new(5_000),
-                label: None,
-            },
-        ];
+        let rebalance_action = Rebalance {
+            routes: vec![
+                SwapRoute::Fin(FinRoute {
+                    pair_address: harness.fin_addr.clone(),
+                }),
+                SwapRoute::Fin(FinRoute {
+                    pair_address: harness.unknown.clone(),
+                }),
+            ],
+            ..default_rebalance_action(&harness)
+        };
 
-        let total_fee_applied_shares = destinations
-            .iter()
-            .fold(Uint128::zero(), |acc, d| acc + d.shares)
-            .mul_ceil(Decimal::bps(10_000 + BASE_FEE_BPS));
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Rebalance(rebalance_action.clone()))
+            .instantiate(&[Coin::new(20_000u128, pair.denoms.base())]);
 
-        let fee_collector_destination = Destination {
-            recipient: Recipient::Bank {
-                address: fee_collector.clone(),
-            },
-            shares: total_fee_applied_shares.mul_ceil(Decimal::bps(BASE_FEE_BPS)),
-            label: None,
+        strategy.assert_bank_balance(&Coin::new(10_000u128, pair.denoms.base()));
+
+        assert_eq!(
+            strategy.config().strategy.action,
+            Action::Rebalance(rebalance_action)
+        );
+    }
+
+    // Basket Action tests
+
+    #[test]
+    fn test_instantiate_basket_action_with_weights_not_summing_to_one_fails() {
+        let mut harness = CalcTestApp::setup();
+        let default_basket = default_basket_action(&harness);
+
+        let basket_action = Basket {
+            legs: vec![BasketLeg {
+                weight: Decimal::percent(50),
+                ..default_basket.legs[0].clone()
+            }],
+            ..default_basket
         };
 
-        let distribution_action = Distribution {
-            denoms: vec!["eth-usdc".to_string()],
-            destinations: destinations.clone(),
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Basket(basket_action.clone()))
+            .try_instantiate(&[basket_action.swap_amount.clone()]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_basket_action_with_duplicate_targets_fails() {
+        let mut harness = CalcTestApp::setup();
+        let default_basket = default_basket_action(&harness);
+
+        let basket_action = Basket {
+            legs: vec![
+                default_basket.legs[0].clone(),
+                default_basket.legs[0].clone(),
+            ],
+            ..default_basket
         };
 
-        let starting_balances = vec![Coin::new(100_000u128, "eth-usdc")];
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Basket(basket_action.clone()))
+            .try_instantiate(&[basket_action.swap_amount.clone()]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_basket_action_with_mismatched_leg_route_fails() {
+        let mut harness = CalcTestApp::setup();
+        let default_basket = default_basket_action(&harness);
+
+        let basket_action = Basket {
+            legs: vec![
+                default_basket.legs[0].clone(),
+                BasketLeg {
+                    routes: default_basket.legs[0].routes.clone(),
+                    ..default_basket.legs[1].clone()
+                },
+            ],
+            ..default_basket
+        };
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Basket(basket_action.clone()))
+            .try_instantiate(&[basket_action.swap_amount.clone()]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_execute_basket_action_skips_failing_legs() {
+        let mut harness = CalcTestApp::setup();
+        let default_basket = default_basket_action(&harness);
+
+        let basket_action = Basket {
+            legs: vec![
+                default_basket.legs[0].clone(),
+                BasketLeg {
+                    minimum_receive_amount: Coin::new(u128::MAX, "btc-btc"),
+                    ..default_basket.legs[1].clone()
+                },
+            ],
+            ..default_basket
+        };
+
+        let swap_denom = basket_action.swap_amount.denom.clone();
+        let receive_denom = basket_action.legs[0].minimum_receive_amount.denom.clone();
 
         let mut strategy = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Distribute(distribution_action.clone()))
-            .instantiate(&starting_balances);
+            .with_action(Action::Basket(basket_action.clone()))
+            .instantiate(&[basket_action.swap_amount.clone()]);
 
-        let total_shares = destinations.iter().map(|d| d.shares).sum::<Uint128>()
-            + fee_collector_destination.shares;
+        strategy
+            .assert_bank_balance(&Coin::new(500u128, swap_denom.clone()))
+            .assert_bank_balance(&Coin::new(495u128, receive_denom))
+            .assert_stats(Statistics {
+                debited: vec![Coin::new(500u128, swap_denom)],
+                ..Statistics::default()
+            });
+    }
 
-        strategy.assert_stats(Statistics {
-            credited: [destinations, vec![fee_collector_destination]]
-                .concat()
-                .iter()
-                .map(|d| {
-                    (
-                        d.recipient.clone(),
-                        starting_balances
-                            .iter()
-                            .map(|b| {
-                                Coin::new(
-                                    b.amount
-                                        .mul_floor(Decimal::from_ratio(d.shares, total_shares)),
-                                    b.denom.clone(),
-                                )
-                            })
-                            .collect(),
-                    )
-                })
-                .collect::<Vec<_>>(),
-            ..Statistics::default()
-        });
-        // TODO: Enable when MsgDeposit mock handler moves bank funds
-        // .assert_balance(&Coin::new(0u128, "eth-usdc"));
+    #[test]
+    fn test_execute_basket_action_with_insufficient_balance_scales_all_legs() {
+        let mut harness = CalcTestApp::setup();
+        let default_basket = default_basket_action(&harness);
+
+        let basket_action = Basket {
+            legs: vec![
+                BasketLeg {
+                    weight: Decimal::percent(40),
+                    ..default_basket.legs[0].clone()
+                },
+                BasketLeg {
+                    weight: Decimal::percent(60),
+                    minimum_receive_amount: Coin::new(u128::MAX, "btc-btc"),
+                    ..default_basket.legs[1].clone()
+                },
+            ],
+            ..default_basket
+        };
+
+        let swap_denom = basket_action.swap_amount.denom.clone();
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Basket(basket_action.clone()))
+            .instantiate(&[Coin::new(500u128, swap_denom.clone())]);
+
+        strategy
+            .assert_bank_balance(&Coin::new(300u128, swap_denom.clone()))
+            .assert_stats(Statistics {
+                debited: vec![Coin::new(200u128, swap_denom)],
+                ..Statistics::default()
+            });
     }
 
+    // Liquidity Action tests
+
     #[test]
-    fn test_execute_distribution_multiple_times_accumulates_statistics() {
+    fn test_instantiate_liquidity_action_with_invalid_pool_fails() {
         let mut harness = CalcTestApp::setup();
-        let owner = harness.owner.clone();
-        let fee_collector = harness.fee_collector_addr.clone();
 
-        let destinations = vec![Destination {
-            recipient: Recipient::Bank {
-                address: owner.clone(),
-            },
-            shares: Uint128::new(10_000),
-            label: None,
-        }];
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Liquidity(Liquidity {
+                pool: "not-a-pool".to_string(),
+                operation: LiquidityOperation::Add {
+                    amount: Coin::new(1000u128, "rune"),
+                },
+            }))
+            .try_instantiate(&[]);
 
-        let total_fee_applied_shares = destinations
-            .iter()
-            .fold(Uint128::zero(), |acc, d| acc + d.shares)
-            .mul_ceil(Decimal::bps(10_000 + BASE_FEE_BPS));
+        assert!(result.is_err());
+    }
 
-        let fee_collector_destination = Destination {
-            recipient: Recipient::Bank {
-                address: fee_collector.clone(),
-            },
-            shares: total_fee_applied_shares.mul_floor(Decimal::bps(BASE_FEE_BPS)),
-            label: None,
+    #[test]
+    fn test_instantiate_liquidity_action_with_native_denom_fails() {
+        let mut harness = CalcTestApp::setup();
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Liquidity(Liquidity {
+                pool: "BTC.BTC".to_string(),
+                operation: LiquidityOperation::Add {
+                    amount: Coin::new(1000u128, "x/ruji"),
+                },
+            }))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_liquidity_action_with_mismatched_pool_denom_fails() {
+        let mut harness = CalcTestApp::setup();
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Liquidity(Liquidity {
+                pool: "BTC.BTC".to_string(),
+                operation: LiquidityOperation::Add {
+                    amount: Coin::new(1000u128, "eth-eth"),
+                },
+            }))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_liquidity_action_with_invalid_withdrawal_basis_points_fails() {
+        let mut harness = CalcTestApp::setup();
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Liquidity(Liquidity {
+                pool: "BTC.BTC".to_string(),
+                operation: LiquidityOperation::Withdraw {
+                    basis_points: 10_001,
+                },
+            }))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_execute_add_liquidity_action_records_deposits() {
+        let mut harness = CalcTestApp::setup();
+
+        let strategy_addr = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Liquidity(Liquidity {
+                pool: "BTC.BTC".to_string(),
+                operation: LiquidityOperation::Add {
+                    amount: Coin::new(1000u128, "rune"),
+                },
+            }))
+            .instantiate(&[Coin::new(1000u128, "rune")])
+            .strategy_addr
+            .clone();
+
+        let stats = harness.query_strategy_stats(&strategy_addr);
+
+        assert_eq!(
+            stats.deposited,
+            vec![("BTC.BTC".to_string(), vec![Coin::new(1000u128, "rune")])]
+        );
+    }
+
+    #[test]
+    fn test_execute_withdraw_liquidity_action_records_no_deposits() {
+        let mut harness = CalcTestApp::setup();
+
+        let strategy_addr = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Liquidity(Liquidity {
+                pool: "BTC.BTC".to_string(),
+                operation: LiquidityOperation::Withdraw {
+                    basis_points: 5_000,
+                },
+            }))
+            .instantiate(&[])
+            .strategy_addr
+            .clone();
+
+        let stats = harness.query_strategy_stats(&strategy_addr);
+
+        assert!(stats.deposited.is_empty());
+    }
+
+    // ProtectiveExit Action tests
+
+    #[test]
+    fn test_instantiate_protective_exit_action_without_exit_levels_fails() {
+        let mut harness = CalcTestApp::setup();
+
+        let exit_action = ProtectiveExit {
+            stop_loss_bps: None,
+            ..default_protective_exit_action(&harness)
         };
 
-        let distribution_action = Distribution {
-            denoms: vec!["x/ruji".to_string()],
-            destinations: destinations.clone(),
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::ProtectiveExit(exit_action))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_protective_exit_action_with_trailing_stop_and_no_stop_loss_fails() {
+        let mut harness = CalcTestApp::setup();
+
+        let exit_action = ProtectiveExit {
+            stop_loss_bps: None,
+            trailing: true,
+            take_profits: vec![TakeProfit {
+                gain_bps: 1_000,
+                sell_bps: 10_000,
+                executed: false,
+            }],
+            ..default_protective_exit_action(&harness)
         };
 
-        let starting_balances = vec![Coin::new(100_000u128, "x/ruji")];
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::ProtectiveExit(exit_action))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_protective_exit_action_records_entry_price() {
+        let mut harness = CalcTestApp::setup();
+        let exit_action = default_protective_exit_action(&harness);
 
         let mut strategy = StrategyBuilder::new(&mut harness)
-            .with_action(Action::Distribute(distribution_action))
-            .instantiate(&starting_balances);
+            .with_action(Action::ProtectiveExit(exit_action.clone()))
+            .instantiate(&[Coin::new(1_000u128, exit_action.sell_denom.clone())]);
 
-        let total_shares = destinations.iter().map(|d| d.shares).sum::<Uint128>()
-            + fee_collector_destination.shares;
+        let config = strategy
+            .harness
+            .query_strategy_config(&strategy.strategy_addr);
+
+        assert_eq!(
+            config.strategy.action,
+            Action::ProtectiveExit(ProtectiveExit {
+                entry_price: Some(Decimal::one()),
+                highest_price: Some(Decimal::one()),
+                ..exit_action.clone()
+            })
+        );
 
         strategy
-            .deposit(&starting_balances.clone())
-            .execute()
-            .assert_bank_balance(&Coin::new(1u128, "x/ruji")) // just dust
+            .assert_bank_balance(&Coin::new(1_000u128, exit_action.sell_denom))
+            .assert_stats(Statistics::default());
+    }
+
+    #[test]
+    fn test_execute_protective_exit_action_below_stop_sells_whole_position() {
+        let mut harness = CalcTestApp::setup();
+
+        let exit_action = ProtectiveExit {
+            entry_price: Some(Decimal::percent(120)),
+            ..default_protective_exit_action(&harness)
+        };
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::ProtectiveExit(exit_action.clone()))
+            .instantiate(&[Coin::new(1_000u128, exit_action.sell_denom.clone())]);
+
+        strategy
+            .assert_bank_balances(vec![Coin::new(990u128, exit_action.receive_denom.clone())])
             .assert_stats(Statistics {
-                credited: [destinations, vec![fee_collector_destination]]
-                    .concat()
-                    .iter()
-                    .map(|d| {
-                        (
-                            d.recipient.clone(),
-                            starting_balances
-                                .iter()
-                                .map(|b| {
-                                    Coin::new(
-                                        (b.amount * Uint128::new(2))
-                                            .mul_floor(Decimal::from_ratio(d.shares, total_shares)),
-                                        b.denom.clone(),
-                                    )
-                                })
-                                .collect(),
-                        )
-                    })
-                    .collect::<Vec<_>>(),
+                debited: vec![Coin::new(1_000u128, exit_action.sell_denom.clone())],
                 ..Statistics::default()
-            });
+            })
+            .deposit(&[Coin::new(1_000u128, exit_action.sell_denom.clone())])
+            .execute()
+            .assert_bank_balance(&Coin::new(1_000u128, exit_action.sell_denom));
     }
 
-    // Conditional Action tests
+    #[test]
+    fn test_execute_protective_exit_action_above_target_takes_partial_profit_once() {
+        let mut harness = CalcTestApp::setup();
+
+        let exit_action = ProtectiveExit {
+            entry_price: Some(Decimal::percent(80)),
+            stop_loss_bps: None,
+            take_profits: vec![TakeProfit {
+                gain_bps: 1_000,
+                sell_bps: 5_000,
+                executed: false,
+            }],
+            ..default_protective_exit_action(&harness)
+        };
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::ProtectiveExit(exit_action.clone()))
+            .instantiate(&[Coin::new(1_000u128, exit_action.sell_denom.clone())]);
+
+        strategy
+            .assert_bank_balance(&Coin::new(500u128, exit_action.sell_denom.clone()))
+            .assert_bank_balance(&Coin::new(495u128, exit_action.receive_denom.clone()))
+            .execute()
+            .assert_bank_balance(&Coin::new(500u128, exit_action.sell_denom.clone()))
+            .assert_stats(Statistics {
+                debited: vec![Coin::new(500u128, exit_action.sell_denom)],
+                ..Statistics::default()
+            });
+    }
+
+    // Call Action tests
+
+    fn fin_order_call_msg(amount: &str) -> String {
+        to_json_string(&FinExecuteMsg::Order((
+            vec![(
+                Side::Base,
+                Price::Fixed(Decimal::percent(200)),
+                Some(Uint128::new(123_456_789)),
+            )],
+            None,
+        )))
+        .unwrap()
+        .replace("123456789", amount)
+    }
+
+    #[test]
+    fn test_instantiate_call_action_with_invalid_json_fails() {
+        let mut harness = CalcTestApp::setup();
+        let fin_addr = harness.fin_addr.clone();
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Call(Call {
+                contract: fin_addr,
+                msg: "{\"order\": [".to_string(),
+                funds: vec![],
+            }))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_call_action_with_unknown_placeholder_fails() {
+        let mut harness = CalcTestApp::setup();
+        let fin_addr = harness.fin_addr.clone();
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Call(Call {
+                contract: fin_addr,
+                msg: fin_order_call_msg("{{price:rune}}"),
+                funds: vec![],
+            }))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_call_action_with_invalid_percent_fails() {
+        let mut harness = CalcTestApp::setup();
+        let fin_addr = harness.fin_addr.clone();
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Call(Call {
+                contract: fin_addr,
+                msg: fin_order_call_msg("{{balance:rune}}"),
+                funds: vec![CallFunds::Percent {
+                    denom: "rune".to_string(),
+                    percent: Decimal::percent(150),
+                }],
+            }))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_execute_call_action_sends_templated_balance() {
+        let mut harness = CalcTestApp::setup();
+        let fin_addr = harness.fin_addr.clone();
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Call(Call {
+                contract: fin_addr.clone(),
+                msg: fin_order_call_msg("{{balance:rune}}"),
+                funds: vec![CallFunds::All {
+                    denom: "rune".to_string(),
+                }],
+            }))
+            .instantiate(&[Coin::new(1_000u128, "rune")]);
+
+        strategy.assert_bank_balances(vec![]).assert_fin_orders(
+            &fin_addr,
+            vec![(
+                Side::Base,
+                Decimal::percent(200),
+                Uint128::new(1_000),
+                Uint128::new(1_000),
+                Uint128::zero(),
+            )],
+        );
+    }
+
+    #[test]
+    fn test_execute_call_action_caps_fixed_funds_at_balance() {
+        let mut harness = CalcTestApp::setup();
+        let fin_addr = harness.fin_addr.clone();
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Call(Call {
+                contract: fin_addr.clone(),
+                msg: fin_order_call_msg("{{balance:rune}}"),
+                funds: vec![CallFunds::Fixed {
+                    amount: Coin::new(5_000u128, "rune"),
+                }],
+            }))
+            .instantiate(&[Coin::new(1_000u128, "rune")]);
+
+        strategy.assert_bank_balances(vec![]).assert_fin_orders(
+            &fin_addr,
+            vec![(
+                Side::Base,
+                Decimal::percent(200),
+                Uint128::new(1_000),
+                Uint128::new(1_000),
+                Uint128::zero(),
+            )],
+        );
+    }
+
+    #[test]
+    fn test_execute_call_action_without_funds_available_skips() {
+        let mut harness = CalcTestApp::setup();
+        let fin_addr = harness.fin_addr.clone();
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Call(Call {
+                contract: fin_addr.clone(),
+                msg: fin_order_call_msg("{{balance:rune}}"),
+                funds: vec![CallFunds::All {
+                    denom: "rune".to_string(),
+                }],
+            }))
+            .instantiate(&[]);
+
+        strategy
+            .assert_fin_orders(&fin_addr, vec![])
+            .assert_stats(Statistics::default());
+    }
+
+    // Ref Action tests
+
+    #[test]
+    fn test_instantiate_ref_action_with_unknown_definition_fails() {
+        let mut harness = CalcTestApp::setup();
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Ref(Ref("swap".to_string())))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_definitions_with_duplicate_names_fails() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_definitions(vec![
+                ("swap".to_string(), Action::Swap(swap_action.clone())),
+                ("swap".to_string(), Action::Swap(swap_action)),
+            ])
+            .with_action(Action::Ref(Ref("swap".to_string())))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_definition_referencing_later_definition_fails() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_definitions(vec![
+                ("outer".to_string(), Action::Ref(Ref("swap".to_string()))),
+                ("swap".to_string(), Action::Swap(swap_action)),
+            ])
+            .with_action(Action::Ref(Ref("outer".to_string())))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_ref_actions_count_definition_size_once() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+
+        let inlined = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Many(
+                (0..8).map(|_| Action::Swap(swap_action.clone())).collect(),
+            ))
+            .try_instantiate(&[]);
+
+        assert!(inlined.is_err());
+
+        let referenced = StrategyBuilder::new(&mut harness)
+            .with_definitions(vec![("swap".to_string(), Action::Swap(swap_action))])
+            .with_action(Action::Many(
+                (0..8)
+                    .map(|_| Action::Ref(Ref("swap".to_string())))
+                    .collect(),
+            ))
+            .try_instantiate(&[]);
+
+        assert!(referenced.is_ok());
+    }
+
+    #[test]
+    fn test_execute_ref_actions_execute_definition_for_each_reference() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let pair = harness.query_fin_config(&harness.fin_addr);
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_definitions(vec![(
+                "swap".to_string(),
+                Action::Swap(swap_action.clone()),
+            )])
+            .with_action(Action::Many(vec![
+                Action::Ref(Ref("swap".to_string())),
+                Action::Ref(Ref("swap".to_string())),
+            ]))
+            .instantiate(&[Coin::new(2_000u128, swap_action.swap_amount.denom.clone())]);
+
+        strategy
+            .assert_bank_balances(vec![Coin::new(1_980u128, pair.denoms.quote())])
+            .assert_stats(Statistics {
+                debited: vec![Coin::new(2_000u128, swap_action.swap_amount.denom)],
+                ..Statistics::default()
+            });
+    }
+
+    #[test]
+    fn test_pause_ref_action_cancels_shared_definition_state() {
+        let mut harness = CalcTestApp::setup();
+        let order_action = default_limit_order_action(&harness);
+        let starting_balance = Coin::new(1_000u128, order_action.bid_denom.clone());
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_definitions(vec![(
+                "order".to_string(),
+                Action::LimitOrder(order_action.clone()),
+            )])
+            .with_action(Action::Ref(Ref("order".to_string())))
+            .instantiate(&[starting_balance.clone()]);
+
+        strategy
+            .assert_bank_balances(vec![])
+            .assert_fin_orders(
+                &order_action.pair_address,
+                vec![(
+                    order_action.side.clone(),
+                    Decimal::one(),          // price
+                    starting_balance.amount, // offer
+                    starting_balance.amount, // remaining
+                    Uint128::zero(),         // filled
+                )],
+            )
+            .pause()
+            .assert_fin_orders(&order_action.pair_address, vec![])
+            .assert_bank_balance(&starting_balance);
+    }
+
+    // Many Action tests
+
+    #[test]
+    fn test_instantiate_empty_many_action_fails() {
+        let mut harness = CalcTestApp::setup();
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Many(vec![]))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_many_action_with_too_many_actions_fails() {
+        let mut harness = CalcTestApp::setup();
+
+        let actions = (1..=11)
+            .map(|_| Action::LimitOrder(default_limit_order_action(&harness)))
+            .collect::<Vec<_>>();
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Many(actions))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_many_action_succeeds() {
+        let mut harness = CalcTestApp::setup();
+        let pair = harness.query_fin_config(&harness.fin_addr);
+
+        let actions = vec![
+            Action::Swap(default_swap_action(&harness)),
+            Action::Swap(default_swap_action(&harness)),
+        ];
+
+        let manager = harness.manager_addr.clone();
+        let owner = harness.owner.clone();
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Many(actions.clone()))
+            .instantiate(&[]);
+
+        strategy.assert_config(StrategyConfig {
+            manager,
+            strategy: Strategy {
+                action: Action::Many(actions),
+                definitions: vec![],
+                state: Committed,
+                owner,
+            },
+            denoms: HashSet::from([
+                pair.denoms.quote().to_string(),
+                pair.denoms.base().to_string(),
+            ]),
+            escrowed: HashSet::from([pair.denoms.quote().to_string()]),
+        });
+    }
+
+    #[test]
+    fn test_execute_many_action_executes_actions_in_order() {
+        let mut harness = CalcTestApp::setup();
+        let pair_address = harness.fin_addr.clone();
+
+        let fin_swap_action = default_swap_action(&harness);
+        let limit_order_action = default_limit_order_action(&harness);
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(
+                Action::Many(vec![
+                    Action::Swap(fin_swap_action.clone()),
+                    Action::LimitOrder(limit_order_action.clone()),
+                ])
+                .clone(),
+            )
+            .instantiate(&[fin_swap_action.swap_amount.clone()]);
+
+        strategy
+            .assert_bank_balance(&Coin::new(0u128, fin_swap_action.swap_amount.denom.clone()))
+            .assert_fin_orders(&pair_address, vec![])
+            .assert_stats(Statistics {
+                debited: vec![fin_swap_action.swap_amount.clone()],
+                ..Statistics::default()
+            });
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(
+                Action::Many(vec![
+                    Action::LimitOrder(limit_order_action.clone()),
+                    Action::Swap(fin_swap_action.clone()),
+                ])
+                .clone(),
+            )
+            .instantiate(&[fin_swap_action.swap_amount.clone()]);
+
+        strategy
+            .assert_bank_balance(&Coin::new(0u128, fin_swap_action.swap_amount.denom.clone()))
+            .assert_fin_orders(
+                &pair_address,
+                vec![(
+                    limit_order_action.side,
+                    Decimal::one(),                     // price
+                    fin_swap_action.swap_amount.amount, // offer
+                    fin_swap_action.swap_amount.amount, // remaining
+                    Uint128::zero(),                    // filled
+                )],
+            )
+            .assert_stats(Statistics::default());
+    }
+
+    #[test]
+    fn test_instantiate_many_action_with_nested_many_action_succeeds() {
+        let mut harness = CalcTestApp::setup();
+        let pair = harness.query_fin_config(&harness.fin_addr);
+
+        let actions = vec![
+            Action::Swap(default_swap_action(&harness)),
+            Action::Many(vec![Action::LimitOrder(default_limit_order_action(
+                &harness,
+            ))]),
+        ];
+
+        let manager = harness.manager_addr.clone();
+        let owner = harness.owner.clone();
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Many(actions.clone()))
+            .instantiate(&[]);
+
+        strategy.assert_config(StrategyConfig {
+            manager,
+            strategy: Strategy {
+                action: Action::Many(actions),
+                definitions: vec![],
+                state: Committed,
+                owner,
+            },
+            denoms: HashSet::from([
+                pair.denoms.quote().to_string(),
+                pair.denoms.base().to_string(),
+            ]),
+            escrowed: HashSet::from([pair.denoms.quote().to_string()]),
+        });
+    }
+
+    // Distribution Action tests
+
+    #[test]
+    fn test_instantiate_distribution_with_empty_denoms_fails() {
+        let mut harness = CalcTestApp::setup();
+        let distribution_action = Distribution {
+            denoms: vec![],
+            ..default_distribution_action(&harness)
+        };
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Distribute(distribution_action))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_distribution_with_empty_destinations_fails() {
+        let mut harness = CalcTestApp::setup();
+        let distribution_action = Distribution {
+            destinations: vec![],
+            ..default_distribution_action(&harness)
+        };
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Distribute(distribution_action))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_distribution_with_zero_shares_destination_fails() {
+        let mut harness = CalcTestApp::setup();
+        let distribution_action = Distribution {
+            destinations: vec![
+                Destination {
+                    recipient: Recipient::Bank {
+                        address: harness.owner.clone(),
+                    },
+                    shares: Uint128::new(10_000),
+                    label: None,
+                },
+                Destination {
+                    recipient: Recipient::Bank {
+                        address: harness.owner.clone(),
+                    },
+                    shares: Uint128::zero(),
+                    label: None,
+                },
+            ],
+            ..default_distribution_action(&harness)
+        };
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Distribute(distribution_action))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_distribution_with_invalid_bank_recipient_fails() {
+        let mut harness = CalcTestApp::setup();
+        let distribution_action = Distribution {
+            destinations: vec![Destination {
+                recipient: Recipient::Bank {
+                    address: Addr::unchecked("test_invalid_recipient"),
+                },
+                shares: Uint128::new(10_000),
+                label: None,
+            }],
+            ..default_distribution_action(&harness)
+        };
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Distribute(distribution_action))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_distribution_with_invalid_contract_recipient_fails() {
+        let mut harness = CalcTestApp::setup();
+        let distribution_action = Distribution {
+            destinations: vec![Destination {
+                recipient: Recipient::Contract {
+                    address: Addr::unchecked("test_invalid_recipient"),
+                    msg: Binary::default(),
+                },
+                shares: Uint128::new(10_000),
+                label: None,
+            }],
+            ..default_distribution_action(&harness)
+        };
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Distribute(distribution_action))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_distribution_with_native_denom_and_deposit_destination_fails() {
+        let mut harness = CalcTestApp::setup();
+        let distribution_action = Distribution {
+            destinations: vec![Destination {
+                recipient: Recipient::Deposit {
+                    memo: "-secure:eth-usdc".to_string(),
+                },
+                shares: Uint128::new(10_000),
+                label: None,
+            }],
+            denoms: vec!["x/ruji".to_string()],
+        };
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Distribute(distribution_action))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_distribution_with_malformed_deposit_memo_fails() {
+        let mut harness = CalcTestApp::setup();
+        let distribution_action = Distribution {
+            destinations: vec![Destination {
+                recipient: Recipient::Deposit {
+                    memo: "secure:eth-usdc".to_string(),
+                },
+                shares: Uint128::new(10_000),
+                label: None,
+            }],
+            denoms: vec!["eth-usdc".to_string()],
+        };
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Distribute(distribution_action))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_distribution_with_native_denom_and_non_deposit_recipients_succeeds() {
+        let mut harness = CalcTestApp::setup();
+        let scheduler = harness.scheduler_addr.clone();
+        let fee_collector = harness.fee_collector_addr.clone();
+
+        let destinations = vec![
+            Destination {
+                recipient: Recipient::Bank {
+                    address: harness.app.api().addr_make(&"test1"),
+                },
+                shares: Uint128::new(5_000),
+                label: None,
+            },
+            Destination {
+                recipient: Recipient::Bank {
+                    address: harness.app.api().addr_make(&"test2"),
+                },
+                shares: Uint128::new(10_000),
+                label: None,
+            },
+            Destination {
+                recipient: Recipient::Contract {
+                    address: scheduler.clone(),
+                    msg: to_json_binary(&SchedulerExecuteMsg::Create(CreateTriggerMsg {
+                        condition: Condition::BlocksCompleted(100),
+                        msg: Binary::default(),
+                        contract_address: Addr::unchecked("test_contract"),
+                        executors: vec![],
+                        jitter: None,
+                        on_error: ErrorPolicy::default(),
+                    }))
+                    .unwrap(),
+                },
+                shares: Uint128::new(5_000),
+                label: None,
+            },
+        ];
+
+        let total_shares_with_fees = destinations
+            .iter()
+            .fold(Uint128::zero(), |acc, d| acc + d.shares)
+            .mul_ceil(Decimal::bps(10_000 + BASE_FEE_BPS));
+
+        let fee_collector_destination = Destination {
+            recipient: Recipient::Bank {
+                address: fee_collector.clone(),
+            },
+            shares: total_shares_with_fees.mul_floor(Decimal::bps(BASE_FEE_BPS)),
+            label: None,
+        };
+
+        let distribution_action = Distribution {
+            denoms: vec!["x/ruji".to_string()],
+            destinations: destinations.clone(),
+        };
+
+        let starting_balances = vec![Coin::new(120_000u128, "x/ruji")];
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Distribute(distribution_action))
+            .instantiate(&starting_balances);
+
+        strategy
+            .assert_bank_balance(&Coin::new(0u128, "x/ruji"))
+            .assert_stats(Statistics {
+                credited: [destinations, vec![fee_collector_destination]]
+                    .concat()
+                    .iter()
+                    .map(|d| {
+                        (
+                            d.recipient.clone(),
+                            starting_balances
+                                .iter()
+                                .map(|b| {
+                                    Coin::new(
+                                        b.amount.mul_floor(Decimal::from_ratio(
+                                            d.shares,
+                                            total_shares_with_fees,
+                                        )),
+                                        b.denom.clone(),
+                                    )
+                                })
+                                .collect(),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+                ..Statistics::default()
+            });
+    }
+
+    #[test]
+    fn test_instantiate_distribution_with_secured_denom_and_all_recipient_types_succeeds() {
+        let mut harness = CalcTestApp::setup();
+        let owner = harness.owner.clone();
+        let scheduler = harness.scheduler_addr.clone();
+        let fee_collector = harness.fee_collector_addr.clone();
+
+        let destinations = vec![
+            Destination {
+                recipient: Recipient::Deposit {
+                    memo: "-secure:eth-usdc".to_string(),
+                },
+                shares: Uint128::new(10_000),
+                label: None,
+            },
+            Destination {
+                recipient: Recipient::Bank {
+                    address: owner.clone(),
+                },
+                shares: Uint128::new(10_000),
+                label: None,
+            },
+            Destination {
+                recipient: Recipient::Contract {
+                    address: scheduler.clone(),
+                    msg: to_json_binary(&SchedulerExecuteMsg::Create(CreateTriggerMsg {
+                        condition: Condition::BlocksCompleted(100),
+                        msg: Binary::default(),
+                        contract_address: Addr::unchecked("test_contract"),
+                        executors: vec![],
+                        jitter: None,
+                        on_error: ErrorPolicy::default(),
+                    }))
+                    .unwrap(),
+                },
+                shares: Uint128::new(5_000),
+                label: None,
+            },
+        ];
+
+        let total_fee_applied_shares = destinations
+            .iter()
+            .fold(Uint128::zero(), |acc, d| acc + d.shares)
+            .mul_ceil(Decimal::bps(10_000 + BASE_FEE_BPS));
+
+        let fee_collector_destination = Destination {
+            recipient: Recipient::Bank {
+                address: fee_collector.clone(),
+            },
+            shares: total_fee_applied_shares.mul_ceil(Decimal::bps(BASE_FEE_BPS)),
+            label: None,
+        };
+
+        let distribution_action = Distribution {
+            denoms: vec!["eth-usdc".to_string()],
+            destinations: destinations.clone(),
+        };
+
+        let starting_balances = vec![Coin::new(100_000u128, "eth-usdc")];
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Distribute(distribution_action.clone()))
+            .instantiate(&starting_balances);
+
+        let total_shares = destinations.iter().map(|d| d.shares).sum::<Uint128>()
+            + fee_collector_destination.shares;
+
+        strategy.assert_stats(Statistics {
+            credited: [destinations, vec![fee_collector_destination]]
+                .concat()
+                .iter()
+                .map(|d| {
+                    (
+                        d.recipient.clone(),
+                        starting_balances
+                            .iter()
+                            .map(|b| {
+                                Coin::new(
+                                    b.amount
+                                        .mul_floor(Decimal::from_ratio(d.shares, total_shares)),
+                                    b.denom.clone(),
+                                )
+                            })
+                            .collect(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            ..Statistics::default()
+        });
+        // TODO: Enable when MsgDeposit mock handler moves bank funds
+        // .assert_balance(&Coin::new(0u128, "eth-usdc"));
+    }
+
+    #[test]
+    fn test_execute_distribution_multiple_times_accumulates_statistics() {
+        let mut harness = CalcTestApp::setup();
+        let owner = harness.owner.clone();
+        let fee_collector = harness.fee_collector_addr.clone();
+
+        let destinations = vec![Destination {
+            recipient: Recipient::Bank {
+                address: owner.clone(),
+            },
+            shares: Uint128::new(10_000),
+            label: None,
+        }];
+
+        let total_fee_applied_shares = destinations
+            .iter()
+            .fold(Uint128::zero(), |acc, d| acc + d.shares)
+            .mul_ceil(Decimal::bps(10_000 + BASE_FEE_BPS));
+
+        let fee_collector_destination = Destination {
+            recipient: Recipient::Bank {
+                address: fee_collector.clone(),
+            },
+            shares: total_fee_applied_shares.mul_floor(Decimal::bps(BASE_FEE_BPS)),
+            label: None,
+        };
+
+        let distribution_action = Distribution {
+            denoms: vec!["x/ruji".to_string()],
+            destinations: destinations.clone(),
+        };
+
+        let starting_balances = vec![Coin::new(100_000u128, "x/ruji")];
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Distribute(distribution_action))
+            .instantiate(&starting_balances);
+
+        let total_shares = destinations.iter().map(|d| d.shares).sum::<Uint128>()
+            + fee_collector_destination.shares;
+
+        strategy
+            .deposit(&starting_balances.clone())
+            .execute()
+            .assert_bank_balance(&Coin::new(1u128, "x/ruji")) // just dust
+            .assert_stats(Statistics {
+                credited: [destinations, vec![fee_collector_destination]]
+                    .concat()
+                    .iter()
+                    .map(|d| {
+                        (
+                            d.recipient.clone(),
+                            starting_balances
+                                .iter()
+                                .map(|b| {
+                                    Coin::new(
+                                        (b.amount * Uint128::new(2))
+                                            .mul_floor(Decimal::from_ratio(d.shares, total_shares)),
+                                        b.denom.clone(),
+                                    )
+                                })
+                                .collect(),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+                ..Statistics::default()
+            });
+    }
+
+    // Conditional Action tests
+
+    #[test]
+    fn test_instantiate_conditional_action_with_too_many_nested_actions_fails() {
+        let mut harness = CalcTestApp::setup();
+
+        let nested_actions = (1..=11)
+            .map(|_| Action::LimitOrder(default_limit_order_action(&harness)))
+            .collect::<Vec<_>>();
+
+        let action = Action::Conditional(Conditional {
+            condition: Condition::StrategyBalanceAvailable {
+                amount: Coin::new(1000u128, "x/ruji"),
+            },
+            action: Box::new(Action::Many(nested_actions)),
+            on_error: ErrorPolicy::default(),
+        });
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(action)
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_conditional_action_with_too_many_conditions_fails() {
+        let mut harness = CalcTestApp::setup();
+
+        let action = Action::Conditional(Conditional {
+            condition: Condition::Composite(CompositeCondition {
+                conditions: vec![
+                    Condition::StrategyBalanceAvailable {
+                        amount: Coin::new(1000u128, "x/ruji"),
+                    };
+                    20
+                ],
+                threshold: Threshold::All,
+            }),
+            action: Box::new(Action::Swap(default_swap_action(&harness))),
+            on_error: ErrorPolicy::default(),
+        });
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(action)
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_conditional_action_succeeds() {
+        let mut harness = CalcTestApp::setup();
+        let manager = harness.manager_addr.clone();
+        let owner = harness.owner.clone();
+
+        let action = Action::Conditional(Conditional {
+            condition: Condition::StrategyBalanceAvailable {
+                amount: Coin::new(1000u128, "rune"),
+            },
+            action: Box::new(Action::Swap(default_swap_action(&harness))),
+            on_error: ErrorPolicy::default(),
+        });
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(action.clone())
+            .instantiate(&[]);
+
+        strategy.assert_config(StrategyConfig {
+            manager,
+            strategy: Strategy {
+                owner,
+                action,
+                definitions: vec![],
+                state: Committed,
+            },
+            denoms: HashSet::from(["rune".to_string(), "eth-usdc".to_string()]),
+            escrowed: HashSet::from(["eth-usdc".to_string()]),
+        });
+    }
+
+    #[test]
+    fn test_execute_conditional_action_with_satisfied_conditions_executes_action() {
+        let mut harness = CalcTestApp::setup();
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+
+        let swap_action = Swap {
+            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
+            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
+            maximum_slippage_bps: 101,
+            adjustment: SwapAmountAdjustment::Fixed,
+            routes: vec![SwapRoute::Fin(FinRoute {
+                pair_address: harness.fin_addr.clone(),
+            })],
+        };
+
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount + Uint128::one(),
+            fin_pair.denoms.base(),
+        )];
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::StrategyBalanceAvailable {
+                    amount: swap_action.swap_amount.clone(),
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds);
+
+        strategy.assert_swapped(vec![swap_action.swap_amount.clone()]);
+    }
+
+    #[test]
+    fn test_execute_conditional_action_with_unsatisfied_conditions_skips_action() {
+        let mut harness = CalcTestApp::setup();
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+
+        let swap_action = Swap {
+            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
+            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
+            maximum_slippage_bps: 101,
+            adjustment: SwapAmountAdjustment::Fixed,
+            routes: vec![SwapRoute::Fin(FinRoute {
+                pair_address: harness.fin_addr.clone(),
+            })],
+        };
+
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount + Uint128::one(),
+            fin_pair.denoms.base(),
+        )];
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::StrategyBalanceAvailable {
+                    amount: Coin::new(
+                        swap_action.swap_amount.amount * Uint128::new(2),
+                        fin_pair.denoms.base(),
+                    ),
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![]);
+    }
+
+    #[test]
+    fn test_execute_condition_action_respects_timestamp_elapsed_condition() {
+        let mut harness = CalcTestApp::setup();
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+
+        let swap_action = Swap {
+            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
+            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
+            maximum_slippage_bps: 101,
+            adjustment: SwapAmountAdjustment::Fixed,
+            routes: vec![SwapRoute::Fin(FinRoute {
+                pair_address: harness.fin_addr.clone(),
+            })],
+        };
+
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount + Uint128::one(),
+            fin_pair.denoms.base(),
+        )];
+
+        let block_time = harness.app.block_info().time;
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::TimestampElapsed(block_time.plus_seconds(60)),
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![])
+            .advance_time(61)
+            .execute()
+            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+    }
+
+    #[test]
+    fn test_execute_condition_action_respects_block_elapsed_condition() {
+        let mut harness = CalcTestApp::setup();
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+
+        let swap_action = Swap {
+            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
+            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
+            maximum_slippage_bps: 101,
+            adjustment: SwapAmountAdjustment::Fixed,
+            routes: vec![SwapRoute::Fin(FinRoute {
+                pair_address: harness.fin_addr.clone(),
+            })],
+        };
+
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount + Uint128::one(),
+            fin_pair.denoms.base(),
+        )];
+
+        let block_height = harness.app.block_info().height;
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::BlocksCompleted(block_height + 60),
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![])
+            .advance_blocks(61)
+            .execute()
+            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+    }
+
+    #[test]
+    fn test_execute_condition_action_respects_can_swap_condition() {
+        let mut harness = CalcTestApp::setup();
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+
+        let swap_action = default_swap_action(&harness);
+
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount + Uint128::one(),
+            fin_pair.denoms.base(),
+        )];
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::CanSwap(Swap {
+                    minimum_receive_amount: Coin::new(20_000_000u128, fin_pair.denoms.quote()),
+                    ..swap_action.clone()
+                }),
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![]);
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::CanSwap(Swap {
+                    minimum_receive_amount: Coin::new(20u128, fin_pair.denoms.quote()),
+                    ..swap_action.clone()
+                }),
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+    }
+
+    #[test]
+    fn test_execute_condition_action_respects_balance_available_condition() {
+        let mut harness = CalcTestApp::setup();
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+
+        let swap_action = Swap {
+            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
+            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
+            maximum_slippage_bps: 101,
+            adjustment: SwapAmountAdjustment::Fixed,
+            routes: vec![SwapRoute::Fin(FinRoute {
+                pair_address: harness.fin_addr.clone(),
+            })],
+        };
+
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount + Uint128::one(),
+            fin_pair.denoms.base(),
+        )];
+
+        let random = harness.app.api().addr_make("random");
+        let owner = harness.owner.clone();
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::BalanceAvailable {
+                    address: random,
+                    amount: Coin::new(1u128, fin_pair.denoms.base()),
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![]);
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::BalanceAvailable {
+                    address: owner,
+                    amount: Coin::new(1u128, fin_pair.denoms.base()),
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+    }
+
+    #[test]
+    fn test_execute_condition_action_respects_strategy_balance_available_condition() {
+        let mut harness = CalcTestApp::setup();
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+
+        let swap_action = Swap {
+            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
+            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
+            maximum_slippage_bps: 101,
+            adjustment: SwapAmountAdjustment::Fixed,
+            routes: vec![SwapRoute::Fin(FinRoute {
+                pair_address: harness.fin_addr.clone(),
+            })],
+        };
+
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount + Uint128::one(),
+            fin_pair.denoms.base(),
+        )];
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::StrategyBalanceAvailable {
+                    amount: Coin::new(1u128, fin_pair.denoms.base()),
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&[])
+            .assert_swapped(vec![]);
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::StrategyBalanceAvailable {
+                    amount: funds[0].clone(),
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+    }
+
+    #[test]
+    fn test_execute_condition_action_respects_strategy_status_condition() {
+        let mut harness = CalcTestApp::setup();
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+
+        let swap_action = Swap {
+            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
+            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
+            maximum_slippage_bps: 101,
+            adjustment: SwapAmountAdjustment::Fixed,
+            routes: vec![SwapRoute::Fin(FinRoute {
+                pair_address: harness.fin_addr.clone(),
+            })],
+        };
+
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount + Uint128::one(),
+            fin_pair.denoms.base(),
+        )];
+
+        let manager = harness.manager_addr.clone();
+        let strategy_action = Action::Swap(default_swap_action(&harness));
+
+        let strategy = StrategyBuilder::new(&mut harness)
+            .with_action(strategy_action)
+            .instantiate(&[Coin::new(100_000u128, "x/ruji")]);
+
+        StrategyBuilder::new(strategy.harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::StrategyStatus {
+                    manager_contract: manager.clone(),
+                    contract_address: strategy.strategy_addr.clone(),
+                    status: StrategyStatus::Archived,
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![]);
+
+        StrategyBuilder::new(strategy.harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::StrategyStatus {
+                    manager_contract: manager,
+                    contract_address: strategy.strategy_addr.clone(),
+                    status: StrategyStatus::Active,
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+    }
+
+    #[test]
+    fn test_execute_condition_action_respects_not_condition() {
+        let mut harness = CalcTestApp::setup();
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+
+        let swap_action = Swap {
+            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
+            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
+            maximum_slippage_bps: 101,
+            adjustment: SwapAmountAdjustment::Fixed,
+            routes: vec![SwapRoute::Fin(FinRoute {
+                pair_address: harness.fin_addr.clone(),
+            })],
+        };
+
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount + Uint128::one(),
+            fin_pair.denoms.base(),
+        )];
+
+        let manager = harness.manager_addr.clone();
+        let strategy_action = Action::Swap(default_swap_action(&harness));
+
+        let strategy = StrategyBuilder::new(&mut harness)
+            .with_action(strategy_action)
+            .instantiate(&[Coin::new(100_000u128, "x/ruji")]);
+
+        StrategyBuilder::new(strategy.harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::StrategyStatus {
+                    manager_contract: manager.clone(),
+                    contract_address: strategy.strategy_addr.clone(),
+                    status: StrategyStatus::Archived,
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![]);
+
+        StrategyBuilder::new(strategy.harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::Not(Box::new(Condition::StrategyStatus {
+                    manager_contract: manager,
+                    contract_address: strategy.strategy_addr.clone(),
+                    status: StrategyStatus::Archived,
+                })),
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+    }
+
+    #[test]
+    fn test_execute_condition_action_respects_composite_condition_threshold() {
+        let mut harness = CalcTestApp::setup();
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+
+        let swap_action = Swap {
+            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
+            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
+            maximum_slippage_bps: 101,
+            adjustment: SwapAmountAdjustment::Fixed,
+            routes: vec![SwapRoute::Fin(FinRoute {
+                pair_address: harness.fin_addr.clone(),
+            })],
+        };
+
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount + Uint128::one(),
+            fin_pair.denoms.base(),
+        )];
+
+        let manager = harness.manager_addr.clone();
+        let strategy_action = Action::Swap(default_swap_action(&harness));
+
+        let strategy = StrategyBuilder::new(&mut harness)
+            .with_action(strategy_action)
+            .instantiate(&[Coin::new(100_000u128, "x/ruji")]);
+
+        StrategyBuilder::new(strategy.harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::Composite(CompositeCondition {
+                    conditions: vec![
+                        Condition::StrategyStatus {
+                            manager_contract: manager.clone(),
+                            contract_address: strategy.strategy_addr.clone(),
+                            status: StrategyStatus::Archived,
+                        },
+                        Condition::StrategyBalanceAvailable {
+                            amount: funds[0].clone(),
+                        },
+                    ],
+                    threshold: Threshold::All,
+                }),
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![]);
+
+        StrategyBuilder::new(strategy.harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::Composite(CompositeCondition {
+                    conditions: vec![
+                        Condition::StrategyStatus {
+                            manager_contract: manager.clone(),
+                            contract_address: strategy.strategy_addr.clone(),
+                            status: StrategyStatus::Archived,
+                        },
+                        Condition::StrategyBalanceAvailable {
+                            amount: funds[0].clone(),
+                        },
+                    ],
+                    threshold: Threshold::Any,
+                }),
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+    }
+
+    #[test]
+    fn test_execute_conditional_action_with_time_window_condition() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        let hour = harness.app.block_info().time.seconds() / 3_600 % 24;
+
+        let window = |hours: u64| {
+            Action::Conditional(Conditional {
+                condition: Condition::TimeWindow {
+                    days: "*".to_string(),
+                    hours: hours.to_string(),
+                    utc_offset: None,
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            })
+        };
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(window(hour))
+            .instantiate(&funds)
+            .assert_swapped(funds.clone());
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(window((hour + 1) % 24))
+            .instantiate(&funds)
+            .assert_swapped(vec![]);
+    }
+
+    #[test]
+    fn test_instantiate_conditional_action_with_invalid_time_window_fails() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::TimeWindow {
+                    days: "Mon-Fri".to_string(),
+                    hours: "13-24".to_string(),
+                    utc_offset: None,
+                },
+                action: Box::new(Action::Swap(swap_action)),
+                on_error: ErrorPolicy::default(),
+            }))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_execute_conditional_action_with_thorchain_trading_available_condition() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        // ETH trading is halted in the mocked mimir
+        let cases = [("btc-btc", true), ("eth-usdc", false), ("rune", true)];
+
+        for (asset, swaps) in cases {
+            StrategyBuilder::new(&mut harness)
+                .with_action(Action::Conditional(Conditional {
+                    condition: Condition::ThorchainTradingAvailable {
+                        asset: asset.to_string(),
+                        max_outbound_delay: None,
+                    },
+                    action: Box::new(Action::Swap(swap_action.clone())),
+                    on_error: ErrorPolicy::default(),
+                }))
+                .instantiate(&funds)
+                .assert_swapped(if swaps { funds.clone() } else { vec![] });
+        }
+    }
+
+    #[test]
+    fn test_execute_conditional_action_with_thorchain_outbound_delay_condition() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::ThorchainTradingAvailable {
+                    asset: "btc-btc".to_string(),
+                    // Quotes are stubbed with no outbound delay
+                    max_outbound_delay: Some(OutboundDelay {
+                        swap_amount: Coin::new(1_000u128, "rune"),
+                        max_delay: Duration::from_secs(0),
+                    }),
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(funds.clone());
+    }
+
+    #[test]
+    fn test_instantiate_conditional_action_with_outbound_delay_in_same_denom_fails() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::ThorchainTradingAvailable {
+                    asset: "btc-btc".to_string(),
+                    max_outbound_delay: Some(OutboundDelay {
+                        swap_amount: Coin::new(1_000u128, "btc-btc"),
+                        max_delay: Duration::from_secs(600),
+                    }),
+                },
+                action: Box::new(Action::Swap(swap_action)),
+                on_error: ErrorPolicy::default(),
+            }))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_execute_conditional_action_with_pool_depth_condition() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        // BTC pool stubbed with ~11.7M RUNE
+        let cases = [
+            (1_000_000_000_000_000u128, true),
+            (2_000_000_000_000_000, false),
+        ];
+
+        for (min_depth, swaps) in cases {
+            StrategyBuilder::new(&mut harness)
+                .with_action(Action::Conditional(Conditional {
+                    condition: Condition::PoolDepth {
+                        asset: "btc-btc".to_string(),
+                        min_depth: Uint128::new(min_depth),
+                    },
+                    action: Box::new(Action::Swap(swap_action.clone())),
+                    on_error: ErrorPolicy::default(),
+                }))
+                .instantiate(&funds)
+                .assert_swapped(if swaps { funds.clone() } else { vec![] });
+        }
+    }
+
+    #[test]
+    fn test_execute_conditional_action_with_fin_book_depth_condition() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        // Book stubbed with 100,000 on each side, 1% either side of the mid
+        let cases = [
+            (Side::Base, 100, 100_000u128, true),
+            (Side::Base, 100, 100_001, false),
+            (Side::Quote, 50, 1, false),
+        ];
+
+        for (side, band_bps, min_depth, swaps) in cases {
+            StrategyBuilder::new(&mut harness)
+                .with_action(Action::Conditional(Conditional {
+                    condition: Condition::FinBookDepth {
+                        pair_address: harness.fin_addr.clone(),
+                        side,
+                        band_bps,
+                        min_depth: Uint128::new(min_depth),
+                    },
+                    action: Box::new(Action::Swap(swap_action.clone())),
+                    on_error: ErrorPolicy::default(),
+                }))
+                .instantiate(&funds)
+                .assert_swapped(if swaps { funds.clone() } else { vec![] });
+        }
+    }
+
+    #[test]
+    fn test_execute_conditional_action_with_balance_ratio_condition() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+
+        // Twice as much base as quote is deposited
+        let funds = vec![
+            Coin::new(
+                swap_action.swap_amount.amount / Uint128::new(2),
+                fin_pair.denoms.quote(),
+            ),
+            swap_action.swap_amount.clone(),
+        ];
+
+        let cases = [
+            (Direction::Above, "1.9", true),
+            (Direction::Above, "2", false),
+            (Direction::Below, "2.1", true),
+            (Direction::Below, "2", false),
+        ];
+
+        for (direction, ratio, swaps) in cases {
+            StrategyBuilder::new(&mut harness)
+                .with_action(Action::Conditional(Conditional {
+                    condition: Condition::BalanceRatio {
+                        address: None,
+                        numerator_denom: fin_pair.denoms.base().to_string(),
+                        denominator_denom: fin_pair.denoms.quote().to_string(),
+                        valuation: Valuation::Units,
+                        direction,
+                        ratio: Decimal::from_str(ratio).unwrap(),
+                    },
+                    action: Box::new(Action::Swap(swap_action.clone())),
+                    on_error: ErrorPolicy::default(),
+                }))
+                .instantiate(&funds)
+                .assert_swapped(if swaps {
+                    vec![swap_action.swap_amount.clone()]
+                } else {
+                    vec![]
+                });
+        }
+    }
+
+    #[test]
+    fn test_instantiate_conditional_action_with_balance_ratio_of_unpriced_denom_fails() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        // RUNE is not a secured asset, so has no oracle price
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::BalanceRatio {
+                    address: None,
+                    numerator_denom: fin_pair.denoms.base().to_string(),
+                    denominator_denom: fin_pair.denoms.quote().to_string(),
+                    valuation: Valuation::Oracle,
+                    direction: Direction::Above,
+                    ratio: Decimal::one(),
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .try_instantiate(&funds);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_execute_conditional_distribution_once_owner_is_inactive() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let beneficiary = harness.unknown.clone();
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::OwnerInactive {
+                    period: Duration::from_secs(86_400),
+                    strategy: None,
+                },
+                action: Box::new(Action::Distribute(Distribution {
+                    destinations: vec![Destination {
+                        recipient: Recipient::Bank {
+                            address: beneficiary.clone(),
+                        },
+                        shares: Uint128::new(10_000),
+                        label: None,
+                    }],
+                    denoms: vec![funds[0].denom.clone()],
+                })),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds);
+
+        let created_at = strategy.harness.app.block_info().time;
+
+        assert_eq!(
+            strategy.harness.query_heartbeat(&strategy.strategy_addr),
+            created_at
+        );
+
+        strategy
+            .advance_time(43_200)
+            .heartbeat()
+            .advance_time(43_201)
+            .execute()
+            .assert_bank_balance(&funds[0]);
+
+        assert_eq!(
+            strategy.harness.query_heartbeat(&strategy.strategy_addr),
+            created_at.plus_seconds(43_200)
+        );
+
+        let beneficiary_balance = strategy
+            .harness
+            .query_balance(&beneficiary, &funds[0].denom);
+
+        strategy
+            .advance_time(43_200)
+            .execute()
+            .assert_bank_balance(&Coin::new(0u128, funds[0].denom.clone()));
+
+        assert_eq!(
+            strategy
+                .harness
+                .query_balance(&beneficiary, &funds[0].denom)
+                .amount,
+            beneficiary_balance.amount + funds[0].amount
+        );
+    }
+
+    #[test]
+    fn test_heartbeat_from_non_owner_fails() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let unknown = harness.unknown.clone();
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Swap(swap_action.clone()))
+            .instantiate(&[swap_action.swap_amount.clone()]);
+
+        assert!(strategy
+            .harness
+            .heartbeat(&unknown, &strategy.strategy_addr)
+            .is_err());
+    }
+
+    #[test]
+    fn test_trace_condition_reports_observed_values() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+
+        let strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Swap(swap_action.clone()))
+            .instantiate(&[swap_action.swap_amount.clone()]);
+
+        let condition = Condition::Composite(CompositeCondition {
+            conditions: vec![
+                // BTC-BTC oracle price stubbed at $100,100.00
+                Condition::OraclePrice {
+                    asset: "BTC-BTC".to_string(),
+                    rate: Decimal::from_str("100000").unwrap(),
+                    direction: Direction::Above,
+                },
+                Condition::ExecutionCount {
+                    min: Some(2),
+                    max: None,
+                    strategy: Some(strategy.strategy_addr.clone()),
+                },
+            ],
+            threshold: Threshold::All,
+        });
+
+        for trace in [
+            strategy
+                .harness
+                .query_strategy_condition_trace(&strategy.strategy_addr, condition.clone()),
+            strategy
+                .harness
+                .query_scheduler_condition_trace(condition.clone()),
+        ] {
+            assert!(!trace.satisfied);
+            assert_eq!(trace.error, None);
+
+            assert!(trace.children[0].satisfied);
+            assert_eq!(
+                trace.children[0].observed,
+                vec![("price".to_string(), "100100".to_string())]
+            );
+
+            assert!(!trace.children[1].satisfied);
+            assert_eq!(
+                trace.children[1].observed,
+                vec![("executions".to_string(), "1".to_string())]
+            );
+        }
+    }
+
+    #[test]
+    fn test_execute_conditional_action_applies_error_policy() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        // None means the strategy execution itself should fail
+        let cases = [
+            (ErrorPolicy::TreatAsFalse, Some(false)),
+            (ErrorPolicy::TreatAsTrue, Some(true)),
+            (ErrorPolicy::Fail, None),
+        ];
+
+        for (on_error, swaps) in cases {
+            let watched = StrategyBuilder::new(&mut harness)
+                .with_action(Action::Swap(swap_action.clone()))
+                .instantiate(&[Coin::new(1000u128, "x/ruji")]);
+
+            let watched_addr = watched.strategy_addr.clone();
+
+            // Not satisfied at first, then unresolvable once the balance is withdrawn
+            let mut strategy = StrategyBuilder::new(watched.harness)
+                .with_action(Action::Conditional(Conditional {
+                    condition: Condition::Query {
+                        contract: watched_addr.clone(),
+                        msg: r#"{"balances":["x/ruji"]}"#.to_string(),
+                        path: "/0/amount".to_string(),
+                        comparator: Comparator::LessThan,
+                        value: "1".to_string(),
+                        max_response_bytes: None,
+                    },
+                    action: Box::new(Action::Swap(swap_action.clone())),
+                    on_error,
+                }))
+                .instantiate(&funds);
+
+            strategy.assert_swapped(vec![]);
+
+            let owner = strategy.owner.clone();
+
+            strategy
+                .harness
+                .withdraw(&owner, &watched_addr, HashSet::from(["x/ruji".to_string()]))
+                .unwrap();
+
+            match swaps {
+                Some(swaps) => {
+                    strategy
+                        .execute()
+                        .assert_swapped(if swaps { funds.clone() } else { vec![] });
+                }
+                None => {
+                    assert!(strategy.try_execute().is_err());
+                    strategy.assert_swapped(vec![]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_execute_conditional_action_with_query_condition() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        // Book stubbed with 100,000 on each side, 1% either side of the mid
+        let cases = [
+            ("/base/0/total", Comparator::Equal, "100000", true),
+            ("/base/0/total", Comparator::GreaterThan, "100000", false),
+            (
+                "/quote/0/price",
+                Comparator::GreaterThanOrEqual,
+                "0.99",
+                true,
+            ),
+            ("/quote/0/price", Comparator::LessThan, "0.99", false),
+        ];
+
+        for (path, comparator, value, swaps) in cases {
+            StrategyBuilder::new(&mut harness)
+                .with_action(Action::Conditional(Conditional {
+                    condition: Condition::Query {
+                        contract: harness.fin_addr.clone(),
+                        msg: r#"{"book":{"limit":1}}"#.to_string(),
+                        path: path.to_string(),
+                        comparator,
+                        value: value.to_string(),
+                        max_response_bytes: None,
+                    },
+                    action: Box::new(Action::Swap(swap_action.clone())),
+                    on_error: ErrorPolicy::default(),
+                }))
+                .instantiate(&funds)
+                .assert_swapped(if swaps { funds.clone() } else { vec![] });
+        }
+    }
+
+    #[test]
+    fn test_instantiate_conditional_action_with_unresolvable_query_condition_fails() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::Query {
+                    contract: harness.fin_addr.clone(),
+                    msg: r#"{"book":{"limit":1}}"#.to_string(),
+                    path: "/missing".to_string(),
+                    comparator: Comparator::Equal,
+                    value: "1".to_string(),
+                    max_response_bytes: None,
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .try_instantiate(&funds);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_execute_conditional_action_with_at_least_threshold() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        // BTC-BTC oracle price stubbed at $100,100.00
+        let signals = vec![
+            Condition::OraclePrice {
+                asset: "BTC-BTC".to_string(),
+                rate: Decimal::from_str("100000").unwrap(),
+                direction: Direction::Above,
+            },
+            Condition::StrategyBalanceAvailable {
+                amount: funds[0].clone(),
+            },
+            Condition::OraclePrice {
+                asset: "BTC-BTC".to_string(),
+                rate: Decimal::from_str("101000").unwrap(),
+                direction: Direction::Above,
+            },
+        ];
+
+        let cases = [
+            (Threshold::AtLeast(2), true),
+            (Threshold::AtLeast(3), false),
+            (Threshold::Exactly(2), true),
+            (Threshold::Exactly(1), false),
+        ];
+
+        for (threshold, swaps) in cases {
+            StrategyBuilder::new(&mut harness)
+                .with_action(Action::Conditional(Conditional {
+                    condition: Condition::Composite(CompositeCondition {
+                        conditions: signals.clone(),
+                        threshold,
+                    }),
+                    action: Box::new(Action::Swap(swap_action.clone())),
+                    on_error: ErrorPolicy::default(),
+                }))
+                .instantiate(&funds)
+                .assert_swapped(if swaps { funds.clone() } else { vec![] });
+        }
+    }
+
+    #[test]
+    fn test_instantiate_conditional_action_with_unreachable_threshold_fails() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::Composite(CompositeCondition {
+                    conditions: vec![Condition::BlocksCompleted(0), Condition::BlocksCompleted(1)],
+                    threshold: Threshold::AtLeast(3),
+                }),
+                action: Box::new(Action::Swap(swap_action)),
+                on_error: ErrorPolicy::default(),
+            }))
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_execute_condition_action_respects_oracle_price_condition() {
+        let mut harness = CalcTestApp::setup();
+        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+
+        let swap_action = Swap {
+            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
+            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
+            maximum_slippage_bps: 101,
+            adjustment: SwapAmountAdjustment::Fixed,
+            routes: vec![SwapRoute::Fin(FinRoute {
+                pair_address: harness.fin_addr.clone(),
+            })],
+        };
+
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount + Uint128::one(),
+            fin_pair.denoms.base(),
+        )];
+
+        let strategy_action = Action::Swap(default_swap_action(&harness));
+
+        let strategy = StrategyBuilder::new(&mut harness)
+            .with_action(strategy_action)
+            .instantiate(&[Coin::new(100_000u128, "x/ruji")]);
+
+        // BTC-BTC oracle price stubbed at $100,100.00
+
+        StrategyBuilder::new(strategy.harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::OraclePrice {
+                    asset: "BTC-BTC".to_string(),
+                    rate: Decimal::from_str("100000").unwrap(),
+                    direction: Direction::Below,
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![]);
+
+        StrategyBuilder::new(strategy.harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::OraclePrice {
+                    asset: "BTC-BTC".to_string(),
+                    rate: Decimal::from_str("101000").unwrap(),
+                    direction: Direction::Below,
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+
+        StrategyBuilder::new(strategy.harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::OraclePrice {
+                    asset: "BTC-BTC".to_string(),
+                    rate: Decimal::from_str("101000").unwrap(),
+                    direction: Direction::Above,
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![]);
+
+        StrategyBuilder::new(strategy.harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::OraclePrice {
+                    asset: "BTC-BTC".to_string(),
+                    rate: Decimal::from_str("100000").unwrap(),
+                    direction: Direction::Above,
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&funds)
+            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+    }
+
+    #[test]
+    fn test_execute_conditional_action_with_fin_price_condition() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        // Book stubbed with a best ask of 1.01 and a best bid of 0.99
+        let condition = |side: BookSide, direction: Direction, price: &str| {
+            Action::Conditional(Conditional {
+                condition: Condition::FinPrice {
+                    pair_address: harness.fin_addr.clone(),
+                    side,
+                    direction,
+                    price: Decimal::from_str(price).unwrap(),
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            })
+        };
+
+        let actions = [
+            (condition(BookSide::Mid, Direction::Above, "0.995"), true),
+            (condition(BookSide::Mid, Direction::Above, "1.005"), false),
+            (condition(BookSide::Ask, Direction::Above, "1.005"), true),
+            (condition(BookSide::Bid, Direction::Below, "0.995"), true),
+            (condition(BookSide::Bid, Direction::Above, "0.995"), false),
+        ];
+
+        for (action, swaps) in actions {
+            StrategyBuilder::new(&mut harness)
+                .with_action(action)
+                .instantiate(&funds)
+                .assert_swapped(if swaps { funds.clone() } else { vec![] });
+        }
+    }
+
+    #[test]
+    fn test_execute_conditional_action_with_execution_count_condition() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::ExecutionCount {
+                    min: None,
+                    max: Some(1),
+                    strategy: None,
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&[Coin::new(
+                swap_action.swap_amount.amount * Uint128::new(3),
+                swap_action.swap_amount.denom.clone(),
+            )])
+            .execute()
+            .execute()
+            .assert_swapped(vec![Coin::new(
+                swap_action.swap_amount.amount * Uint128::new(2),
+                swap_action.swap_amount.denom.clone(),
+            )]);
+    }
+
+    #[test]
+    fn test_resume_strategy_is_not_counted_as_an_execution() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(Action::Swap(swap_action.clone()))
+            .instantiate(&[Coin::new(
+                swap_action.swap_amount.amount * Uint128::new(3),
+                swap_action.swap_amount.denom.clone(),
+            )]);
 
-    #[test]
-    fn test_instantiate_conditional_action_with_too_many_nested_actions_fails() {
-        let mut harness = CalcTestApp::setup();
+        assert_eq!(
+            strategy
+                .harness
+                .query_strategy_stats(&strategy.strategy_addr)
+                .executions,
+            1
+        );
 
-        let nested_actions = (1..=11)
-            .map(|_| Action::LimitOrder(default_limit_order_action(&harness)))
-            .collect::<Vec<_>>();
+        strategy.pause().resume();
 
-        let action = Action::Conditional(Conditional {
-            condition: Condition::StrategyBalanceAvailable {
-                amount: Coin::new(1000u128, "x/ruji"),
-            },
-            action: Box::new(Action::Many(nested_actions)),
-        });
+        assert_eq!(
+            strategy
+                .harness
+                .query_strategy_stats(&strategy.strategy_addr)
+                .executions,
+            1
+        );
 
-        let result = StrategyBuilder::new(&mut harness)
-            .with_action(action)
-            .try_instantiate(&[]);
+        strategy.execute();
 
-        assert!(result.is_err());
+        assert_eq!(
+            strategy
+                .harness
+                .query_strategy_stats(&strategy.strategy_addr)
+                .executions,
+            2
+        );
     }
 
     #[test]
-    fn test_instantiate_conditional_action_with_too_many_conditions_fails() {
+    fn test_execute_conditional_action_stops_once_total_debited_reached() {
         let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
 
-        let action = Action::Conditional(Conditional {
-            condition: Condition::Composite(CompositeCondition {
-                conditions: vec![
-                    Condition::StrategyBalanceAvailable {
-                        amount: Coin::new(1000u128, "x/ruji"),
-                    };
-                    20
-                ],
-                threshold: Threshold::All,
-            }),
-            action: Box::new(Action::Swap(default_swap_action(&harness))),
-        });
+        StrategyBuilder::new(&mut harness)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::Not(Box::new(Condition::TotalDebited {
+                    amount: swap_action.swap_amount.clone(),
+                    strategy: None,
+                })),
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&[Coin::new(
+                swap_action.swap_amount.amount * Uint128::new(3),
+                swap_action.swap_amount.denom.clone(),
+            )])
+            .execute()
+            .execute()
+            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+    }
+
+    #[test]
+    fn test_instantiate_conditional_action_with_zero_price_change_fails() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
 
         let result = StrategyBuilder::new(&mut harness)
-            .with_action(action)
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::PriceChange {
+                    asset: "BTC-BTC".to_string(),
+                    window: Duration::from_secs(3_600),
+                    direction: Direction::Below,
+                    bps: 0,
+                    strategy: None,
+                },
+                action: Box::new(Action::Swap(swap_action)),
+                on_error: ErrorPolicy::default(),
+            }))
             .try_instantiate(&[]);
 
         assert!(result.is_err());
     }
 
     #[test]
-    fn test_instantiate_conditional_action_succeeds() {
+    fn test_execute_conditional_action_with_price_change_samples_oracle_price() {
         let mut harness = CalcTestApp::setup();
-        let manager = harness.manager_addr.clone();
-        let owner = harness.owner.clone();
-
-        let action = Action::Conditional(Conditional {
-            condition: Condition::StrategyBalanceAvailable {
-                amount: Coin::new(1000u128, "rune"),
-            },
-            action: Box::new(Action::Swap(default_swap_action(&harness))),
-        });
+        let swap_action = default_swap_action(&harness);
+        let source = PriceSource::Oracle {
+            asset: "BTC-BTC".to_string(),
+        };
 
         let mut strategy = StrategyBuilder::new(&mut harness)
-            .with_action(action.clone())
-            .instantiate(&[]);
+            .with_action(Action::Conditional(Conditional {
+                condition: Condition::PriceChange {
+                    asset: "BTC-BTC".to_string(),
+                    window: Duration::from_secs(3_600),
+                    direction: Direction::Below,
+                    bps: 500,
+                    strategy: None,
+                },
+                action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
+            }))
+            .instantiate(&[swap_action.swap_amount.clone()]);
 
-        strategy.assert_config(StrategyConfig {
-            manager,
-            strategy: Strategy {
-                owner,
-                action,
-                state: Committed,
-            },
-            denoms: HashSet::from(["rune".to_string(), "eth-usdc".to_string()]),
-            escrowed: HashSet::from(["eth-usdc".to_string()]),
-        });
+        // BTC-BTC oracle price stubbed at $100,100.00
+        let price = Decimal::from_str("100100").unwrap();
+        let started_at = strategy.harness.app.block_info().time;
+
+        assert_eq!(
+            strategy
+                .harness
+                .query_price_history(&strategy.strategy_addr, source.clone()),
+            vec![PriceSample {
+                time: started_at,
+                price,
+            }]
+        );
+
+        strategy
+            .advance_time(3_600)
+            .execute()
+            .assert_swapped(vec![]);
+
+        assert_eq!(
+            strategy
+                .harness
+                .query_price_history(&strategy.strategy_addr, source),
+            vec![
+                PriceSample {
+                    time: started_at,
+                    price,
+                },
+                PriceSample {
+                    time: started_at.plus_seconds(3_600),
+                    price,
+                },
+            ]
+        );
     }
 
     #[test]
-    fn test_execute_conditional_action_with_satisfied_conditions_executes_action() {
+    fn test_instantiate_conditional_action_with_fast_window_not_shorter_than_slow_fails() {
         let mut harness = CalcTestApp::setup();
-        let fin_pair = harness.query_fin_config(&harness.fin_addr);
-
-        let swap_action = Swap {
-            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
-            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
-            maximum_slippage_bps: 101,
-            adjustment: SwapAmountAdjustment::Fixed,
-            routes: vec![SwapRoute::Fin(FinRoute {
-                pair_address: harness.fin_addr.clone(),
-            })],
-        };
-
-        let funds = vec![Coin::new(
-            swap_action.swap_amount.amount + Uint128::one(),
-            fin_pair.denoms.base(),
-        )];
+        let swap_action = default_swap_action(&harness);
 
-        let mut strategy = StrategyBuilder::new(&mut harness)
+        let result = StrategyBuilder::new(&mut harness)
             .with_action(Action::Conditional(Conditional {
-                condition: Condition::StrategyBalanceAvailable {
-                    amount: swap_action.swap_amount.clone(),
+                condition: Condition::MovingAverageCross {
+                    source: PriceSource::Oracle {
+                        asset: "BTC-BTC".to_string(),
+                    },
+                    fast_window: Duration::from_secs(7_200),
+                    slow_window: Duration::from_secs(3_600),
+                    direction: Direction::Above,
+                    strategy: None,
                 },
-                action: Box::new(Action::Swap(swap_action.clone())),
+                action: Box::new(Action::Swap(swap_action)),
+                on_error: ErrorPolicy::default(),
             }))
-            .instantiate(&funds);
+            .try_instantiate(&[]);
 
-        strategy.assert_swapped(vec![swap_action.swap_amount.clone()]);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_execute_conditional_action_with_unsatisfied_conditions_skips_action() {
+    fn test_execute_conditional_action_with_moving_average_cross_samples_fin_mid_price() {
         let mut harness = CalcTestApp::setup();
-        let fin_pair = harness.query_fin_config(&harness.fin_addr);
-
-        let swap_action = Swap {
-            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
-            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
-            maximum_slippage_bps: 101,
-            adjustment: SwapAmountAdjustment::Fixed,
-            routes: vec![SwapRoute::Fin(FinRoute {
-                pair_address: harness.fin_addr.clone(),
-            })],
+        let swap_action = default_swap_action(&harness);
+        let source = PriceSource::Fin {
+            pair_address: harness.fin_addr.clone(),
         };
 
-        let funds = vec![Coin::new(
-            swap_action.swap_amount.amount + Uint128::one(),
-            fin_pair.denoms.base(),
-        )];
-
-        StrategyBuilder::new(&mut harness)
+        let mut strategy = StrategyBuilder::new(&mut harness)
             .with_action(Action::Conditional(Conditional {
-                condition: Condition::StrategyBalanceAvailable {
-                    amount: Coin::new(
-                        swap_action.swap_amount.amount * Uint128::new(2),
-                        fin_pair.denoms.base(),
-                    ),
+                condition: Condition::MovingAverageCross {
+                    source: source.clone(),
+                    fast_window: Duration::from_secs(3_600),
+                    slow_window: Duration::from_secs(2 * 3_600),
+                    direction: Direction::Above,
+                    strategy: None,
                 },
                 action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
             }))
-            .instantiate(&funds)
+            .instantiate(&[swap_action.swap_amount.clone()]);
+
+        let started_at = strategy.harness.app.block_info().time;
+
+        strategy
+            .advance_time(3_600)
+            .execute()
+            .advance_time(3_600)
+            .execute()
+            // A flat price never puts the fast average above the slow one
             .assert_swapped(vec![]);
+
+        assert_eq!(
+            strategy
+                .harness
+                .query_price_history(&strategy.strategy_addr, source),
+            (0..3)
+                .map(|i| PriceSample {
+                    time: started_at.plus_seconds(i * 3_600),
+                    price: Decimal::one(),
+                })
+                .collect::<Vec<_>>()
+        );
     }
 
     #[test]
-    fn test_execute_condition_action_respects_timestamp_elapsed_condition() {
+    fn test_execute_conditional_action_without_price_change_over_window_executes_inverse() {
         let mut harness = CalcTestApp::setup();
-        let fin_pair = harness.query_fin_config(&harness.fin_addr);
-
-        let swap_action = Swap {
-            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
-            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
-            maximum_slippage_bps: 101,
-            adjustment: SwapAmountAdjustment::Fixed,
-            routes: vec![SwapRoute::Fin(FinRoute {
-                pair_address: harness.fin_addr.clone(),
-            })],
-        };
-
-        let funds = vec![Coin::new(
-            swap_action.swap_amount.amount + Uint128::one(),
-            fin_pair.denoms.base(),
-        )];
-
-        let block_time = harness.app.block_info().time;
+        let swap_action = default_swap_action(&harness);
 
         StrategyBuilder::new(&mut harness)
             .with_action(Action::Conditional(Conditional {
-                condition: Condition::TimestampElapsed(block_time.plus_seconds(60)),
+                condition: Condition::Not(Box::new(Condition::PriceChange {
+                    asset: "BTC-BTC".to_string(),
+                    window: Duration::from_secs(3_600),
+                    direction: Direction::Above,
+                    bps: 100,
+                    strategy: None,
+                })),
                 action: Box::new(Action::Swap(swap_action.clone())),
+                on_error: ErrorPolicy::default(),
             }))
-            .instantiate(&funds)
-            .assert_swapped(vec![])
-            .advance_time(61)
-            .execute()
-            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+            .instantiate(&[swap_action.swap_amount.clone()])
+            .assert_swapped(vec![swap_action.swap_amount]);
     }
 
+    // Schedule Action tests
+
     #[test]
-    fn test_execute_condition_action_respects_block_elapsed_condition() {
+    fn test_instantiate_schedule_action_with_invalid_cron_expression_fails() {
         let mut harness = CalcTestApp::setup();
-        let fin_pair = harness.query_fin_config(&harness.fin_addr);
 
-        let swap_action = Swap {
-            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
-            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
-            maximum_slippage_bps: 101,
-            adjustment: SwapAmountAdjustment::Fixed,
-            routes: vec![SwapRoute::Fin(FinRoute {
-                pair_address: harness.fin_addr.clone(),
-            })],
-        };
+        let action = Action::Schedule(Schedule {
+            action: Box::new(Action::Swap(default_swap_action(&harness))),
+            scheduler: Addr::unchecked("scheduler"),
+            contract_address: Addr::unchecked("manager"),
+            msg: None,
+            cadence: Cadence::Cron {
+                expr: "invalid cron".to_string(),
+                previous: None,
+                jitter: None,
+            },
+            execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
+        });
 
-        let funds = vec![Coin::new(
-            swap_action.swap_amount.amount + Uint128::one(),
-            fin_pair.denoms.base(),
-        )];
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(action)
+            .try_instantiate(&[]);
 
-        let block_height = harness.app.block_info().height;
+        assert!(result.is_err());
+    }
 
-        StrategyBuilder::new(&mut harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::BlocksCompleted(block_height + 60),
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
-            .instantiate(&funds)
-            .assert_swapped(vec![])
-            .advance_blocks(61)
-            .execute()
-            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+    #[test]
+    fn test_instantiate_schedule_action_with_too_many_nested_actions_fails() {
+        let mut harness = CalcTestApp::setup();
+
+        let nested_actions = (1..=11)
+            .map(|_| Action::LimitOrder(default_limit_order_action(&harness)))
+            .collect::<Vec<_>>();
+
+        let action = Action::Schedule(Schedule {
+            action: Box::new(Action::Many(nested_actions)),
+            scheduler: Addr::unchecked("scheduler"),
+            contract_address: Addr::unchecked("manager"),
+            msg: None,
+            cadence: Cadence::Cron {
+                expr: "invalid cron".to_string(),
+                previous: None,
+                jitter: None,
+            },
+            execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
+        });
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(action)
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_execute_condition_action_respects_can_swap_condition() {
+    fn test_instantiate_schedule_action_with_time_cadence_due_executes_immediately() {
         let mut harness = CalcTestApp::setup();
-        let fin_pair = harness.query_fin_config(&harness.fin_addr);
 
         let swap_action = default_swap_action(&harness);
 
+        let action = Action::Schedule(Schedule {
+            action: Box::new(Action::Swap(swap_action.clone())),
+            scheduler: harness.scheduler_addr.clone(),
+            contract_address: harness.manager_addr.clone(),
+            msg: None,
+            cadence: Cadence::Time {
+                duration: Duration::from_secs(60),
+                previous: None,
+                jitter: None,
+            },
+            execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
+        });
+
         let funds = vec![Coin::new(
-            swap_action.swap_amount.amount + Uint128::one(),
-            fin_pair.denoms.base(),
+            100_000u128,
+            swap_action.swap_amount.denom.clone(),
         )];
 
         StrategyBuilder::new(&mut harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::CanSwap(Swap {
-                    minimum_receive_amount: Coin::new(20_000_000u128, fin_pair.denoms.quote()),
-                    ..swap_action.clone()
-                }),
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
-            .instantiate(&funds)
-            .assert_swapped(vec![]);
-
-        StrategyBuilder::new(&mut harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::CanSwap(Swap {
-                    minimum_receive_amount: Coin::new(20u128, fin_pair.denoms.quote()),
-                    ..swap_action.clone()
-                }),
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
+            .with_action(action.clone())
             .instantiate(&funds)
             .assert_swapped(vec![swap_action.swap_amount.clone()]);
     }
 
     #[test]
-    fn test_execute_condition_action_respects_balance_available_condition() {
+    fn test_instantiate_schedule_action_with_time_cadence_not_due_executes_immediately() {
         let mut harness = CalcTestApp::setup();
-        let fin_pair = harness.query_fin_config(&harness.fin_addr);
 
-        let swap_action = Swap {
-            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
-            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
-            maximum_slippage_bps: 101,
-            adjustment: SwapAmountAdjustment::Fixed,
-            routes: vec![SwapRoute::Fin(FinRoute {
-                pair_address: harness.fin_addr.clone(),
-            })],
-        };
+        let swap_action = default_swap_action(&harness);
+
+        let action = Action::Schedule(Schedule {
+            action: Box::new(Action::Swap(swap_action.clone())),
+            scheduler: harness.scheduler_addr.clone(),
+            contract_address: harness.manager_addr.clone(),
+            msg: None,
+            cadence: Cadence::Time {
+                duration: Duration::from_secs(60),
+                previous: Some(harness.app.block_info().time),
+                jitter: None,
+            },
+            execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
+        });
 
         let funds = vec![Coin::new(
-            swap_action.swap_amount.amount + Uint128::one(),
-            fin_pair.denoms.base(),
+            100_000u128,
+            swap_action.swap_amount.denom.clone(),
         )];
 
-        let random = harness.app.api().addr_make("random");
-        let owner = harness.owner.clone();
-
-        StrategyBuilder::new(&mut harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::BalanceAvailable {
-                    address: random,
-                    amount: Coin::new(1u128, fin_pair.denoms.base()),
-                },
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
-            .instantiate(&funds)
-            .assert_swapped(vec![]);
-
         StrategyBuilder::new(&mut harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::BalanceAvailable {
-                    address: owner,
-                    amount: Coin::new(1u128, fin_pair.denoms.base()),
-                },
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
+            .with_action(action.clone())
             .instantiate(&funds)
+            .assert_swapped(vec![])
+            .advance_time(61)
+            .execute_triggers(ConditionFilter::Timestamp {
+                start: None,
+                end: None,
+            })
             .assert_swapped(vec![swap_action.swap_amount.clone()]);
     }
 
     #[test]
-    fn test_execute_condition_action_respects_strategy_balance_available_condition() {
+    fn test_instantiate_schedule_action_with_block_cadence_due_executes_immediately() {
         let mut harness = CalcTestApp::setup();
-        let fin_pair = harness.query_fin_config(&harness.fin_addr);
 
-        let swap_action = Swap {
-            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
-            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
-            maximum_slippage_bps: 101,
-            adjustment: SwapAmountAdjustment::Fixed,
-            routes: vec![SwapRoute::Fin(FinRoute {
-                pair_address: harness.fin_addr.clone(),
-            })],
-        };
+        let swap_action = default_swap_action(&harness);
+
+        let action = Action::Schedule(Schedule {
+            action: Box::new(Action::Swap(swap_action.clone())),
+            scheduler: harness.scheduler_addr.clone(),
+            contract_address: harness.manager_addr.clone(),
+            msg: None,
+            cadence: Cadence::Blocks {
+                interval: 60,
+                previous: None,
+                jitter: None,
+            },
+            execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
+        });
 
         let funds = vec![Coin::new(
-            swap_action.swap_amount.amount + Uint128::one(),
-            fin_pair.denoms.base(),
+            100_000u128,
+            swap_action.swap_amount.denom.clone(),
         )];
 
         StrategyBuilder::new(&mut harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::StrategyBalanceAvailable {
-                    amount: Coin::new(1u128, fin_pair.denoms.base()),
-                },
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
-            .instantiate(&[])
-            .assert_swapped(vec![]);
-
-        StrategyBuilder::new(&mut harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::StrategyBalanceAvailable {
-                    amount: funds[0].clone(),
-                },
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
+            .with_action(action.clone())
             .instantiate(&funds)
             .assert_swapped(vec![swap_action.swap_amount.clone()]);
     }
 
     #[test]
-    fn test_execute_condition_action_respects_strategy_status_condition() {
+    fn test_instantiate_schedule_action_with_block_cadence_not_due_executes_immediately() {
         let mut harness = CalcTestApp::setup();
-        let fin_pair = harness.query_fin_config(&harness.fin_addr);
-
-        let swap_action = Swap {
-            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
-            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
-            maximum_slippage_bps: 101,
-            adjustment: SwapAmountAdjustment::Fixed,
-            routes: vec![SwapRoute::Fin(FinRoute {
-                pair_address: harness.fin_addr.clone(),
-            })],
-        };
-
-        let funds = vec![Coin::new(
-            swap_action.swap_amount.amount + Uint128::one(),
-            fin_pair.denoms.base(),
-        )];
-
-        let manager = harness.manager_addr.clone();
-        let strategy_action = Action::Swap(default_swap_action(&harness));
-
-        let strategy = StrategyBuilder::new(&mut harness)
-            .with_action(strategy_action)
-            .instantiate(&[Coin::new(100_000u128, "x/ruji")]);
-
-        StrategyBuilder::new(strategy.harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::StrategyStatus {
-                    manager_contract: manager.clone(),
-                    contract_address: strategy.strategy_addr.clone(),
-                    status: StrategyStatus::Archived,
-                },
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
-            .instantiate(&funds)
-            .assert_swapped(vec![]);
-
-        StrategyBuilder::new(strategy.harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::StrategyStatus {
-                    manager_contract: manager,
-                    contract_address: strategy.strategy_addr.clone(),
-                    status: StrategyStatus::Active,
-                },
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
-            .instantiate(&funds)
-            .assert_swapped(vec![swap_action.swap_amount.clone()]);
-    }
 
-    #[test]
-    fn test_execute_condition_action_respects_not_condition() {
-        let mut harness = CalcTestApp::setup();
-        let fin_pair = harness.query_fin_config(&harness.fin_addr);
+        let swap_action = default_swap_action(&harness);
 
-        let swap_action = Swap {
-            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
-            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
-            maximum_slippage_bps: 101,
-            adjustment: SwapAmountAdjustment::Fixed,
-            routes: vec![SwapRoute::Fin(FinRoute {
-                pair_address: harness.fin_addr.clone(),
-            })],
-        };
+        let action = Action::Schedule(Schedule {
+            action: Box::new(Action::Swap(swap_action.clone())),
+            scheduler: harness.scheduler_addr.clone(),
+            contract_address: harness.manager_addr.clone(),
+            msg: None,
+            cadence: Cadence::Blocks {
+                interval: 60,
+                previous: Some(harness.app.block_info().height),
+                jitter: None,
+            },
+            execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
+        });
 
         let funds = vec![Coin::new(
-            swap_action.swap_amount.amount + Uint128::one(),
-            fin_pair.denoms.base(),
+            100_000u128,
+            swap_action.swap_amount.denom.clone(),
         )];
 
-        let manager = harness.manager_addr.clone();
-        let strategy_action = Action::Swap(default_swap_action(&harness));
-
-        let strategy = StrategyBuilder::new(&mut harness)
-            .with_action(strategy_action)
-            .instantiate(&[Coin::new(100_000u128, "x/ruji")]);
-
-        StrategyBuilder::new(strategy.harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::StrategyStatus {
-                    manager_contract: manager.clone(),
-                    contract_address: strategy.strategy_addr.clone(),
-                    status: StrategyStatus::Archived,
-                },
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
-            .instantiate(&funds)
-            .assert_swapped(vec![]);
-
-        StrategyBuilder::new(strategy.harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::Not(Box::new(Condition::StrategyStatus {
-                    manager_contract: manager,
-                    contract_address: strategy.strategy_addr.clone(),
-                    status: StrategyStatus::Archived,
-                })),
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
+        StrategyBuilder::new(&mut harness)
+            .with_action(action.clone())
             .instantiate(&funds)
+            .assert_swapped(vec![])
+            .advance_blocks(61)
+            .execute_triggers(ConditionFilter::BlockHeight {
+                start: None,
+                end: None,
+            })
             .assert_swapped(vec![swap_action.swap_amount.clone()]);
     }
 
     #[test]
-    fn test_execute_condition_action_respects_composite_condition_threshold() {
+    fn test_instantiate_schedule_action_with_cron_cadence_due_executes_immediately() {
         let mut harness = CalcTestApp::setup();
-        let fin_pair = harness.query_fin_config(&harness.fin_addr);
 
-        let swap_action = Swap {
-            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
-            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
-            maximum_slippage_bps: 101,
-            adjustment: SwapAmountAdjustment::Fixed,
-            routes: vec![SwapRoute::Fin(FinRoute {
-                pair_address: harness.fin_addr.clone(),
-            })],
-        };
+        let swap_action = default_swap_action(&harness);
+
+        let action = Action::Schedule(Schedule {
+            action: Box::new(Action::Swap(swap_action.clone())),
+            scheduler: harness.scheduler_addr.clone(),
+            contract_address: harness.manager_addr.clone(),
+            msg: None,
+            cadence: Cadence::Cron {
+                expr: "0 0 * * * *".to_string(),
+                previous: None,
+                jitter: None,
+            },
+            execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
+        });
 
         let funds = vec![Coin::new(
-            swap_action.swap_amount.amount + Uint128::one(),
-            fin_pair.denoms.base(),
+            100_000u128,
+            swap_action.swap_amount.denom.clone(),
         )];
 
-        let manager = harness.manager_addr.clone();
-        let strategy_action = Action::Swap(default_swap_action(&harness));
-
-        let strategy = StrategyBuilder::new(&mut harness)
-            .with_action(strategy_action)
-            .instantiate(&[Coin::new(100_000u128, "x/ruji")]);
-
-        StrategyBuilder::new(strategy.harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::Composite(CompositeCondition {
-                    conditions: vec![
-                        Condition::StrategyStatus {
-                            manager_contract: manager.clone(),
-                            contract_address: strategy.strategy_addr.clone(),
-                            status: StrategyStatus::Archived,
-                        },
-                        Condition::StrategyBalanceAvailable {
-                            amount: funds[0].clone(),
-                        },
-                    ],
-                    threshold: Threshold::All,
-                }),
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
-            .instantiate(&funds)
-            .assert_swapped(vec![]);
-
-        StrategyBuilder::new(strategy.harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::Composite(CompositeCondition {
-                    conditions: vec![
-                        Condition::StrategyStatus {
-                            manager_contract: manager.clone(),
-                            contract_address: strategy.strategy_addr.clone(),
-                            status: StrategyStatus::Archived,
-                        },
-                        Condition::StrategyBalanceAvailable {
-                            amount: funds[0].clone(),
-                        },
-                    ],
-                    threshold: Threshold::Any,
-                }),
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
+        StrategyBuilder::new(&mut harness)
+            .with_action(action.clone())
             .instantiate(&funds)
             .assert_swapped(vec![swap_action.swap_amount.clone()]);
     }
 
     #[test]
-    fn test_execute_condition_action_respects_oracle_price_condition() {
+    fn test_instantiate_schedule_action_with_cron_cadence_not_due_executes_immediately() {
         let mut harness = CalcTestApp::setup();
-        let fin_pair = harness.query_fin_config(&harness.fin_addr);
 
-        let swap_action = Swap {
-            swap_amount: Coin::new(1000u128, fin_pair.denoms.base()),
-            minimum_receive_amount: Coin::new(1u128, fin_pair.denoms.quote()),
-            maximum_slippage_bps: 101,
-            adjustment: SwapAmountAdjustment::Fixed,
-            routes: vec![SwapRoute::Fin(FinRoute {
-                pair_address: harness.fin_addr.clone(),
-            })],
-        };
+        let swap_action = default_swap_action(&harness);
+
+        let action = Action::Schedule(Schedule {
+            action: Box::new(Action::Swap(swap_action.clone())),
+            scheduler: harness.scheduler_addr.clone(),
+            contract_address: harness.manager_addr.clone(),
+            msg: None,
+            cadence: Cadence::Cron {
+                expr: "0 0 * * * *".to_string(),
+                previous: Some(harness.app.block_info().time),
+                jitter: None,
+            },
+            execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
+        });
 
         let funds = vec![Coin::new(
-            swap_action.swap_amount.amount + Uint128::one(),
-            fin_pair.denoms.base(),
+            100_000u128,
+            swap_action.swap_amount.denom.clone(),
         )];
 
-        let strategy_action = Action::Swap(default_swap_action(&harness));
-
-        let strategy = StrategyBuilder::new(&mut harness)
-            .with_action(strategy_action)
-            .instantiate(&[Coin::new(100_000u128, "x/ruji")]);
-
-        // BTC-BTC oracle price stubbed at $100,100.00
-
-        StrategyBuilder::new(strategy.harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::OraclePrice {
-                    asset: "BTC-BTC".to_string(),
-                    rate: Decimal::from_str("100000").unwrap(),
-                    direction: Direction::Below,
-                },
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
-            .instantiate(&funds)
-            .assert_swapped(vec![]);
-
-        StrategyBuilder::new(strategy.harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::OraclePrice {
-                    asset: "BTC-BTC".to_string(),
-                    rate: Decimal::from_str("101000").unwrap(),
-                    direction: Direction::Below,
-                },
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
-            .instantiate(&funds)
-            .assert_swapped(vec![swap_action.swap_amount.clone()]);
-
-        StrategyBuilder::new(strategy.harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::OraclePrice {
-                    asset: "BTC-BTC".to_string(),
-                    rate: Decimal::from_str("101000").unwrap(),
-                    direction: Direction::Above,
-                },
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
-            .instantiate(&funds)
-            .assert_swapped(vec![]);
-
-        StrategyBuilder::new(strategy.harness)
-            .with_action(Action::Conditional(Conditional {
-                condition: Condition::OraclePrice {
-                    asset: "BTC-BTC".to_string(),
-                    rate: Decimal::from_str("100000").unwrap(),
-                    direction: Direction::Above,
-                },
-                action: Box::new(Action::Swap(swap_action.clone())),
-            }))
+        StrategyBuilder::new(&mut harness)
+            .with_action(action.clone())
             .instantiate(&funds)
+            .assert_swapped(vec![])
+            .advance_time(3601)
             .assert_swapped(vec![swap_action.swap_amount.clone()]);
     }
 
-    // Schedule Action tests
-
     #[test]
-    fn test_instantiate_schedule_action_with_invalid_cron_expression_fails() {
+    fn test_crank_time_schedule_sets_and_resets_triggers() {
         let mut harness = CalcTestApp::setup();
 
+        let swap_action = default_swap_action(&harness);
+
         let action = Action::Schedule(Schedule {
-            action: Box::new(Action::Swap(default_swap_action(&harness))),
-            scheduler: Addr::unchecked("scheduler"),
-            contract_address: Addr::unchecked("manager"),
+            action: Box::new(Action::Swap(swap_action.clone())),
+            scheduler: harness.scheduler_addr.clone(),
+            contract_address: harness.manager_addr.clone(),
             msg: None,
-            cadence: Cadence::Cron {
-                expr: "invalid cron".to_string(),
-                previous: None,
+            cadence: Cadence::Time {
+                duration: Duration::from_secs(60),
+                previous: Some(harness.app.block_info().time),
+                jitter: None,
             },
             execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
         });
 
-        let result = StrategyBuilder::new(&mut harness)
-            .with_action(action)
-            .try_instantiate(&[]);
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount * Uint128::new(20),
+            swap_action.swap_amount.denom.clone(),
+        )];
 
-        assert!(result.is_err());
+        StrategyBuilder::new(&mut harness)
+            .with_action(action.clone())
+            .instantiate(&funds)
+            .assert_swapped(vec![])
+            .advance_time(62)
+            .advance_time(62)
+            .advance_time(62)
+            .advance_time(62)
+            .advance_time(62)
+            .assert_swapped(vec![Coin::new(
+                swap_action.swap_amount.amount * Uint128::new(5),
+                swap_action.swap_amount.denom.clone(),
+            )]);
     }
 
     #[test]
-    fn test_instantiate_schedule_action_with_too_many_nested_actions_fails() {
+    fn test_schedule_action_deposits_execution_rebate() {
         let mut harness = CalcTestApp::setup();
 
-        let nested_actions = (1..=11)
-            .map(|_| Action::LimitOrder(default_limit_order_action(&harness)))
-            .collect::<Vec<_>>();
+        let swap_action = default_swap_action(&harness);
 
         let action = Action::Schedule(Schedule {
-            action: Box::new(Action::Many(nested_actions)),
-            scheduler: Addr::unchecked("scheduler"),
-            contract_address: Addr::unchecked("manager"),
+            action: Box::new(Action::Swap(swap_action.clone())),
+            scheduler: harness.scheduler_addr.clone(),
+            contract_address: harness.manager_addr.clone(),
             msg: None,
-            cadence: Cadence::Cron {
-                expr: "invalid cron".to_string(),
-                previous: None,
+            cadence: Cadence::Time {
+                duration: Duration::from_secs(60),
+                previous: Some(harness.app.block_info().time),
+                jitter: None,
             },
-            execution_rebate: vec![],
+            execution_rebate: vec![Coin::new(1u128, "x/ruji")],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
         });
 
-        let result = StrategyBuilder::new(&mut harness)
-            .with_action(action)
-            .try_instantiate(&[]);
+        let funds = vec![
+            Coin::new(
+                swap_action.swap_amount.amount * Uint128::new(20),
+                swap_action.swap_amount.denom.clone(),
+            ),
+            Coin::new(10u128, "x/ruji"),
+        ];
+
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(action.clone())
+            .instantiate(&funds);
+
+        let keeper = strategy.keeper.clone();
+
+        strategy
+            .assert_bank_balance(&Coin::new(10u128, "x/ruji"))
+            .advance_time(62)
+            .advance_time(62)
+            .advance_time(62)
+            .advance_time(62)
+            .advance_time(62)
+            .assert_swapped(vec![Coin::new(
+                swap_action.swap_amount.amount * Uint128::new(5),
+                swap_action.swap_amount.denom.clone(),
+            )])
+            .assert_bank_balance(&Coin::new(5u128, "x/ruji"));
+
+        let keeper_balance = strategy
+            .harness
+            .app
+            .wrap()
+            .query_balance(keeper, "x/ruji")
+            .unwrap();
 
-        assert!(result.is_err());
+        assert_eq!(keeper_balance, Coin::new(5u128, "x/ruji"));
     }
 
     #[test]
-    fn test_instantiate_schedule_action_with_time_cadence_due_executes_immediately() {
+    fn test_once_schedule_action_executes_only_once() {
         let mut harness = CalcTestApp::setup();
 
         let swap_action = default_swap_action(&harness);
@@ -2951,26 +5479,36 @@ mod integration_tests {
             scheduler: harness.scheduler_addr.clone(),
             contract_address: harness.manager_addr.clone(),
             msg: None,
-            cadence: Cadence::Time {
-                duration: Duration::from_secs(60),
-                previous: None,
+            cadence: Cadence::Once {
+                at: Moment::Timestamp(harness.app.block_info().time.plus_seconds(60)),
+                jitter: None,
             },
             execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
         });
 
         let funds = vec![Coin::new(
-            100_000u128,
+            swap_action.swap_amount.amount * Uint128::new(20),
             swap_action.swap_amount.denom.clone(),
         )];
 
         StrategyBuilder::new(&mut harness)
-            .with_action(action.clone())
+            .with_action(action)
             .instantiate(&funds)
+            .assert_swapped(vec![])
+            .advance_time(61)
+            .assert_swapped(vec![swap_action.swap_amount.clone()])
+            .advance_time(61)
+            .execute()
+            .advance_time(61)
             .assert_swapped(vec![swap_action.swap_amount.clone()]);
     }
 
     #[test]
-    fn test_instantiate_schedule_action_with_time_cadence_not_due_executes_immediately() {
+    fn test_schedule_action_stops_after_max_runs() {
         let mut harness = CalcTestApp::setup();
 
         let swap_action = default_swap_action(&harness);
@@ -2983,61 +5521,76 @@ mod integration_tests {
             cadence: Cadence::Time {
                 duration: Duration::from_secs(60),
                 previous: Some(harness.app.block_info().time),
+                jitter: None,
             },
             execution_rebate: vec![],
+            until: None,
+            max_runs: Some(2),
+            runs: 0,
+            completed: false,
         });
 
         let funds = vec![Coin::new(
-            100_000u128,
+            swap_action.swap_amount.amount * Uint128::new(20),
             swap_action.swap_amount.denom.clone(),
         )];
 
         StrategyBuilder::new(&mut harness)
-            .with_action(action.clone())
+            .with_action(action)
             .instantiate(&funds)
-            .assert_swapped(vec![])
-            .advance_time(61)
-            .execute_triggers(ConditionFilter::Timestamp {
-                start: None,
-                end: None,
-            })
-            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+            .advance_time(62)
+            .advance_time(62)
+            .advance_time(62)
+            .advance_time(62)
+            .execute()
+            .assert_swapped(vec![Coin::new(
+                swap_action.swap_amount.amount * Uint128::new(2),
+                swap_action.swap_amount.denom.clone(),
+            )]);
     }
 
     #[test]
-    fn test_instantiate_schedule_action_with_block_cadence_due_executes_immediately() {
+    fn test_schedule_action_reports_completion_once() {
         let mut harness = CalcTestApp::setup();
 
         let swap_action = default_swap_action(&harness);
 
         let action = Action::Schedule(Schedule {
             action: Box::new(Action::Swap(swap_action.clone())),
-            scheduler: harness.scheduler_addr.clone(),
-            contract_address: harness.manager_addr.clone(),
-            msg: None,
-            cadence: Cadence::Blocks {
-                interval: 60,
-                previous: None,
+            max_runs: Some(1),
+            cadence: Cadence::Time {
+                duration: Duration::from_secs(60),
+                previous: Some(harness.app.block_info().time),
+                jitter: None,
             },
-            execution_rebate: vec![],
+            ..default_schedule_action(&harness)
         });
 
-        let funds = vec![Coin::new(
-            100_000u128,
-            swap_action.swap_amount.denom.clone(),
-        )];
+        let mut strategy = StrategyBuilder::new(&mut harness)
+            .with_action(action)
+            .instantiate(&[swap_action.swap_amount.clone()]);
 
-        StrategyBuilder::new(&mut harness)
-            .with_action(action.clone())
-            .instantiate(&funds)
-            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+        let completions = |response: AppResponse| {
+            response
+                .events
+                .iter()
+                .filter(|event| event.ty == "wasm-schedule_completed")
+                .count()
+        };
+
+        strategy.harness.advance_time(62);
+        assert_eq!(completions(strategy.try_execute().unwrap()), 1);
+
+        strategy.harness.advance_time(62);
+        assert_eq!(completions(strategy.try_execute().unwrap()), 0);
     }
 
     #[test]
-    fn test_instantiate_schedule_action_with_block_cadence_not_due_executes_immediately() {
+    fn test_schedule_action_stops_after_until_height() {
         let mut harness = CalcTestApp::setup();
 
         let swap_action = default_swap_action(&harness);
+        let height = harness.app.block_info().height;
 
         let action = Action::Schedule(Schedule {
             action: Box::new(Action::Swap(swap_action.clone())),
@@ -3045,91 +5598,111 @@ mod integration_tests {
             contract_address: harness.manager_addr.clone(),
             msg: None,
             cadence: Cadence::Blocks {
-                interval: 60,
-                previous: Some(harness.app.block_info().height),
+                interval: 10,
+                previous: Some(height),
+                jitter: None,
             },
             execution_rebate: vec![],
+            until: Some(Moment::Height(height + 25)),
+            max_runs: None,
+            runs: 0,
+            completed: false,
         });
 
         let funds = vec![Coin::new(
-            100_000u128,
+            swap_action.swap_amount.amount * Uint128::new(20),
             swap_action.swap_amount.denom.clone(),
         )];
 
         StrategyBuilder::new(&mut harness)
-            .with_action(action.clone())
+            .with_action(action)
             .instantiate(&funds)
-            .assert_swapped(vec![])
-            .advance_blocks(61)
-            .execute_triggers(ConditionFilter::BlockHeight {
-                start: None,
-                end: None,
-            })
-            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+            .advance_blocks(10)
+            .advance_blocks(10)
+            .advance_blocks(10)
+            .advance_blocks(10)
+            .execute()
+            .assert_swapped(vec![Coin::new(
+                swap_action.swap_amount.amount * Uint128::new(2),
+                swap_action.swap_amount.denom.clone(),
+            )]);
     }
 
     #[test]
-    fn test_instantiate_schedule_action_with_cron_cadence_due_executes_immediately() {
+    fn test_instantiate_schedule_action_resets_used_runs() {
         let mut harness = CalcTestApp::setup();
 
         let swap_action = default_swap_action(&harness);
 
         let action = Action::Schedule(Schedule {
             action: Box::new(Action::Swap(swap_action.clone())),
-            scheduler: harness.scheduler_addr.clone(),
-            contract_address: harness.manager_addr.clone(),
-            msg: None,
-            cadence: Cadence::Cron {
-                expr: "0 0 * * * *".to_string(),
+            cadence: Cadence::Time {
+                duration: Duration::from_secs(60),
                 previous: None,
+                jitter: None,
             },
-            execution_rebate: vec![],
+            max_runs: Some(1),
+            runs: 1,
+            completed: true,
+            ..default_schedule_action(&harness)
         });
 
-        let funds = vec![Coin::new(
-            100_000u128,
-            swap_action.swap_amount.denom.clone(),
-        )];
-
         StrategyBuilder::new(&mut harness)
-            .with_action(action.clone())
-            .instantiate(&funds)
+            .with_action(action)
+            .instantiate(&[swap_action.swap_amount.clone()])
             .assert_swapped(vec![swap_action.swap_amount.clone()]);
     }
 
     #[test]
-    fn test_instantiate_schedule_action_with_cron_cadence_not_due_executes_immediately() {
+    fn test_instantiate_schedule_action_with_zero_max_runs_fails() {
         let mut harness = CalcTestApp::setup();
 
+        let action = Action::Schedule(Schedule {
+            max_runs: Some(0),
+            ..default_schedule_action(&harness)
+        });
+
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(action)
+            .try_instantiate(&[]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_instantiate_condition_schedule_action_with_unresolvable_query_condition_fails() {
+        let mut harness = CalcTestApp::setup();
         let swap_action = default_swap_action(&harness);
+        let funds = vec![swap_action.swap_amount.clone()];
 
         let action = Action::Schedule(Schedule {
             action: Box::new(Action::Swap(swap_action.clone())),
-            scheduler: harness.scheduler_addr.clone(),
-            contract_address: harness.manager_addr.clone(),
-            msg: None,
-            cadence: Cadence::Cron {
-                expr: "0 0 * * * *".to_string(),
-                previous: Some(harness.app.block_info().time),
+            cadence: Cadence::Condition {
+                condition: Condition::Query {
+                    contract: harness.fin_addr.clone(),
+                    msg: r#"{"book":{"limit":1}}"#.to_string(),
+                    path: "/missing".to_string(),
+                    comparator: Comparator::Equal,
+                    value: "1".to_string(),
+                    max_response_bytes: None,
+                },
+                cooldown: Duration::from_secs(3_600),
+                previous: None,
+                jitter: None,
+                on_error: ErrorPolicy::default(),
             },
-            execution_rebate: vec![],
+            ..default_schedule_action(&harness)
         });
 
-        let funds = vec![Coin::new(
-            100_000u128,
-            swap_action.swap_amount.denom.clone(),
-        )];
+        let result = StrategyBuilder::new(&mut harness)
+            .with_action(action)
+            .try_instantiate(&funds);
 
-        StrategyBuilder::new(&mut harness)
-            .with_action(action.clone())
-            .instantiate(&funds)
-            .assert_swapped(vec![])
-            .advance_time(3601)
-            .assert_swapped(vec![swap_action.swap_amount.clone()]);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_crank_time_schedule_sets_and_resets_triggers() {
+    fn test_condition_schedule_action_executes_at_most_once_per_cooldown() {
         let mut harness = CalcTestApp::setup();
 
         let swap_action = default_swap_action(&harness);
@@ -3139,11 +5712,22 @@ mod integration_tests {
             scheduler: harness.scheduler_addr.clone(),
             contract_address: harness.manager_addr.clone(),
             msg: None,
-            cadence: Cadence::Time {
-                duration: Duration::from_secs(60),
-                previous: Some(harness.app.block_info().time),
+            cadence: Cadence::Condition {
+                condition: Condition::OraclePrice {
+                    asset: "BTC-BTC".to_string(),
+                    rate: Decimal::from_str("100000").unwrap(),
+                    direction: Direction::Above,
+                },
+                cooldown: Duration::from_secs(3_600),
+                previous: None,
+                jitter: None,
+                on_error: ErrorPolicy::default(),
             },
             execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
         });
 
         let funds = vec![Coin::new(
@@ -3152,72 +5736,217 @@ mod integration_tests {
         )];
 
         StrategyBuilder::new(&mut harness)
-            .with_action(action.clone())
+            .with_action(action)
             .instantiate(&funds)
-            .assert_swapped(vec![])
-            .advance_time(62)
-            .advance_time(62)
-            .advance_time(62)
-            .advance_time(62)
-            .advance_time(62)
+            .assert_swapped(vec![swap_action.swap_amount.clone()])
+            .advance_time(60)
+            .execute()
+            .assert_swapped(vec![swap_action.swap_amount.clone()])
+            .advance_time(3_600)
             .assert_swapped(vec![Coin::new(
-                swap_action.swap_amount.amount * Uint128::new(5),
+                swap_action.swap_amount.amount * Uint128::new(2),
                 swap_action.swap_amount.denom.clone(),
             )]);
     }
 
     #[test]
-    fn test_schedule_action_deposits_execution_rebate() {
+    fn test_condition_schedule_action_registers_oracle_price_trigger() {
         let mut harness = CalcTestApp::setup();
 
         let swap_action = default_swap_action(&harness);
 
+        let condition = Condition::OraclePrice {
+            asset: "BTC-BTC".to_string(),
+            rate: Decimal::from_str("100000").unwrap(),
+            direction: Direction::Below,
+        };
+
         let action = Action::Schedule(Schedule {
             action: Box::new(Action::Swap(swap_action.clone())),
             scheduler: harness.scheduler_addr.clone(),
             contract_address: harness.manager_addr.clone(),
             msg: None,
-            cadence: Cadence::Time {
-                duration: Duration::from_secs(60),
-                previous: Some(harness.app.block_info().time),
+            cadence: Cadence::Condition {
+                condition: condition.clone(),
+                cooldown: Duration::from_secs(3_600),
+                previous: None,
+                jitter: None,
+                on_error: ErrorPolicy::default(),
             },
-            execution_rebate: vec![Coin::new(1u128, "x/ruji")],
+            execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
         });
 
-        let funds = vec![
-            Coin::new(
-                swap_action.swap_amount.amount * Uint128::new(20),
-                swap_action.swap_amount.denom.clone(),
-            ),
-            Coin::new(10u128, "x/ruji"),
-        ];
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount * Uint128::new(20),
+            swap_action.swap_amount.denom.clone(),
+        )];
 
         let mut strategy = StrategyBuilder::new(&mut harness)
-            .with_action(action.clone())
+            .with_action(action)
             .instantiate(&funds);
 
-        let keeper = strategy.keeper.clone();
-
-        strategy
-            .assert_bank_balance(&Coin::new(10u128, "x/ruji"))
-            .advance_time(62)
-            .advance_time(62)
-            .advance_time(62)
-            .advance_time(62)
-            .advance_time(62)
-            .assert_swapped(vec![Coin::new(
-                swap_action.swap_amount.amount * Uint128::new(5),
-                swap_action.swap_amount.denom.clone(),
-            )])
-            .assert_bank_balance(&Coin::new(5u128, "x/ruji"));
+        strategy.assert_swapped(vec![]);
 
-        let keeper_balance = strategy
+        let triggers = strategy
             .harness
             .app
             .wrap()
-            .query_balance(keeper, "x/ruji")
+            .query_wasm_smart::<Vec<Trigger>>(
+                strategy.harness.scheduler_addr.clone(),
+                &SchedulerQueryMsg::Filtered {
+                    filter: ConditionFilter::OraclePrice {
+                        asset: "BTC-BTC".to_string(),
+                        start_after: None,
+                    },
+                    limit: None,
+                },
+            )
             .unwrap();
 
-        assert_eq!(keeper_balance, Coin::new(5u128, "x/ruji"));
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].condition, condition);
+    }
+
+    #[test]
+    fn test_condition_schedule_action_applies_error_policy() {
+        let mut harness = CalcTestApp::setup();
+        let swap_action = default_swap_action(&harness);
+        let funds = vec![swap_action.swap_amount.clone()];
+
+        // None means the strategy execution itself should fail
+        let cases = [
+            (ErrorPolicy::TreatAsFalse, Some(false)),
+            (ErrorPolicy::TreatAsTrue, Some(true)),
+            (ErrorPolicy::Fail, None),
+        ];
+
+        for (on_error, swaps) in cases {
+            let schedule = default_schedule_action(&harness);
+
+            let watched = StrategyBuilder::new(&mut harness)
+                .with_action(Action::Swap(swap_action.clone()))
+                .instantiate(&[Coin::new(1000u128, "x/ruji")]);
+
+            let watched_addr = watched.strategy_addr.clone();
+
+            // Not satisfied at first, then unresolvable once the balance is
+            // withdrawn. The oracle price lets the trigger be looked up.
+            let condition = Condition::Composite(CompositeCondition {
+                conditions: vec![
+                    Condition::OraclePrice {
+                        asset: "BTC-BTC".to_string(),
+                        rate: Decimal::from_str("100000").unwrap(),
+                        direction: Direction::Above,
+                    },
+                    Condition::Query {
+                        contract: watched_addr.clone(),
+                        msg: r#"{"balances":["x/ruji"]}"#.to_string(),
+                        path: "/0/amount".to_string(),
+                        comparator: Comparator::LessThan,
+                        value: "1".to_string(),
+                        max_response_bytes: None,
+                    },
+                ],
+                threshold: Threshold::All,
+            });
+
+            let mut strategy = StrategyBuilder::new(watched.harness)
+                .with_action(Action::Schedule(Schedule {
+                    action: Box::new(Action::Swap(swap_action.clone())),
+                    cadence: Cadence::Condition {
+                        condition: condition.clone(),
+                        cooldown: Duration::from_secs(3_600),
+                        previous: None,
+                        jitter: None,
+                        on_error: on_error.clone(),
+                    },
+                    ..schedule
+                }))
+                .instantiate(&funds);
+
+            strategy.assert_swapped(vec![]);
+
+            let triggers = strategy
+                .harness
+                .app
+                .wrap()
+                .query_wasm_smart::<Vec<Trigger>>(
+                    strategy.harness.scheduler_addr.clone(),
+                    &SchedulerQueryMsg::Filtered {
+                        filter: ConditionFilter::OraclePrice {
+                            asset: "BTC-BTC".to_string(),
+                            start_after: None,
+                        },
+                        limit: None,
+                    },
+                )
+                .unwrap();
+
+            let trigger = triggers
+                .iter()
+                .find(|trigger| trigger.condition == condition)
+                .unwrap();
+
+            assert_eq!(trigger.on_error, on_error);
+
+            let owner = strategy.owner.clone();
+
+            strategy
+                .harness
+                .withdraw(&owner, &watched_addr, HashSet::from(["x/ruji".to_string()]))
+                .unwrap();
+
+            match swaps {
+                Some(swaps) => {
+                    strategy
+                        .execute()
+                        .assert_swapped(if swaps { funds.clone() } else { vec![] });
+                }
+                None => {
+                    assert!(strategy.try_execute().is_err());
+                    strategy.assert_swapped(vec![]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_schedule_action_with_jitter_executes_within_window() {
+        let mut harness = CalcTestApp::setup();
+
+        let swap_action = default_swap_action(&harness);
+
+        let action = Action::Schedule(Schedule {
+            action: Box::new(Action::Swap(swap_action.clone())),
+            scheduler: harness.scheduler_addr.clone(),
+            contract_address: harness.manager_addr.clone(),
+            msg: None,
+            cadence: Cadence::Time {
+                duration: Duration::from_secs(60),
+                previous: Some(harness.app.block_info().time),
+                jitter: Some(Duration::from_secs(30)),
+            },
+            execution_rebate: vec![],
+            until: None,
+            max_runs: None,
+            runs: 0,
+            completed: false,
+        });
+
+        let funds = vec![Coin::new(
+            swap_action.swap_amount.amount * Uint128::new(20),
+            swap_action.swap_amount.denom.clone(),
+        )];
+
+        StrategyBuilder::new(&mut harness)
+            .with_action(action)
+            .instantiate(&funds)
+            .assert_swapped(vec![])
+            .advance_time(91)
+            .assert_swapped(vec![swap_action.swap_amount.clone()]);
     }
 }
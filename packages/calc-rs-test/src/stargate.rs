@@ -10,7 +10,7 @@ use cw_multi_test::{
 };
 use serde::de::DeserializeOwned;
 
-use crate::fixtures::{mock_pool, mock_quote_response};
+use crate::fixtures::{mock_mimir, mock_pool, mock_quote_response};
 
 pub type RujiraApp = App<
     BankKeeper,
@@ -99,6 +99,7 @@ impl Stargate for RujiraStargate {
         match request.path.as_str() {
             "/types.Query/QuoteSwap" => mock_quote_response(),
             "/types.Query/Pool" => mock_pool(request.data),
+            "/types.Query/MimirWithKey" => mock_mimir(request.data),
             _ => {
                 anyhow::bail!("Unexpected grpc query: request={:?}", request)
             }
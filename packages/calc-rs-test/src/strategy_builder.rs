@@ -1,5 +1,5 @@
 use calc_rs::{
-    actions::action::Action,
+    actions::{action::Action, reference::Definitions},
     manager::Affiliate,
     strategy::{Json, Strategy},
 };
@@ -14,6 +14,7 @@ pub struct StrategyBuilder<'a> {
     label: String,
     affiliates: Vec<Affiliate>,
     action: Option<Action>,
+    definitions: Definitions,
     keeper: Addr,
 }
 
@@ -28,6 +29,7 @@ impl<'a> StrategyBuilder<'a> {
             label: "Test Strategy".to_string(),
             affiliates: vec![],
             action: None,
+            definitions: vec![],
             keeper,
         }
     }
@@ -37,6 +39,11 @@ impl<'a> StrategyBuilder<'a> {
         self
     }
 
+    pub fn with_definitions(mut self, definitions: Definitions) -> Self {
+        self.definitions = definitions;
+        self
+    }
+
     pub fn with_affiliates(mut self, affiliates: Vec<Affiliate>) -> Self {
         self.affiliates = affiliates;
         self
@@ -46,6 +53,7 @@ impl<'a> StrategyBuilder<'a> {
         let strategy = Strategy {
             owner: self.owner.clone(),
             action: self.action.unwrap(),
+            definitions: self.definitions,
             state: Json,
         };
 
@@ -66,6 +74,7 @@ impl<'a> StrategyBuilder<'a> {
         let strategy = Strategy {
             owner: self.owner.clone(),
             action: self.action.unwrap(),
+            definitions: self.definitions,
             state: Json,
         };
 
@@ -89,6 +98,7 @@ impl<'a> StrategyBuilder<'a> {
         let strategy = Strategy {
             owner: self.owner.clone(),
             action: self.action.unwrap(),
+            definitions: self.definitions,
             state: Json,
         };
 
@@ -113,6 +123,7 @@ impl<'a> StrategyBuilder<'a> {
         let strategy = Strategy {
             owner: self.owner.clone(),
             action: self.action.unwrap(),
+            definitions: self.definitions,
             state: Json,
         };
 
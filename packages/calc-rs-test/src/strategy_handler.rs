@@ -112,6 +112,11 @@ impl<'a> StrategyHandler<'a> {
         self
     }
 
+    pub fn try_execute(&mut self) -> AnyResult<AppResponse> {
+        self.harness
+            .execute_strategy(&self.keeper, &self.strategy_addr)
+    }
+
     pub fn deposit(&mut self, funds: &[Coin]) -> &mut Self {
         self.harness
             .fund_contract(&self.owner, &self.strategy_addr, funds);
@@ -153,6 +158,14 @@ impl<'a> StrategyHandler<'a> {
             .withdraw(&self.owner, &self.strategy_addr, denoms)
     }
 
+    pub fn heartbeat(&mut self) -> &mut Self {
+        println!("[StrategyHandler] Sending owner heartbeat");
+        self.harness
+            .heartbeat(&self.owner, &self.strategy_addr)
+            .unwrap();
+        self
+    }
+
     pub fn config(self) -> StrategyConfig {
         self.harness.query_strategy_config(&self.strategy_addr)
     }
@@ -5,14 +5,23 @@ use cosmwasm_std::{Coins, Deps, Env, Event, StdResult};
 
 use crate::{
     actions::{
+        basket::Basket,
+        call::Call,
         conditional::Conditional,
         distribution::Distribution,
+        grid::Grid,
         limit_order::LimitOrder,
+        liquidity::Liquidity,
+        market_maker::MarketMaker,
         operation::{StatefulOperation, StatelessOperation},
+        protective_exit::ProtectiveExit,
+        rebalance::Rebalance,
+        reference::{Definitions, Ref},
         schedule::Schedule,
         swaps::swap::Swap,
     },
     manager::Affiliate,
+    price::PriceSource,
     strategy::StrategyMsg,
 };
 
@@ -20,10 +29,18 @@ use crate::{
 pub enum Action {
     Swap(Swap),
     LimitOrder(LimitOrder),
+    MarketMaker(MarketMaker),
+    Grid(Grid),
+    Rebalance(Rebalance),
+    Basket(Basket),
+    Liquidity(Liquidity),
+    ProtectiveExit(ProtectiveExit),
+    Call(Call),
     Distribute(Distribution),
     Schedule(Schedule),
     Conditional(Conditional),
     Many(Vec<Action>),
+    Ref(Ref),
 }
 
 impl Action {
@@ -32,9 +49,46 @@ impl Action {
             Action::Swap(action) => action.routes.len() * 4 + 1,
             Action::Distribute(action) => action.destinations.len() + 1,
             Action::LimitOrder(_) => 4,
-            Action::Schedule(action) => action.action.size() + 1,
+            Action::MarketMaker(_) => 8,
+            Action::Grid(_) => 6,
+            Action::Liquidity(_) => 2,
+            Action::Call(action) => action.funds.len() + 2,
+            Action::ProtectiveExit(action) => {
+                action.routes.len() * 4 + action.take_profits.len() + 2
+            }
+            Action::Basket(action) => {
+                action
+                    .legs
+                    .iter()
+                    .map(|leg| leg.routes.len() * 2)
+                    .sum::<usize>()
+                    + 1
+            }
+            Action::Rebalance(action) => action.routes.len() * 4 + action.targets.len() + 1,
+            Action::Schedule(action) => action.action.size() + action.cadence.size(),
             Action::Conditional(action) => action.action.size() + action.condition.size() + 1,
             Action::Many(actions) => actions.iter().map(|a| a.size()).sum::<usize>() + 1,
+            // Definitions are counted once at the strategy level
+            Action::Ref(_) => 1,
+        }
+    }
+
+    pub fn price_sources(&self) -> Vec<PriceSource> {
+        match self {
+            Action::Conditional(conditional) => conditional
+                .condition
+                .price_sources()
+                .into_iter()
+                .chain(conditional.action.price_sources())
+                .collect(),
+            Action::Schedule(schedule) => schedule
+                .cadence
+                .price_sources()
+                .into_iter()
+                .chain(schedule.action.price_sources())
+                .collect(),
+            Action::Many(actions) => actions.iter().flat_map(|a| a.price_sources()).collect(),
+            _ => vec![],
         }
     }
 
@@ -44,6 +98,9 @@ impl Action {
                 Action::Distribute(distribution.with_affiliates(affiliates)?)
             }
             Action::Swap(swap) => Action::Swap(swap.with_affiliates()),
+            Action::Rebalance(rebalance) => Action::Rebalance(rebalance.with_affiliates()),
+            Action::Basket(basket) => Action::Basket(basket.with_affiliates()),
+            Action::ProtectiveExit(exit) => Action::ProtectiveExit(exit.with_affiliates()),
             Action::Schedule(schedule) => Action::Schedule(Schedule {
                 action: Box::new(Self::add_affiliates(*schedule.action, affiliates)?),
                 ..schedule
@@ -67,47 +124,89 @@ impl Action {
 }
 
 impl StatelessOperation for Action {
-    fn init(self, deps: Deps, env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+    fn init(
+        self,
+        deps: Deps,
+        env: &Env,
+        definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
         match self {
-            Action::Swap(action) => action.init(deps, env),
-            Action::LimitOrder(action) => action.init(deps, env),
-            Action::Distribute(action) => action.init(deps, env),
-            Action::Schedule(action) => action.init(deps, env),
-            Action::Conditional(action) => action.init(deps, env),
-            Action::Many(action) => action.init(deps, env),
+            Action::Swap(action) => action.init(deps, env, definitions),
+            Action::Rebalance(action) => action.init(deps, env, definitions),
+            Action::Basket(action) => action.init(deps, env, definitions),
+            Action::Liquidity(action) => action.init(deps, env, definitions),
+            Action::ProtectiveExit(action) => action.init(deps, env, definitions),
+            Action::Call(action) => action.init(deps, env, definitions),
+            Action::LimitOrder(action) => action.init(deps, env, definitions),
+            Action::MarketMaker(action) => action.init(deps, env, definitions),
+            Action::Grid(action) => action.init(deps, env, definitions),
+            Action::Distribute(action) => action.init(deps, env, definitions),
+            Action::Schedule(action) => action.init(deps, env, definitions),
+            Action::Conditional(action) => action.init(deps, env, definitions),
+            Action::Many(action) => action.init(deps, env, definitions),
+            Action::Ref(reference) => reference.init(deps, env, definitions),
         }
     }
 
-    fn execute(self, deps: Deps, env: &Env) -> (Vec<StrategyMsg>, Vec<Event>, Action) {
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
         match self {
-            Action::Swap(action) => action.execute(deps, env),
-            Action::LimitOrder(action) => action.execute(deps, env),
-            Action::Distribute(action) => action.execute(deps, env),
-            Action::Schedule(action) => action.execute(deps, env),
-            Action::Conditional(action) => action.execute(deps, env),
-            Action::Many(action) => action.execute(deps, env),
+            Action::Swap(action) => action.execute(deps, env, definitions),
+            Action::Rebalance(action) => action.execute(deps, env, definitions),
+            Action::Basket(action) => action.execute(deps, env, definitions),
+            Action::Liquidity(action) => action.execute(deps, env, definitions),
+            Action::ProtectiveExit(action) => action.execute(deps, env, definitions),
+            Action::Call(action) => action.execute(deps, env, definitions),
+            Action::LimitOrder(action) => action.execute(deps, env, definitions),
+            Action::MarketMaker(action) => action.execute(deps, env, definitions),
+            Action::Grid(action) => action.execute(deps, env, definitions),
+            Action::Distribute(action) => action.execute(deps, env, definitions),
+            Action::Schedule(action) => action.execute(deps, env, definitions),
+            Action::Conditional(action) => action.execute(deps, env, definitions),
+            Action::Many(action) => action.execute(deps, env, definitions),
+            Action::Ref(reference) => reference.execute(deps, env, definitions),
         }
     }
 
     fn denoms(&self, deps: Deps, env: &Env) -> StdResult<HashSet<String>> {
         match self {
             Action::Swap(action) => action.denoms(deps, env),
+            Action::Rebalance(action) => action.denoms(deps, env),
+            Action::Basket(action) => action.denoms(deps, env),
+            Action::Liquidity(action) => action.denoms(deps, env),
+            Action::ProtectiveExit(action) => action.denoms(deps, env),
+            Action::Call(action) => action.denoms(deps, env),
             Action::LimitOrder(action) => action.denoms(deps, env),
+            Action::MarketMaker(action) => action.denoms(deps, env),
+            Action::Grid(action) => action.denoms(deps, env),
             Action::Distribute(action) => action.denoms(deps, env),
             Action::Schedule(action) => action.denoms(deps, env),
             Action::Conditional(action) => action.denoms(deps, env),
             Action::Many(actions) => actions.denoms(deps, env),
+            Action::Ref(reference) => reference.denoms(deps, env),
         }
     }
 
     fn escrowed(&self, deps: Deps, env: &Env) -> StdResult<HashSet<String>> {
         match self {
             Action::Swap(action) => action.escrowed(deps, env),
+            Action::Rebalance(action) => action.escrowed(deps, env),
+            Action::Basket(action) => action.escrowed(deps, env),
+            Action::Liquidity(action) => action.escrowed(deps, env),
+            Action::ProtectiveExit(action) => action.escrowed(deps, env),
+            Action::Call(action) => action.escrowed(deps, env),
             Action::LimitOrder(action) => action.escrowed(deps, env),
+            Action::MarketMaker(action) => action.escrowed(deps, env),
+            Action::Grid(action) => action.escrowed(deps, env),
             Action::Distribute(action) => action.escrowed(deps, env),
             Action::Schedule(action) => action.escrowed(deps, env),
             Action::Conditional(action) => action.escrowed(deps, env),
             Action::Many(action) => action.escrowed(deps, env),
+            Action::Ref(reference) => reference.escrowed(deps, env),
         }
     }
 }
@@ -116,6 +215,8 @@ impl StatefulOperation for Action {
     fn balances(&self, deps: Deps, env: &Env, denoms: &HashSet<String>) -> StdResult<Coins> {
         match self {
             Action::LimitOrder(action) => action.balances(deps, env, denoms),
+            Action::MarketMaker(action) => action.balances(deps, env, denoms),
+            Action::Grid(action) => action.balances(deps, env, denoms),
             Action::Conditional(conditional) => conditional.balances(deps, env, denoms),
             Action::Many(actions) => actions.balances(deps, env, denoms),
             Action::Schedule(schedule) => schedule.balances(deps, env, denoms),
@@ -131,6 +232,8 @@ impl StatefulOperation for Action {
     ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
         match self {
             Action::LimitOrder(action) => action.withdraw(deps, env, desired),
+            Action::MarketMaker(action) => action.withdraw(deps, env, desired),
+            Action::Grid(action) => action.withdraw(deps, env, desired),
             Action::Conditional(conditional) => conditional.withdraw(deps, env, desired),
             Action::Many(actions) => actions.withdraw(deps, env, desired),
             Action::Schedule(schedule) => schedule.withdraw(deps, env, desired),
@@ -141,6 +244,8 @@ impl StatefulOperation for Action {
     fn cancel(self, deps: Deps, env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
         match self {
             Action::LimitOrder(action) => action.cancel(deps, env),
+            Action::MarketMaker(action) => action.cancel(deps, env),
+            Action::Grid(action) => action.cancel(deps, env),
             Action::Conditional(conditional) => conditional.cancel(deps, env),
             Action::Many(actions) => actions.cancel(deps, env),
             Action::Schedule(schedule) => schedule.cancel(deps, env),
@@ -151,6 +256,8 @@ impl StatefulOperation for Action {
     fn commit(self, deps: Deps, env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
         match self {
             Action::LimitOrder(limit_order) => limit_order.commit(deps, env),
+            Action::MarketMaker(market_maker) => market_maker.commit(deps, env),
+            Action::Grid(grid) => grid.commit(deps, env),
             Action::Conditional(conditional) => conditional.commit(deps, env),
             Action::Schedule(scheduled) => scheduled.commit(deps, env),
             Action::Many(actions) => actions.commit(deps, env),
@@ -0,0 +1,246 @@
+use std::{cmp::min, collections::HashSet};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Decimal, Deps, Env, Event, StdError, StdResult};
+
+use crate::{
+    actions::{
+        action::Action,
+        operation::StatelessOperation,
+        reference::Definitions,
+        swaps::swap::{Swap, SwapAmountAdjustment, SwapRoute},
+    },
+    strategy::StrategyMsg,
+};
+
+enum BasketEvent {
+    SkipBasket {
+        reason: String,
+    },
+    SkipBasketLeg {
+        target_denom: String,
+        reason: String,
+    },
+}
+
+impl From<BasketEvent> for Event {
+    fn from(val: BasketEvent) -> Self {
+        match val {
+            BasketEvent::SkipBasket { reason } => {
+                Event::new("skip_basket").add_attribute("reason", reason)
+            }
+            BasketEvent::SkipBasketLeg {
+                target_denom,
+                reason,
+            } => Event::new("skip_basket_leg")
+                .add_attribute("target_denom", target_denom)
+                .add_attribute("reason", reason),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct BasketLeg {
+    pub weight: Decimal,
+    pub minimum_receive_amount: Coin,
+    pub routes: Vec<SwapRoute>,
+}
+
+#[cw_serde]
+pub struct Basket {
+    pub swap_amount: Coin,
+    pub maximum_slippage_bps: u64,
+    pub legs: Vec<BasketLeg>,
+}
+
+impl Basket {
+    pub fn with_affiliates(self) -> Self {
+        Basket {
+            legs: self
+                .legs
+                .into_iter()
+                .map(|leg| BasketLeg {
+                    routes: leg
+                        .routes
+                        .into_iter()
+                        .map(SwapRoute::with_affiliates)
+                        .collect(),
+                    ..leg
+                })
+                .collect(),
+            ..self
+        }
+    }
+
+    fn swap(&self, leg: &BasketLeg, available: Decimal) -> Swap {
+        Swap {
+            swap_amount: Coin::new(
+                self.swap_amount
+                    .amount
+                    .mul_floor(leg.weight)
+                    .mul_floor(available),
+                self.swap_amount.denom.clone(),
+            ),
+            minimum_receive_amount: Coin::new(
+                leg.minimum_receive_amount.amount.mul_ceil(available),
+                leg.minimum_receive_amount.denom.clone(),
+            ),
+            maximum_slippage_bps: self.maximum_slippage_bps,
+            adjustment: SwapAmountAdjustment::Fixed,
+            routes: leg.routes.clone(),
+        }
+    }
+
+    fn execute_unsafe(
+        self,
+        deps: Deps,
+        env: &Env,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.clone(), self.swap_amount.denom.clone())?;
+
+        if balance.amount.is_zero() {
+            return Ok((
+                vec![],
+                vec![BasketEvent::SkipBasket {
+                    reason: format!("No {} balance available", self.swap_amount.denom),
+                }
+                .into()],
+                Action::Basket(self),
+            ));
+        }
+
+        // Split whatever is available so that every leg shrinks
+        // proportionally rather than the last legs going unfunded.
+        let available = Decimal::from_ratio(
+            min(balance.amount, self.swap_amount.amount),
+            self.swap_amount.amount,
+        );
+
+        let mut messages = vec![];
+        let mut events = vec![];
+        let mut legs = vec![];
+
+        for leg in self.legs.iter() {
+            let swap = self.swap(leg, available);
+
+            if swap.swap_amount.amount.is_zero() {
+                legs.push(leg.clone());
+                continue;
+            }
+
+            match swap.execute_unsafe(deps, env) {
+                Ok((swap_messages, swap_events, Action::Swap(swap))) => {
+                    messages.extend(swap_messages);
+                    events.extend(swap_events);
+
+                    legs.push(BasketLeg {
+                        routes: swap.routes,
+                        ..leg.clone()
+                    });
+                }
+                Ok(_) => {
+                    return Err(StdError::generic_err(
+                        "Swap execution returned an unexpected action",
+                    ))
+                }
+                Err(err) => {
+                    events.push(
+                        BasketEvent::SkipBasketLeg {
+                            target_denom: leg.minimum_receive_amount.denom.clone(),
+                            reason: err.to_string(),
+                        }
+                        .into(),
+                    );
+
+                    legs.push(leg.clone());
+                }
+            }
+        }
+
+        Ok((messages, events, Action::Basket(Basket { legs, ..self })))
+    }
+}
+
+impl StatelessOperation for Basket {
+    fn init(
+        self,
+        deps: Deps,
+        env: &Env,
+        definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        if self.legs.is_empty() {
+            return Err(StdError::generic_err("Basket must have at least one leg"));
+        }
+
+        let mut target_denoms = HashSet::new();
+
+        for leg in self.legs.iter() {
+            if leg.weight.is_zero() {
+                return Err(StdError::generic_err("Basket leg weight cannot be zero"));
+            }
+
+            if leg.minimum_receive_amount.denom == self.swap_amount.denom {
+                return Err(StdError::generic_err(
+                    "Basket leg cannot target the swap denom",
+                ));
+            }
+
+            if !target_denoms.insert(leg.minimum_receive_amount.denom.clone()) {
+                return Err(StdError::generic_err(format!(
+                    "Duplicate basket leg target denom: {}",
+                    leg.minimum_receive_amount.denom
+                )));
+            }
+
+            // Reuse the swap validation for each leg's share of the budget
+            self.swap(leg, Decimal::one())
+                .init(deps, env, definitions)?;
+        }
+
+        if self
+            .legs
+            .iter()
+            .fold(Decimal::zero(), |total, leg| total + leg.weight)
+            != Decimal::one()
+        {
+            return Err(StdError::generic_err("Basket leg weights must sum to 1"));
+        }
+
+        Ok((vec![], vec![], Action::Basket(self)))
+    }
+
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        _definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        Ok(match self.clone().execute_unsafe(deps, env) {
+            Ok(result) => result,
+            Err(err) => (
+                vec![],
+                vec![BasketEvent::SkipBasket {
+                    reason: err.to_string(),
+                }
+                .into()],
+                Action::Basket(self),
+            ),
+        })
+    }
+
+    fn denoms(&self, deps: Deps, env: &Env) -> StdResult<HashSet<String>> {
+        let mut denoms = self.escrowed(deps, env)?;
+        denoms.insert(self.swap_amount.denom.clone());
+        Ok(denoms)
+    }
+
+    fn escrowed(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        Ok(self
+            .legs
+            .iter()
+            .map(|leg| leg.minimum_receive_amount.denom.clone())
+            .collect())
+    }
+}
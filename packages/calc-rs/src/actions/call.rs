@@ -0,0 +1,255 @@
+use std::{cmp::min, collections::HashSet};
+
+use cosmwasm_schema::{cw_serde, serde::de::IgnoredAny};
+use cosmwasm_std::{
+    from_json, to_json_string, Addr, Binary, Coin, Coins, Decimal, Deps, Env, Event, StdError,
+    StdResult,
+};
+
+use crate::{
+    actions::{
+        action::Action, distribution::Recipient, operation::StatelessOperation,
+        reference::Definitions,
+    },
+    core::Contract,
+    statistics::Statistics,
+    strategy::{StrategyMsg, StrategyMsgPayload},
+};
+
+enum CallEvent {
+    SkipCall { reason: String },
+    Call { contract: Addr, funds: Vec<Coin> },
+}
+
+impl From<CallEvent> for Event {
+    fn from(val: CallEvent) -> Self {
+        match val {
+            CallEvent::SkipCall { reason } => {
+                Event::new("skip_call").add_attribute("reason", reason)
+            }
+            CallEvent::Call { contract, funds } => Event::new("call_contract")
+                .add_attribute("contract", contract)
+                .add_attribute(
+                    "funds",
+                    to_json_string(&funds).expect("Failed to serialize call funds"),
+                ),
+        }
+    }
+}
+
+#[cw_serde]
+pub enum CallFunds {
+    All { denom: String },
+    Percent { denom: String, percent: Decimal },
+    Fixed { amount: Coin },
+}
+
+impl CallFunds {
+    pub fn denom(&self) -> &str {
+        match self {
+            CallFunds::All { denom } | CallFunds::Percent { denom, .. } => denom,
+            CallFunds::Fixed { amount } => &amount.denom,
+        }
+    }
+
+    fn resolve(&self, deps: Deps, env: &Env) -> StdResult<Coin> {
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.clone(), self.denom())?;
+
+        Ok(match self {
+            CallFunds::All { .. } => balance,
+            CallFunds::Percent { percent, .. } => {
+                Coin::new(balance.amount.mul_floor(*percent), balance.denom)
+            }
+            CallFunds::Fixed { amount } => {
+                Coin::new(min(balance.amount, amount.amount), balance.denom)
+            }
+        })
+    }
+}
+
+fn placeholder_value(placeholder: &str, deps: Deps, env: &Env) -> StdResult<String> {
+    Ok(match placeholder.trim().split_once(':') {
+        Some(("balance", denom)) => deps
+            .querier
+            .query_balance(env.contract.address.clone(), denom)?
+            .amount
+            .to_string(),
+        None if placeholder.trim() == "block_time" => env.block.time.seconds().to_string(),
+        None if placeholder.trim() == "block_height" => env.block.height.to_string(),
+        None if placeholder.trim() == "contract_address" => env.contract.address.to_string(),
+        _ => {
+            return Err(StdError::generic_err(format!(
+                "Unknown placeholder: {placeholder}"
+            )))
+        }
+    })
+}
+
+#[cw_serde]
+pub struct Call {
+    pub contract: Addr,
+    pub msg: String,
+    pub funds: Vec<CallFunds>,
+}
+
+impl Call {
+    pub fn render(&self, deps: Deps, env: &Env) -> StdResult<Binary> {
+        let mut rendered = String::with_capacity(self.msg.len());
+        let mut rest = self.msg.as_str();
+
+        while let Some(start) = rest.find("{{") {
+            let end = rest[start..]
+                .find("}}")
+                .map(|end| start + end)
+                .ok_or_else(|| StdError::generic_err("Unclosed placeholder in call msg"))?;
+
+            rendered.push_str(&rest[..start]);
+            rendered.push_str(&placeholder_value(&rest[start + 2..end], deps, env)?);
+
+            rest = &rest[end + 2..];
+        }
+
+        rendered.push_str(rest);
+
+        from_json::<IgnoredAny>(rendered.as_bytes())
+            .map_err(|e| StdError::generic_err(format!("Call msg is not valid JSON: {e}")))?;
+
+        Ok(Binary::from(rendered.into_bytes()))
+    }
+
+    fn execute_unsafe(
+        self,
+        deps: Deps,
+        env: &Env,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let msg = self.render(deps, env)?;
+
+        let mut funds = Coins::default();
+
+        for template in self.funds.iter() {
+            funds.add(template.resolve(deps, env)?)?;
+        }
+
+        if !self.funds.is_empty() && funds.is_empty() {
+            return Ok((
+                vec![],
+                vec![CallEvent::SkipCall {
+                    reason: "No funds available to send".to_string(),
+                }
+                .into()],
+                Action::Call(self),
+            ));
+        }
+
+        let funds = funds.into_vec();
+
+        let call_message = StrategyMsg::with_payload(
+            Contract(self.contract.clone()).call(msg.clone(), funds.clone()),
+            StrategyMsgPayload {
+                statistics: Statistics {
+                    credited: if funds.is_empty() {
+                        vec![]
+                    } else {
+                        vec![(
+                            Recipient::Contract {
+                                address: self.contract.clone(),
+                                msg,
+                            },
+                            funds.clone(),
+                        )]
+                    },
+                    ..Statistics::default()
+                },
+                events: vec![CallEvent::Call {
+                    contract: self.contract.clone(),
+                    funds,
+                }
+                .into()],
+            },
+        );
+
+        Ok((vec![call_message], vec![], Action::Call(self)))
+    }
+}
+
+impl StatelessOperation for Call {
+    fn init(
+        self,
+        deps: Deps,
+        env: &Env,
+        _definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        deps.api
+            .addr_validate(self.contract.as_str())
+            .map_err(|_| {
+                StdError::generic_err(format!("Invalid contract address: {}", self.contract))
+            })?;
+
+        if self.contract == env.contract.address {
+            return Err(StdError::generic_err("Strategy cannot call itself"));
+        }
+
+        let mut denoms = HashSet::new();
+
+        for template in self.funds.iter() {
+            if !denoms.insert(template.denom()) {
+                return Err(StdError::generic_err(format!(
+                    "Duplicate call funds denom: {}",
+                    template.denom()
+                )));
+            }
+
+            if let CallFunds::Percent { percent, .. } = template {
+                if percent.is_zero() || *percent > Decimal::one() {
+                    return Err(StdError::generic_err(
+                        "Call funds percent must be greater than 0 and at most 1",
+                    ));
+                }
+            }
+
+            if let CallFunds::Fixed { amount } = template {
+                if amount.amount.is_zero() {
+                    return Err(StdError::generic_err("Call funds amount cannot be zero"));
+                }
+            }
+        }
+
+        // Render once up front so malformed templates fail at init
+        self.render(deps, env)?;
+
+        Ok((vec![], vec![], Action::Call(self)))
+    }
+
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        _definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        Ok(match self.clone().execute_unsafe(deps, env) {
+            Ok(result) => result,
+            Err(err) => (
+                vec![],
+                vec![CallEvent::SkipCall {
+                    reason: err.to_string(),
+                }
+                .into()],
+                Action::Call(self),
+            ),
+        })
+    }
+
+    fn denoms(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        Ok(self
+            .funds
+            .iter()
+            .map(|template| template.denom().to_string())
+            .collect())
+    }
+
+    fn escrowed(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        Ok(HashSet::new())
+    }
+}
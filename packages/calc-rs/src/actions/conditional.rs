@@ -7,8 +7,9 @@ use crate::{
     actions::{
         action::Action,
         operation::{StatefulOperation, StatelessOperation},
+        reference::Definitions,
     },
-    conditions::Condition,
+    conditions::{Condition, ErrorPolicy},
     strategy::StrategyMsg,
 };
 
@@ -30,12 +31,21 @@ impl From<ConditionalEvent> for Event {
 pub struct Conditional {
     pub condition: Condition,
     pub action: Box<Action>,
+    #[serde(default)]
+    pub on_error: ErrorPolicy,
 }
 
 impl StatelessOperation for Conditional {
-    fn init(self, deps: Deps, env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+    fn init(
+        self,
+        deps: Deps,
+        env: &Env,
+        definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        self.condition.validate()?;
+
         // We don't care if it's satisfied at init time,
-        // only that the condition itself is valid.
+        // only that the condition itself can be evaluated.
         self.condition.is_satisfied(deps, env)?;
 
         if self.condition.size() > 10 {
@@ -44,7 +54,7 @@ impl StatelessOperation for Conditional {
             ));
         }
 
-        let (messages, events, action) = self.action.init(deps, env)?;
+        let (messages, events, action) = self.action.init(deps, env, definitions)?;
 
         Ok((
             messages,
@@ -56,26 +66,42 @@ impl StatelessOperation for Conditional {
         ))
     }
 
-    fn execute(self, deps: Deps, env: &Env) -> (Vec<StrategyMsg>, Vec<Event>, Action) {
-        if self.condition.is_satisfied(deps, env).unwrap_or(false) {
-            let (msgs, events, action) = self.action.execute(deps, env);
-            (
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        // Under the fail policy an unreadable condition aborts the
+        // whole strategy execution rather than skipping the action
+        let (satisfied, error_event) =
+            self.condition
+                .evaluate(deps, env, &self.on_error)
+                .map_err(|err| {
+                    StdError::generic_err(format!("Failed to evaluate conditions: {err}"))
+                })?;
+
+        let mut events = error_event.into_iter().collect::<Vec<_>>();
+
+        if satisfied {
+            let (msgs, action_events, action) = self.action.execute(deps, env, definitions)?;
+            events.extend(action_events);
+            Ok((
                 msgs,
                 events,
                 Action::Conditional(Conditional {
                     action: Box::new(action),
                     ..self
                 }),
-            )
+            ))
         } else {
-            (
-                vec![],
-                vec![ConditionalEvent::SkipConditionalExecution {
+            events.push(
+                ConditionalEvent::SkipConditionalExecution {
                     reason: "Conditions not met".into(),
                 }
-                .into()],
-                Action::Conditional(self),
-            )
+                .into(),
+            );
+            Ok((vec![], events, Action::Conditional(self)))
         }
     }
 
@@ -1,4 +1,4 @@
-use std::{collections::HashSet, vec};
+use std::{collections::HashSet, str::FromStr, vec};
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
@@ -8,11 +8,12 @@ use cosmwasm_std::{
 
 use crate::actions::action::Action;
 use crate::actions::operation::StatelessOperation;
+use crate::actions::reference::Definitions;
 use crate::constants::MAX_TOTAL_AFFILIATE_BPS;
 use crate::manager::Affiliate;
 use crate::statistics::Statistics;
 use crate::strategy::{StrategyMsg, StrategyMsgPayload};
-use crate::thorchain::MsgDeposit;
+use crate::thorchain::{Memo, MsgDeposit};
 
 enum DistributionEvent {
     SkipDistribution {
@@ -191,7 +192,12 @@ impl Distribution {
 }
 
 impl StatelessOperation for Distribution {
-    fn init(self, deps: Deps, _env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+    fn init(
+        self,
+        deps: Deps,
+        _env: &Env,
+        _definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
         if self.denoms.is_empty() {
             return Err(StdError::generic_err("Denoms cannot be empty"));
         }
@@ -220,6 +226,10 @@ impl StatelessOperation for Distribution {
                             "Only secured assets can be deposited with memo {memo}"
                         )));
                     }
+
+                    Memo::from_str(memo).map_err(|e| {
+                        StdError::generic_err(format!("Invalid deposit memo {memo}: {e}"))
+                    })?;
                 }
             }
 
@@ -235,9 +245,14 @@ impl StatelessOperation for Distribution {
         Ok((vec![], vec![], Action::Distribute(self)))
     }
 
-    fn execute(self, deps: Deps, env: &Env) -> (Vec<StrategyMsg>, Vec<Event>, Action) {
-        match self.clone().execute_unsafe(deps, env) {
-            Ok((action, messages, events)) => (action, messages, events),
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        _definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        Ok(match self.clone().execute_unsafe(deps, env) {
+            Ok(result) => result,
             Err(err) => (
                 vec![],
                 vec![DistributionEvent::SkipDistribution {
@@ -246,7 +261,7 @@ impl StatelessOperation for Distribution {
                 .into()],
                 Action::Distribute(self),
             ),
-        }
+        })
     }
 
     fn denoms(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
@@ -0,0 +1,540 @@
+use std::{collections::HashSet, vec};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, to_json_string, Addr, Coin, Coins, Decimal, Deps, Env, Event, StdError,
+    StdResult, Uint128,
+};
+use rujira_rs::fin::{ConfigResponse, ExecuteMsg, OrderResponse, Price, QueryMsg, Side};
+
+use crate::{
+    actions::{
+        action::Action,
+        operation::{StatefulOperation, StatelessOperation},
+        reference::Definitions,
+    },
+    core::Contract,
+    price::fin_mid_price,
+    statistics::Statistics,
+    strategy::{StrategyMsg, StrategyMsgPayload},
+};
+
+enum GridEvent {
+    SkipGrid { reason: String },
+    SkipLevel { level: u8, reason: String },
+    SetOrders { orders: Vec<GridOrder> },
+    ClaimOrders { orders: Vec<GridOrder> },
+    RealiseProfit { profit: Coin },
+}
+
+impl From<GridEvent> for Event {
+    fn from(val: GridEvent) -> Self {
+        match val {
+            GridEvent::SkipGrid { reason } => {
+                Event::new("skip_grid").add_attribute("reason", reason)
+            }
+            GridEvent::SkipLevel { level, reason } => Event::new("skip_grid_level")
+                .add_attribute("level", level.to_string())
+                .add_attribute("reason", reason),
+            GridEvent::SetOrders { orders } => Event::new("set_grid_orders").add_attribute(
+                "orders",
+                to_json_string(&orders).expect("Failed to serialize grid orders"),
+            ),
+            GridEvent::ClaimOrders { orders } => Event::new("claim_grid_orders").add_attribute(
+                "orders",
+                to_json_string(&orders).expect("Failed to serialize grid orders"),
+            ),
+            GridEvent::RealiseProfit { profit } => {
+                Event::new("realise_grid_profit").add_attribute("profit", profit.to_string())
+            }
+        }
+    }
+}
+
+#[cw_serde]
+pub struct GridOrder {
+    pub level: u8,
+    pub side: Side,
+    pub price: Decimal,
+    pub offer: Uint128,
+    pub cost_price: Option<Decimal>,
+}
+
+impl GridOrder {
+    fn key(&self) -> (u8, String) {
+        (self.level, self.side.to_string())
+    }
+
+    fn refresh(&self, deps: Deps, env: &Env, pair_address: &Addr) -> StdResult<OrderResponse> {
+        deps.querier.query_wasm_smart::<OrderResponse>(
+            pair_address.clone(),
+            &QueryMsg::Order((
+                env.contract.address.to_string(),
+                self.side.clone(),
+                Price::Fixed(self.price),
+            )),
+        )
+    }
+}
+
+#[cw_serde]
+pub struct Grid {
+    pub pair_address: Addr,
+    pub lower_price: Decimal,
+    pub upper_price: Decimal,
+    pub levels: u8,
+    pub size: Uint128,
+    pub orders: Vec<GridOrder>,
+    pub realised_profit: Uint128,
+}
+
+impl Grid {
+    pub fn get_pair(&self, deps: Deps) -> StdResult<ConfigResponse> {
+        deps.querier
+            .query_wasm_smart::<ConfigResponse>(self.pair_address.clone(), &QueryMsg::Config {})
+    }
+
+    pub fn level_price(&self, level: u8) -> Decimal {
+        self.lower_price.saturating_add(
+            (self.upper_price - self.lower_price)
+                .saturating_mul(Decimal::from_ratio(level, self.levels - 1)),
+        )
+    }
+
+    fn layout(
+        &self,
+        deps: Deps,
+        env: &Env,
+        pair: &ConfigResponse,
+    ) -> StdResult<(Vec<GridOrder>, Vec<Event>)> {
+        let mid_price = fin_mid_price(deps, &self.pair_address)?;
+
+        let mut available_base = deps
+            .querier
+            .query_balance(&env.contract.address, pair.denoms.base())?
+            .amount;
+
+        let mut available_quote = deps
+            .querier
+            .query_balance(&env.contract.address, pair.denoms.quote())?
+            .amount;
+
+        let mut orders = vec![];
+        let mut events = vec![];
+
+        for level in 0..self.levels {
+            let price = self.level_price(level);
+
+            // Sell base above the mid price, buy base below it, and
+            // leave any level sitting exactly on the mid price empty.
+            let (side, offer, available) = if price > mid_price {
+                (Side::Base, self.size, &mut available_base)
+            } else if price < mid_price {
+                (
+                    Side::Quote,
+                    self.size.mul_floor(price),
+                    &mut available_quote,
+                )
+            } else {
+                continue;
+            };
+
+            if *available < offer {
+                events.push(
+                    GridEvent::SkipLevel {
+                        level,
+                        reason: format!("Insufficient balance to offer {offer}"),
+                    }
+                    .into(),
+                );
+                continue;
+            }
+
+            *available -= offer;
+
+            orders.push(GridOrder {
+                level,
+                side,
+                price,
+                offer,
+                cost_price: None,
+            });
+        }
+
+        Ok((orders, events))
+    }
+
+    fn set_orders_msg(
+        &self,
+        pair: &ConfigResponse,
+        orders: Vec<(GridOrder, Uint128)>,
+    ) -> StdResult<Option<StrategyMsg>> {
+        if orders.is_empty() {
+            return Ok(None);
+        }
+
+        let mut funds = Coins::default();
+
+        for (order, addition) in orders.iter() {
+            funds.add(Coin::new(*addition, pair.denoms.bid(&order.side)))?;
+        }
+
+        Ok(Some(StrategyMsg::with_payload(
+            Contract(self.pair_address.clone()).call(
+                to_json_binary(&ExecuteMsg::Order((
+                    orders
+                        .iter()
+                        .map(|(order, _)| {
+                            (
+                                order.side.clone(),
+                                Price::Fixed(order.price),
+                                Some(order.offer),
+                            )
+                        })
+                        .collect(),
+                    None,
+                )))?,
+                funds.to_vec(),
+            ),
+            StrategyMsgPayload {
+                events: vec![GridEvent::SetOrders {
+                    orders: orders.into_iter().map(|(order, _)| order).collect(),
+                }
+                .into()],
+                ..StrategyMsgPayload::default()
+            },
+        )))
+    }
+
+    fn claim_orders_msg(
+        &self,
+        pair: &ConfigResponse,
+        orders: Vec<(GridOrder, Uint128)>,
+    ) -> StdResult<Option<StrategyMsg>> {
+        if orders.is_empty() {
+            return Ok(None);
+        }
+
+        let mut debited = Coins::default();
+
+        for (order, remaining) in orders.iter() {
+            debited.add(Coin::new(
+                order.offer.saturating_sub(*remaining),
+                pair.denoms.bid(&order.side),
+            ))?;
+        }
+
+        Ok(Some(StrategyMsg::with_payload(
+            Contract(self.pair_address.clone()).call(
+                to_json_binary(&ExecuteMsg::Order((
+                    orders
+                        .iter()
+                        .map(|(order, _)| {
+                            (
+                                order.side.clone(),
+                                Price::Fixed(order.price),
+                                Some(Uint128::zero()),
+                            )
+                        })
+                        .collect(),
+                    None,
+                )))?,
+                vec![],
+            ),
+            StrategyMsgPayload {
+                statistics: Statistics {
+                    debited: debited.to_vec(),
+                    ..Statistics::default()
+                },
+                events: vec![GridEvent::ClaimOrders {
+                    orders: orders.into_iter().map(|(order, _)| order).collect(),
+                }
+                .into()],
+            },
+        )))
+    }
+
+    fn execute_unsafe(
+        self,
+        deps: Deps,
+        env: &Env,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let pair = self.get_pair(deps)?;
+
+        if self.orders.is_empty() {
+            let (orders, events) = self.layout(deps, env, &pair)?;
+
+            let messages = self
+                .set_orders_msg(
+                    &pair,
+                    orders
+                        .iter()
+                        .map(|order| (order.clone(), order.offer))
+                        .collect(),
+                )?
+                .into_iter()
+                .collect();
+
+            return Ok((messages, events, Action::Grid(Grid { orders, ..self })));
+        }
+
+        let mut open = vec![];
+        let mut filled = vec![];
+
+        for order in self.orders.iter() {
+            // Orders that no longer exist are dropped from the grid
+            if let Ok(response) = order.refresh(deps, env, &self.pair_address) {
+                if response.remaining.is_zero() {
+                    filled.push((order.clone(), response.filled));
+                } else {
+                    open.push((order.clone(), response.remaining));
+                }
+            }
+        }
+
+        let mut events = vec![];
+        let mut claims = vec![];
+        let mut settlements: Vec<(GridOrder, Uint128)> = vec![];
+        let mut realised_profit = self.realised_profit;
+
+        for (order, proceeds) in filled {
+            claims.push((order.clone(), Uint128::zero()));
+
+            if let (Side::Base, Some(cost_price)) = (&order.side, order.cost_price) {
+                let profit = proceeds.saturating_sub(order.offer.mul_ceil(cost_price));
+                realised_profit = realised_profit.saturating_add(profit);
+
+                events.push(
+                    GridEvent::RealiseProfit {
+                        profit: Coin::new(profit, pair.denoms.quote()),
+                    }
+                    .into(),
+                );
+            }
+
+            // A filled buy is flipped into a sell one level up,
+            // and a filled sell into a buy one level down.
+            let next = match order.side {
+                Side::Quote => order.level.checked_add(1).filter(|l| *l < self.levels),
+                Side::Base => order.level.checked_sub(1),
+            };
+
+            let next_level = if let Some(next_level) = next {
+                next_level
+            } else {
+                events.push(
+                    GridEvent::SkipLevel {
+                        level: order.level,
+                        reason: "Filled order is at the edge of the grid".to_string(),
+                    }
+                    .into(),
+                );
+                continue;
+            };
+
+            let flipped = GridOrder {
+                level: next_level,
+                side: match order.side {
+                    Side::Base => Side::Quote,
+                    Side::Quote => Side::Base,
+                },
+                price: self.level_price(next_level),
+                offer: proceeds,
+                cost_price: Some(order.price),
+            };
+
+            if let Some(position) = open
+                .iter()
+                .position(|(existing, _)| existing.key() == flipped.key())
+            {
+                // Top up the existing order at this level, claiming
+                // anything it has filled so far in the process. The claim
+                // returns the remaining offer, so it is sent back with the
+                // proceeds to fund the larger order.
+                let (existing, remaining) = open.remove(position);
+                claims.push((existing.clone(), remaining));
+
+                settlements.push((
+                    GridOrder {
+                        offer: remaining + flipped.offer,
+                        ..flipped.clone()
+                    },
+                    remaining + flipped.offer,
+                ));
+            } else if let Some(settlement) = settlements
+                .iter_mut()
+                .find(|(pending, _)| pending.key() == flipped.key())
+            {
+                settlement.0.offer += flipped.offer;
+                settlement.1 += flipped.offer;
+            } else {
+                settlements.push((flipped.clone(), flipped.offer));
+            }
+        }
+
+        let mut messages = vec![];
+
+        // Claims must settle before the proceeds can be re-offered
+        messages.extend(self.claim_orders_msg(&pair, claims)?);
+        messages.extend(self.set_orders_msg(&pair, settlements.clone())?);
+
+        let orders = open
+            .into_iter()
+            .map(|(order, _)| order)
+            .chain(settlements.into_iter().map(|(order, _)| order))
+            .collect::<Vec<_>>();
+
+        Ok((
+            messages,
+            events,
+            Action::Grid(Grid {
+                orders,
+                realised_profit,
+                ..self
+            }),
+        ))
+    }
+
+    fn withdraw_all(
+        self,
+        deps: Deps,
+        env: &Env,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let pair = self.get_pair(deps)?;
+
+        let mut withdrawals = vec![];
+
+        for order in self.orders.iter() {
+            if let Ok(response) = order.refresh(deps, env, &self.pair_address) {
+                withdrawals.push((order.clone(), response.remaining));
+            }
+        }
+
+        let messages = self
+            .claim_orders_msg(&pair, withdrawals)?
+            .into_iter()
+            .collect();
+
+        // We let the commit stage remove the withdrawn orders
+        Ok((messages, vec![], Action::Grid(self)))
+    }
+}
+
+impl StatelessOperation for Grid {
+    fn init(
+        self,
+        _deps: Deps,
+        _env: &Env,
+        _definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        if self.lower_price.is_zero() || self.lower_price >= self.upper_price {
+            return Err(StdError::generic_err(
+                "Lower price must be greater than zero and less than upper price",
+            ));
+        }
+
+        if self.levels < 2 || self.levels > 50 {
+            return Err(StdError::generic_err(
+                "Grid must have between 2 and 50 levels",
+            ));
+        }
+
+        if self.size.lt(&Uint128::new(1_000)) {
+            return Err(StdError::generic_err(
+                "Grid level size cannot be less than 1,000",
+            ));
+        }
+
+        if !self.orders.is_empty() || !self.realised_profit.is_zero() {
+            return Err(StdError::generic_err(
+                "Cannot initialise a grid action with existing orders or profit",
+            ));
+        }
+
+        Ok((vec![], vec![], Action::Grid(self)))
+    }
+
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        _definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        Ok(match self.clone().execute_unsafe(deps, env) {
+            Ok(result) => result,
+            Err(err) => (
+                vec![],
+                vec![GridEvent::SkipGrid {
+                    reason: err.to_string(),
+                }
+                .into()],
+                Action::Grid(self),
+            ),
+        })
+    }
+
+    fn denoms(&self, deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        let pair = self.get_pair(deps)?;
+
+        Ok(HashSet::from([
+            pair.denoms.base().to_string(),
+            pair.denoms.quote().to_string(),
+        ]))
+    }
+
+    fn escrowed(&self, deps: Deps, env: &Env) -> StdResult<HashSet<String>> {
+        self.denoms(deps, env)
+    }
+}
+
+impl StatefulOperation for Grid {
+    fn balances(&self, deps: Deps, env: &Env, denoms: &HashSet<String>) -> StdResult<Coins> {
+        let pair = self.get_pair(deps)?;
+
+        if !denoms.contains(pair.denoms.base()) && !denoms.contains(pair.denoms.quote()) {
+            return Ok(Coins::default());
+        }
+
+        let mut balances = Coins::default();
+
+        for order in self.orders.iter() {
+            let response = order.refresh(deps, env, &self.pair_address)?;
+
+            balances.add(Coin::new(response.remaining, pair.denoms.bid(&order.side)))?;
+            balances.add(Coin::new(response.filled, pair.denoms.ask(&order.side)))?;
+        }
+
+        Ok(balances)
+    }
+
+    fn withdraw(
+        self,
+        deps: Deps,
+        env: &Env,
+        desired: &HashSet<String>,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let pair = self.get_pair(deps)?;
+
+        if !desired.contains(pair.denoms.base()) && !desired.contains(pair.denoms.quote()) {
+            return Ok((vec![], vec![], Action::Grid(self)));
+        }
+
+        self.withdraw_all(deps, env)
+    }
+
+    fn cancel(self, deps: Deps, env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        self.withdraw_all(deps, env)
+    }
+
+    fn commit(self, deps: Deps, env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        // Wipe any cached orders that no longer exist
+        let orders = self
+            .orders
+            .iter()
+            .filter(|order| order.refresh(deps, env, &self.pair_address).is_ok())
+            .cloned()
+            .collect();
+
+        Ok((vec![], vec![], Action::Grid(Grid { orders, ..self })))
+    }
+}
@@ -12,6 +12,7 @@ use crate::{
     actions::{
         action::Action,
         operation::{StatefulOperation, StatelessOperation},
+        reference::Definitions,
     },
     core::Contract,
     statistics::Statistics,
@@ -463,7 +464,12 @@ impl LimitOrder {
 }
 
 impl StatelessOperation for LimitOrder {
-    fn init(self, _deps: Deps, _env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+    fn init(
+        self,
+        _deps: Deps,
+        _env: &Env,
+        _definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
         if let Some(amount) = self.max_bid_amount {
             if amount.lt(&Uint128::new(1_000)) {
                 return Err(StdError::generic_err(
@@ -481,9 +487,14 @@ impl StatelessOperation for LimitOrder {
         Ok((vec![], vec![], Action::LimitOrder(self)))
     }
 
-    fn execute(self, deps: Deps, env: &Env) -> (Vec<StrategyMsg>, Vec<Event>, Action) {
-        match self.clone().execute_unsafe(deps, env) {
-            Ok((action, messages, events)) => (action, messages, events),
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        _definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        Ok(match self.clone().execute_unsafe(deps, env) {
+            Ok(result) => result,
             Err(err) => (
                 vec![],
                 vec![LimitOrderEvent::SkipSettingOrder {
@@ -492,7 +503,7 @@ impl StatelessOperation for LimitOrder {
                 .into()],
                 Action::LimitOrder(self),
             ),
-        }
+        })
     }
 
     fn denoms(&self, deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
@@ -0,0 +1,215 @@
+use std::{cmp::min, collections::HashSet};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Deps, Env, Event, StdError, StdResult};
+
+use crate::{
+    actions::{action::Action, operation::StatelessOperation, reference::Definitions},
+    statistics::Statistics,
+    strategy::{StrategyMsg, StrategyMsgPayload},
+    thorchain::{is_secured_asset, pool_secured_asset, secured_asset_pool, Memo, MsgDeposit},
+};
+
+enum LiquidityEvent {
+    SkipLiquidity { reason: String },
+    AddLiquidity { pool: String, amount: Coin },
+    WithdrawLiquidity { pool: String, basis_points: u64 },
+}
+
+impl From<LiquidityEvent> for Event {
+    fn from(val: LiquidityEvent) -> Self {
+        match val {
+            LiquidityEvent::SkipLiquidity { reason } => {
+                Event::new("skip_liquidity").add_attribute("reason", reason)
+            }
+            LiquidityEvent::AddLiquidity { pool, amount } => Event::new("add_liquidity")
+                .add_attribute("pool", pool)
+                .add_attribute("amount", amount.to_string()),
+            LiquidityEvent::WithdrawLiquidity { pool, basis_points } => {
+                Event::new("withdraw_liquidity")
+                    .add_attribute("pool", pool)
+                    .add_attribute("basis_points", basis_points.to_string())
+            }
+        }
+    }
+}
+
+#[cw_serde]
+pub enum LiquidityOperation {
+    Add { amount: Coin },
+    Withdraw { basis_points: u64 },
+}
+
+#[cw_serde]
+pub struct Liquidity {
+    pub pool: String,
+    pub operation: LiquidityOperation,
+}
+
+impl Liquidity {
+    pub fn memo(&self) -> Memo {
+        match self.operation {
+            LiquidityOperation::Add { .. } => Memo::AddLiquidity {
+                pool: self.pool.clone(),
+                paired_address: None,
+            },
+            LiquidityOperation::Withdraw { basis_points } => Memo::WithdrawLiquidity {
+                pool: self.pool.clone(),
+                basis_points,
+                asset: None,
+            },
+        }
+    }
+
+    fn execute_unsafe(
+        self,
+        deps: Deps,
+        env: &Env,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let signer = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+
+        let message = match self.operation.clone() {
+            LiquidityOperation::Add { amount } => {
+                let balance = deps
+                    .querier
+                    .query_balance(env.contract.address.clone(), amount.denom.clone())?;
+
+                // Adding whatever has accumulated up to the configured amount
+                // lets a scheduled add compound any yield paid out in between.
+                let amount = Coin::new(min(balance.amount, amount.amount), amount.denom);
+
+                if amount.amount.is_zero() {
+                    return Ok((
+                        vec![],
+                        vec![LiquidityEvent::SkipLiquidity {
+                            reason: format!("No {} balance available to add", amount.denom),
+                        }
+                        .into()],
+                        Action::Liquidity(self),
+                    ));
+                }
+
+                StrategyMsg::with_payload(
+                    MsgDeposit {
+                        memo: self.memo().to_string(),
+                        coins: vec![amount.clone()],
+                        signer,
+                    }
+                    .into_cosmos_msg()?,
+                    StrategyMsgPayload {
+                        statistics: Statistics {
+                            deposited: vec![(self.pool.clone(), vec![amount.clone()])],
+                            ..Statistics::default()
+                        },
+                        events: vec![LiquidityEvent::AddLiquidity {
+                            pool: self.pool.clone(),
+                            amount,
+                        }
+                        .into()],
+                    },
+                )
+            }
+            LiquidityOperation::Withdraw { basis_points } => StrategyMsg::with_payload(
+                MsgDeposit {
+                    memo: self.memo().to_string(),
+                    // The memo alone instructs the withdrawal
+                    coins: vec![],
+                    signer,
+                }
+                .into_cosmos_msg()?,
+                // Thorchain pays withdrawals out asynchronously, so the
+                // units and amounts withdrawn are not known to record
+                StrategyMsgPayload {
+                    events: vec![LiquidityEvent::WithdrawLiquidity {
+                        pool: self.pool.clone(),
+                        basis_points,
+                    }
+                    .into()],
+                    ..StrategyMsgPayload::default()
+                },
+            ),
+        };
+
+        Ok((vec![message], vec![], Action::Liquidity(self)))
+    }
+}
+
+impl StatelessOperation for Liquidity {
+    fn init(
+        self,
+        _deps: Deps,
+        _env: &Env,
+        _definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let pool_denom = pool_secured_asset(&self.pool).ok_or_else(|| {
+            StdError::generic_err(format!("Invalid Thorchain pool: {}", self.pool))
+        })?;
+
+        if self.pool != self.pool.to_uppercase() {
+            return Err(StdError::generic_err(format!(
+                "Thorchain pool must be uppercase: {}",
+                self.pool
+            )));
+        }
+
+        if let LiquidityOperation::Add { amount } = &self.operation {
+            if amount.amount.is_zero() {
+                return Err(StdError::generic_err("Liquidity amount cannot be zero"));
+            }
+
+            if !is_secured_asset(&amount.denom) {
+                return Err(StdError::generic_err(
+                    "Liquidity denom must be RUNE or a secured asset",
+                ));
+            }
+
+            if let Some(pool) = secured_asset_pool(&amount.denom) {
+                if pool != self.pool {
+                    return Err(StdError::generic_err(format!(
+                        "Cannot add {} to pool {} (expected {} or rune)",
+                        amount.denom, self.pool, pool_denom
+                    )));
+                }
+            }
+        }
+
+        self.memo().validate()?;
+
+        Ok((vec![], vec![], Action::Liquidity(self)))
+    }
+
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        _definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        Ok(match self.clone().execute_unsafe(deps, env) {
+            Ok(result) => result,
+            Err(err) => (
+                vec![],
+                vec![LiquidityEvent::SkipLiquidity {
+                    reason: err.to_string(),
+                }
+                .into()],
+                Action::Liquidity(self),
+            ),
+        })
+    }
+
+    fn denoms(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        match &self.operation {
+            LiquidityOperation::Add { amount } => Ok(HashSet::from([amount.denom.clone()])),
+            // Withdrawals pay out both sides of the pool
+            LiquidityOperation::Withdraw { .. } => Ok(HashSet::from_iter(
+                ["rune".to_string()]
+                    .into_iter()
+                    .chain(pool_secured_asset(&self.pool)),
+            )),
+        }
+    }
+
+    fn escrowed(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        Ok(HashSet::new())
+    }
+}
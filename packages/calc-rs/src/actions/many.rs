@@ -6,12 +6,18 @@ use crate::{
     actions::{
         action::Action,
         operation::{StatefulOperation, StatelessOperation},
+        reference::Definitions,
     },
     strategy::StrategyMsg,
 };
 
 impl StatelessOperation for Vec<Action> {
-    fn init(self, deps: Deps, env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+    fn init(
+        self,
+        deps: Deps,
+        env: &Env,
+        definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
         if self.is_empty() {
             return Err(StdError::generic_err(
                 "Cannot initialize an empty action list",
@@ -23,7 +29,7 @@ impl StatelessOperation for Vec<Action> {
         let mut events = vec![];
 
         for action in self.into_iter() {
-            let (action_messages, action_events, action) = action.init(deps, env)?;
+            let (action_messages, action_events, action) = action.init(deps, env, definitions)?;
 
             actions.push(action);
             messages.extend(action_messages);
@@ -33,20 +39,25 @@ impl StatelessOperation for Vec<Action> {
         Ok((messages, events, Action::Many(actions)))
     }
 
-    fn execute(self, deps: Deps, env: &Env) -> (Vec<StrategyMsg>, Vec<Event>, Action) {
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
         let mut all_messages = vec![];
         let mut all_events = vec![];
         let mut new_actions = Vec::with_capacity(self.len());
 
         for action in self.into_iter() {
-            let (messages, events, action) = action.execute(deps, env);
+            let (messages, events, action) = action.execute(deps, env, definitions)?;
 
             new_actions.push(action);
             all_messages.extend(messages);
             all_events.extend(events);
         }
 
-        (all_messages, all_events, Action::Many(new_actions))
+        Ok((all_messages, all_events, Action::Many(new_actions)))
     }
 
     fn denoms(&self, deps: Deps, env: &Env) -> StdResult<HashSet<String>> {
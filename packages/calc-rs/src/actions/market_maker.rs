@@ -0,0 +1,397 @@
+use std::{collections::HashSet, vec};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Coins, Decimal, Deps, Env, Event, StdError, StdResult, Uint128};
+use rujira_rs::fin::{ConfigResponse, QueryMsg, Side};
+
+use crate::{
+    actions::{
+        action::Action,
+        limit_order::{LimitOrder, LimitOrderState, OrderPriceStrategy, StaleOrder},
+        operation::{StatefulOperation, StatelessOperation},
+        reference::Definitions,
+    },
+    price::fin_mid_price,
+    strategy::StrategyMsg,
+};
+
+enum MarketMakerEvent {
+    SkipQuote { side: Side, reason: String },
+    SkipMarketMaking { reason: String },
+}
+
+impl From<MarketMakerEvent> for Event {
+    fn from(val: MarketMakerEvent) -> Self {
+        match val {
+            MarketMakerEvent::SkipQuote { side, reason } => Event::new("skip_quote")
+                .add_attribute("side", side.to_string())
+                .add_attribute("reason", reason),
+            MarketMakerEvent::SkipMarketMaking { reason } => {
+                Event::new("skip_market_making").add_attribute("reason", reason)
+            }
+        }
+    }
+}
+
+#[cw_serde]
+pub struct MarketMaker {
+    pub pair_address: Addr,
+    pub spread_bps: u64,
+    pub size: Uint128,
+    pub max_skew_bps: u64,
+    pub tolerance_bps: u64,
+    pub bid: Option<StaleOrder>,
+    pub ask: Option<StaleOrder>,
+}
+
+impl MarketMaker {
+    pub fn get_pair(&self, deps: Deps) -> StdResult<ConfigResponse> {
+        deps.querier
+            .query_wasm_smart::<ConfigResponse>(self.pair_address.clone(), &QueryMsg::Config {})
+    }
+
+    fn current_order(&self, side: &Side) -> Option<StaleOrder> {
+        match side {
+            Side::Base => self.ask.clone(),
+            Side::Quote => self.bid.clone(),
+        }
+    }
+
+    fn order(&self, pair: &ConfigResponse, side: &Side, price: Decimal) -> LimitOrder {
+        LimitOrder {
+            pair_address: self.pair_address.clone(),
+            bid_denom: pair.denoms.bid(side).to_string(),
+            max_bid_amount: Some(match side {
+                Side::Base => self.size,
+                Side::Quote => self.size.mul_floor(price),
+            }),
+            side: side.clone(),
+            strategy: OrderPriceStrategy::Fixed(price),
+            current_order: self.current_order(side),
+        }
+    }
+
+    fn quote_price(&self, mid_price: Decimal, side: &Side) -> Decimal {
+        let half_spread = Decimal::from_ratio(self.spread_bps, 20_000u128);
+
+        let target_price = match side {
+            Side::Base => mid_price.saturating_mul(Decimal::one().saturating_add(half_spread)),
+            Side::Quote => mid_price.saturating_mul(Decimal::one().saturating_sub(half_spread)),
+        };
+
+        // Only re-quote once the target has moved further than the tolerance,
+        // otherwise we churn orders (and lose queue priority) on every execution.
+        match self.current_order(side) {
+            Some(current_order)
+                if current_order.price.abs_diff(target_price)
+                    <= current_order
+                        .price
+                        .saturating_mul(Decimal::bps(self.tolerance_bps)) =>
+            {
+                current_order.price
+            }
+            _ => target_price,
+        }
+    }
+
+    fn base_weight(
+        &self,
+        deps: Deps,
+        env: &Env,
+        pair: &ConfigResponse,
+        mid_price: Decimal,
+    ) -> StdResult<Decimal> {
+        let denoms = HashSet::from([
+            pair.denoms.base().to_string(),
+            pair.denoms.quote().to_string(),
+        ]);
+
+        let mut inventory = self.balances(deps, env, &denoms)?;
+
+        for denom in denoms {
+            inventory.add(deps.querier.query_balance(&env.contract.address, denom)?)?;
+        }
+
+        let base_value = inventory.amount_of(pair.denoms.base()).mul_floor(mid_price);
+
+        let total_value = base_value.checked_add(inventory.amount_of(pair.denoms.quote()))?;
+
+        if total_value.is_zero() {
+            return Ok(Decimal::percent(50));
+        }
+
+        Ok(Decimal::from_ratio(base_value, total_value))
+    }
+
+    fn quote_side(
+        &self,
+        deps: Deps,
+        env: &Env,
+        pair: &ConfigResponse,
+        side: Side,
+        mid_price: Decimal,
+        enabled: bool,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Option<StaleOrder>)> {
+        let config = self.order(pair, &side, self.quote_price(mid_price, &side));
+
+        let mut messages = vec![];
+        let mut events: Vec<Event> = vec![];
+
+        let order = if let Some(existing_order) = config.current_order.clone() {
+            let existing_order_state = LimitOrderState {
+                config: config.clone(),
+                state: existing_order.refresh(deps, env, &config)?,
+            };
+
+            let withdrawn_order_state = if enabled {
+                existing_order_state.saturating_withdraw(deps)?
+            } else {
+                existing_order_state.withdraw(deps)?
+            };
+
+            let (withdraw_messages, withdraw_events, withdrawn_order_state) =
+                withdrawn_order_state.execute();
+
+            messages.extend(withdraw_messages);
+            events.extend(withdraw_events);
+
+            withdrawn_order_state
+        } else {
+            LimitOrderState::new(config)
+        };
+
+        if !enabled {
+            events.push(
+                MarketMakerEvent::SkipQuote {
+                    side: side.clone(),
+                    reason: "Inventory skew limit reached".to_string(),
+                }
+                .into(),
+            );
+
+            // We let the commit stage remove any withdrawn order
+            return Ok((messages, events, self.current_order(&side)));
+        }
+
+        let (set_messages, set_events, set_order_state) = order.set(deps, env)?.execute();
+
+        messages.extend(set_messages);
+        events.extend(set_events);
+
+        Ok((messages, events, Some(set_order_state.state.cached())))
+    }
+
+    fn withdraw_side(
+        &self,
+        deps: Deps,
+        env: &Env,
+        pair: &ConfigResponse,
+        side: Side,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>)> {
+        if let Some(existing_order) = self.current_order(&side) {
+            let config = self.order(pair, &side, existing_order.price);
+
+            let order_state = LimitOrderState {
+                config: config.clone(),
+                state: existing_order.refresh(deps, env, &config)?,
+            };
+
+            let (messages, events, _) = order_state.withdraw(deps)?.execute();
+
+            Ok((messages, events))
+        } else {
+            Ok((vec![], vec![]))
+        }
+    }
+
+    fn withdraw_both_sides(
+        self,
+        deps: Deps,
+        env: &Env,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let pair = self.get_pair(deps)?;
+
+        let (ask_messages, ask_events) = self.withdraw_side(deps, env, &pair, Side::Base)?;
+        let (bid_messages, bid_events) = self.withdraw_side(deps, env, &pair, Side::Quote)?;
+
+        // We let the commit stage remove the current orders
+        Ok((
+            [ask_messages, bid_messages].concat(),
+            [ask_events, bid_events].concat(),
+            Action::MarketMaker(self),
+        ))
+    }
+
+    fn execute_unsafe(
+        self,
+        deps: Deps,
+        env: &Env,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let pair = self.get_pair(deps)?;
+        let mid_price = fin_mid_price(deps, &self.pair_address)?;
+        let base_weight = self.base_weight(deps, env, &pair, mid_price)?;
+
+        let skew = Decimal::bps(self.max_skew_bps);
+        let target_weight = Decimal::percent(50);
+
+        // Stop selling base when we hold too little of it, and stop
+        // buying base when we hold too much of it.
+        let ask_enabled = base_weight >= target_weight.saturating_sub(skew);
+        let bid_enabled = base_weight <= target_weight.saturating_add(skew);
+
+        let (ask_messages, ask_events, ask) =
+            self.quote_side(deps, env, &pair, Side::Base, mid_price, ask_enabled)?;
+
+        let (bid_messages, bid_events, bid) =
+            self.quote_side(deps, env, &pair, Side::Quote, mid_price, bid_enabled)?;
+
+        Ok((
+            [ask_messages, bid_messages].concat(),
+            [ask_events, bid_events].concat(),
+            Action::MarketMaker(MarketMaker { ask, bid, ..self }),
+        ))
+    }
+}
+
+impl StatelessOperation for MarketMaker {
+    fn init(
+        self,
+        _deps: Deps,
+        _env: &Env,
+        _definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        if self.spread_bps == 0 || self.spread_bps >= 10_000 {
+            return Err(StdError::generic_err(
+                "Spread basis points must be between 1 and 9,999",
+            ));
+        }
+
+        if self.size.lt(&Uint128::new(1_000)) {
+            return Err(StdError::generic_err(
+                "Quote size cannot be less than 1,000",
+            ));
+        }
+
+        if self.max_skew_bps > 5_000 {
+            return Err(StdError::generic_err(
+                "Maximum skew basis points cannot exceed 5,000",
+            ));
+        }
+
+        if self.tolerance_bps > 10_000 {
+            return Err(StdError::generic_err(
+                "Re-quote tolerance basis points cannot exceed 10,000",
+            ));
+        }
+
+        if self.bid.is_some() || self.ask.is_some() {
+            return Err(StdError::generic_err(
+                "Cannot initialise a market maker action with current orders already set.",
+            ));
+        }
+
+        Ok((vec![], vec![], Action::MarketMaker(self)))
+    }
+
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        _definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        Ok(match self.clone().execute_unsafe(deps, env) {
+            Ok(result) => result,
+            Err(err) => (
+                vec![],
+                vec![MarketMakerEvent::SkipMarketMaking {
+                    reason: err.to_string(),
+                }
+                .into()],
+                Action::MarketMaker(self),
+            ),
+        })
+    }
+
+    fn denoms(&self, deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        let pair = self.get_pair(deps)?;
+
+        Ok(HashSet::from([
+            pair.denoms.base().to_string(),
+            pair.denoms.quote().to_string(),
+        ]))
+    }
+
+    fn escrowed(&self, deps: Deps, env: &Env) -> StdResult<HashSet<String>> {
+        // Both sides of the pair are offered, so neither can be withdrawn freely
+        self.denoms(deps, env)
+    }
+}
+
+impl StatefulOperation for MarketMaker {
+    fn balances(&self, deps: Deps, env: &Env, denoms: &HashSet<String>) -> StdResult<Coins> {
+        let pair = self.get_pair(deps)?;
+
+        if !denoms.contains(pair.denoms.base()) && !denoms.contains(pair.denoms.quote()) {
+            return Ok(Coins::default());
+        }
+
+        let mut balances = Coins::default();
+
+        for side in [Side::Base, Side::Quote] {
+            if let Some(existing_order) = self.current_order(&side) {
+                let config = self.order(&pair, &side, existing_order.price);
+                let order_state = existing_order.refresh(deps, env, &config)?;
+
+                balances.add(Coin::new(order_state.remaining, pair.denoms.bid(&side)))?;
+                balances.add(Coin::new(order_state.filled, pair.denoms.ask(&side)))?;
+            }
+        }
+
+        Ok(balances)
+    }
+
+    fn withdraw(
+        self,
+        deps: Deps,
+        env: &Env,
+        desired: &HashSet<String>,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let pair = self.get_pair(deps)?;
+
+        if !desired.contains(pair.denoms.base()) && !desired.contains(pair.denoms.quote()) {
+            return Ok((vec![], vec![], Action::MarketMaker(self)));
+        }
+
+        // Inventory is shared between both quotes, so we always unwind them together
+        self.withdraw_both_sides(deps, env)
+    }
+
+    fn cancel(self, deps: Deps, env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        self.withdraw_both_sides(deps, env)
+    }
+
+    fn commit(self, deps: Deps, env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let pair = self.get_pair(deps)?;
+
+        let refreshed = |side: Side| {
+            self.current_order(&side).and_then(|existing_order| {
+                let config = self.order(&pair, &side, existing_order.price);
+                // Wipe the cached order if it does not exist
+                existing_order
+                    .clone()
+                    .refresh(deps, env, &config)
+                    .ok()
+                    .map(|_| existing_order)
+            })
+        };
+
+        let ask = refreshed(Side::Base);
+        let bid = refreshed(Side::Quote);
+
+        Ok((
+            vec![],
+            vec![],
+            Action::MarketMaker(MarketMaker { ask, bid, ..self }),
+        ))
+    }
+}
@@ -1,8 +1,16 @@
 pub mod action;
+pub mod basket;
+pub mod call;
 pub mod conditional;
 pub mod distribution;
+pub mod grid;
 pub mod limit_order;
+pub mod liquidity;
 pub mod many;
+pub mod market_maker;
 pub mod operation;
+pub mod protective_exit;
+pub mod rebalance;
+pub mod reference;
 pub mod schedule;
 pub mod swaps;
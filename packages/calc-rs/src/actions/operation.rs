@@ -2,11 +2,24 @@ use std::collections::HashSet;
 
 use cosmwasm_std::{Coins, Deps, Env, Event, StdResult};
 
-use crate::{actions::action::Action, strategy::StrategyMsg};
+use crate::{
+    actions::{action::Action, reference::Definitions},
+    strategy::StrategyMsg,
+};
 
 pub trait StatelessOperation {
-    fn init(self, deps: Deps, env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)>;
-    fn execute(self, deps: Deps, env: &Env) -> (Vec<StrategyMsg>, Vec<Event>, Action);
+    fn init(
+        self,
+        deps: Deps,
+        env: &Env,
+        definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)>;
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)>;
     fn denoms(&self, deps: Deps, env: &Env) -> StdResult<HashSet<String>>;
     fn escrowed(&self, deps: Deps, env: &Env) -> StdResult<HashSet<String>>;
 }
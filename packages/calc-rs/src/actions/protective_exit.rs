@@ -0,0 +1,351 @@
+use std::{collections::HashSet, mem::discriminant};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Decimal, Deps, Env, Event, StdError, StdResult, Uint128};
+use rujira_rs::fin::{ConfigResponse, QueryMsg};
+
+use crate::{
+    actions::{
+        action::Action,
+        operation::StatelessOperation,
+        reference::Definitions,
+        swaps::swap::{Swap, SwapAmountAdjustment, SwapRoute},
+    },
+    price::PriceSource,
+    strategy::StrategyMsg,
+};
+
+enum ProtectiveExitEvent {
+    SkipExit {
+        reason: String,
+    },
+    StopLoss {
+        price: Decimal,
+        stop_price: Decimal,
+    },
+    TakeProfit {
+        price: Decimal,
+        target_price: Decimal,
+    },
+}
+
+impl From<ProtectiveExitEvent> for Event {
+    fn from(val: ProtectiveExitEvent) -> Self {
+        match val {
+            ProtectiveExitEvent::SkipExit { reason } => {
+                Event::new("skip_protective_exit").add_attribute("reason", reason)
+            }
+            ProtectiveExitEvent::StopLoss { price, stop_price } => Event::new("stop_loss")
+                .add_attribute("price", price.to_string())
+                .add_attribute("stop_price", stop_price.to_string()),
+            ProtectiveExitEvent::TakeProfit {
+                price,
+                target_price,
+            } => Event::new("take_profit")
+                .add_attribute("price", price.to_string())
+                .add_attribute("target_price", target_price.to_string()),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct TakeProfit {
+    pub gain_bps: u64,
+    pub sell_bps: u64,
+    pub executed: bool,
+}
+
+#[cw_serde]
+pub struct ProtectiveExit {
+    pub source: PriceSource,
+    pub sell_denom: String,
+    pub receive_denom: String,
+    pub entry_price: Option<Decimal>,
+    pub stop_loss_bps: Option<u64>,
+    pub trailing: bool,
+    pub take_profits: Vec<TakeProfit>,
+    pub maximum_slippage_bps: u64,
+    pub routes: Vec<SwapRoute>,
+    pub highest_price: Option<Decimal>,
+    pub exited: bool,
+}
+
+impl ProtectiveExit {
+    pub fn with_affiliates(self) -> Self {
+        ProtectiveExit {
+            routes: self
+                .routes
+                .into_iter()
+                .map(SwapRoute::with_affiliates)
+                .collect(),
+            ..self
+        }
+    }
+
+    fn price(&self, deps: Deps) -> StdResult<Decimal> {
+        let price = self.source.price(deps)?;
+
+        if let PriceSource::Fin { pair_address } = &self.source {
+            let pair = deps
+                .querier
+                .query_wasm_smart::<ConfigResponse>(pair_address, &QueryMsg::Config {})?;
+
+            // FIN prices are quoted per unit of the base denom
+            if self.sell_denom == pair.denoms.quote() {
+                return Decimal::one().checked_div(price).map_err(|_| {
+                    StdError::generic_err(format!("Cannot invert zero price from {pair_address}"))
+                });
+            }
+        }
+
+        Ok(price)
+    }
+
+    fn stop_price(&self) -> Option<Decimal> {
+        let reference = if self.trailing {
+            self.highest_price.or(self.entry_price)
+        } else {
+            self.entry_price
+        };
+
+        Some(reference? * Decimal::one().saturating_sub(Decimal::bps(self.stop_loss_bps?)))
+    }
+
+    fn execute_unsafe(
+        self,
+        deps: Deps,
+        env: &Env,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        if self.exited {
+            return Ok((
+                vec![],
+                vec![ProtectiveExitEvent::SkipExit {
+                    reason: "Position has already been exited".to_string(),
+                }
+                .into()],
+                Action::ProtectiveExit(self),
+            ));
+        }
+
+        let entry_price = self
+            .entry_price
+            .ok_or_else(|| StdError::generic_err("No entry price recorded"))?;
+
+        let price = self.price(deps)?;
+
+        let highest_price = Some(self.highest_price.map_or(price, |h| h.max(price)));
+
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.clone(), self.sell_denom.clone())?
+            .amount;
+
+        let exit = ProtectiveExit {
+            highest_price,
+            ..self.clone()
+        };
+
+        let mut events = vec![];
+        let mut take_profits = exit.take_profits.clone();
+
+        let (sell_amount, exited) = match exit.stop_price().filter(|stop| price <= *stop) {
+            Some(stop_price) => {
+                events.push(ProtectiveExitEvent::StopLoss { price, stop_price }.into());
+                (balance, true)
+            }
+            None => {
+                let mut remaining = balance;
+
+                for take_profit in take_profits.iter_mut().filter(|tp| !tp.executed) {
+                    let target_price = entry_price
+                        .checked_add(entry_price * Decimal::bps(take_profit.gain_bps))?;
+
+                    if price >= target_price {
+                        events.push(
+                            ProtectiveExitEvent::TakeProfit {
+                                price,
+                                target_price,
+                            }
+                            .into(),
+                        );
+
+                        take_profit.executed = true;
+                        remaining -= remaining.mul_floor(Decimal::bps(take_profit.sell_bps));
+                    }
+                }
+
+                (balance - remaining, false)
+            }
+        };
+
+        if sell_amount.is_zero() {
+            // Keep tracking the high water mark for the trailing stop
+            return Ok((vec![], events, Action::ProtectiveExit(exit)));
+        }
+
+        let swap = Swap {
+            swap_amount: Coin::new(sell_amount, exit.sell_denom.clone()),
+            minimum_receive_amount: Coin::new(Uint128::zero(), exit.receive_denom.clone()),
+            maximum_slippage_bps: exit.maximum_slippage_bps,
+            adjustment: SwapAmountAdjustment::Fixed,
+            routes: exit.routes.clone(),
+        };
+
+        let route = match swap.best_route(deps, env)? {
+            Some(route) => route,
+            None => {
+                // Leave the exit armed so it is retried on the next execution
+                events.push(
+                    ProtectiveExitEvent::SkipExit {
+                        reason: "No viable swap route found".to_string(),
+                    }
+                    .into(),
+                );
+
+                return Ok((vec![], events, Action::ProtectiveExit(exit)));
+            }
+        };
+
+        let executable = route.execute(deps, env)?;
+
+        let routes = exit
+            .routes
+            .iter()
+            .map(|r| {
+                if discriminant(r) == discriminant(&executable.route) {
+                    executable.route.clone()
+                } else {
+                    r.clone()
+                }
+            })
+            .collect();
+
+        Ok((
+            executable.swap_messages(),
+            events,
+            Action::ProtectiveExit(ProtectiveExit {
+                take_profits,
+                exited,
+                routes,
+                ..exit
+            }),
+        ))
+    }
+}
+
+impl StatelessOperation for ProtectiveExit {
+    fn init(
+        self,
+        deps: Deps,
+        _env: &Env,
+        _definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        if self.sell_denom == self.receive_denom {
+            return Err(StdError::generic_err(
+                "Sell denom and receive denom cannot be the same",
+            ));
+        }
+
+        if self.stop_loss_bps.is_none() && self.take_profits.is_empty() {
+            return Err(StdError::generic_err(
+                "Must provide a stop loss or at least one take profit level",
+            ));
+        }
+
+        if let Some(stop_loss_bps) = self.stop_loss_bps {
+            if stop_loss_bps == 0 || stop_loss_bps >= 10_000 {
+                return Err(StdError::generic_err(
+                    "Stop loss basis points must be between 1 and 9,999",
+                ));
+            }
+        } else if self.trailing {
+            return Err(StdError::generic_err(
+                "Trailing stop requires stop loss basis points",
+            ));
+        }
+
+        for take_profit in self.take_profits.iter() {
+            if take_profit.gain_bps == 0 {
+                return Err(StdError::generic_err(
+                    "Take profit gain basis points cannot be zero",
+                ));
+            }
+
+            if take_profit.sell_bps == 0 || take_profit.sell_bps > 10_000 {
+                return Err(StdError::generic_err(
+                    "Take profit sell basis points must be between 1 and 10,000",
+                ));
+            }
+        }
+
+        if self.maximum_slippage_bps > 10_000 {
+            return Err(StdError::generic_err(
+                "Maximum slippage basis points cannot exceed 10,000",
+            ));
+        }
+
+        if self.routes.is_empty() {
+            return Err(StdError::generic_err("No swap routes provided"));
+        }
+
+        // Without an explicit entry, the position is referenced
+        // against the price at the time the strategy is created.
+        let entry_price = match self.entry_price {
+            Some(entry_price) => entry_price,
+            None => self.price(deps)?,
+        };
+
+        if entry_price.is_zero() {
+            return Err(StdError::generic_err("Entry price cannot be zero"));
+        }
+
+        Ok((
+            vec![],
+            vec![],
+            Action::ProtectiveExit(ProtectiveExit {
+                entry_price: Some(entry_price),
+                highest_price: None,
+                exited: false,
+                take_profits: self
+                    .take_profits
+                    .into_iter()
+                    .map(|take_profit| TakeProfit {
+                        executed: false,
+                        ..take_profit
+                    })
+                    .collect(),
+                ..self
+            }),
+        ))
+    }
+
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        _definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        Ok(match self.clone().execute_unsafe(deps, env) {
+            Ok(result) => result,
+            Err(err) => (
+                vec![],
+                vec![ProtectiveExitEvent::SkipExit {
+                    reason: err.to_string(),
+                }
+                .into()],
+                Action::ProtectiveExit(self),
+            ),
+        })
+    }
+
+    fn denoms(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        Ok(HashSet::from([
+            self.sell_denom.clone(),
+            self.receive_denom.clone(),
+        ]))
+    }
+
+    fn escrowed(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        Ok(HashSet::from([self.receive_denom.clone()]))
+    }
+}
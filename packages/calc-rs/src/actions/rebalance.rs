@@ -0,0 +1,389 @@
+use std::{cmp::min, collections::HashSet};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Decimal, Deps, Env, Event, StdError, StdResult, Uint128};
+
+use crate::{
+    actions::{
+        action::Action,
+        operation::StatelessOperation,
+        reference::Definitions,
+        swaps::swap::{New, Quotable, Swap, SwapAmountAdjustment, SwapQuote, SwapRoute, Validated},
+    },
+    strategy::StrategyMsg,
+};
+
+enum RebalanceEvent {
+    SkipRebalance {
+        reason: String,
+    },
+    SkipRebalanceSwap {
+        swap_amount: Coin,
+        target_denom: String,
+        reason: String,
+    },
+}
+
+impl From<RebalanceEvent> for Event {
+    fn from(val: RebalanceEvent) -> Self {
+        match val {
+            RebalanceEvent::SkipRebalance { reason } => {
+                Event::new("skip_rebalance").add_attribute("reason", reason)
+            }
+            RebalanceEvent::SkipRebalanceSwap {
+                swap_amount,
+                target_denom,
+                reason,
+            } => Event::new("skip_rebalance_swap")
+                .add_attribute("swap_amount", swap_amount.to_string())
+                .add_attribute("target_denom", target_denom)
+                .add_attribute("reason", reason),
+        }
+    }
+}
+
+struct Holding {
+    denom: String,
+    balance: Uint128,
+    value: Uint128,
+    target: Decimal,
+}
+
+#[cw_serde]
+pub struct Rebalance {
+    pub targets: Vec<(String, Decimal)>,
+    pub valuation_denom: String,
+    pub drift_tolerance_bps: u64,
+    pub maximum_slippage_bps: u64,
+    pub routes: Vec<SwapRoute>,
+}
+
+impl Rebalance {
+    pub fn with_affiliates(self) -> Self {
+        Rebalance {
+            routes: self
+                .routes
+                .into_iter()
+                .map(SwapRoute::with_affiliates)
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Quotes each route that supports swapping between these denoms,
+    /// returning the best quote along with the index of its route.
+    fn quote(
+        &self,
+        deps: Deps,
+        env: &Env,
+        swap_amount: Coin,
+        target_denom: &str,
+        maximum_slippage_bps: u64,
+    ) -> StdResult<Option<(usize, SwapQuote<Validated>)>> {
+        let minimum_receive_amount = Coin::new(0u128, target_denom);
+
+        let mut best: Option<(usize, SwapQuote<Validated>)> = None;
+
+        for (index, route) in self.routes.iter().enumerate() {
+            let supported = route
+                .verify(
+                    deps,
+                    &SwapQuote {
+                        swap_amount: swap_amount.clone(),
+                        minimum_receive_amount: minimum_receive_amount.clone(),
+                        maximum_slippage_bps,
+                        adjustment: SwapAmountAdjustment::Fixed,
+                        route: route.clone(),
+                        state: New,
+                    },
+                )
+                .is_ok();
+
+            if !supported {
+                continue;
+            }
+
+            let quote = Swap {
+                swap_amount: swap_amount.clone(),
+                minimum_receive_amount: minimum_receive_amount.clone(),
+                maximum_slippage_bps,
+                adjustment: SwapAmountAdjustment::Fixed,
+                routes: vec![route.clone()],
+            }
+            .best_route(deps, env)?;
+
+            if let Some(quote) = quote {
+                let is_better = best.as_ref().map_or(true, |(_, best)| {
+                    quote.state.expected_amount_out.amount > best.state.expected_amount_out.amount
+                });
+
+                if is_better {
+                    best = Some((index, quote));
+                }
+            }
+        }
+
+        Ok(best)
+    }
+
+    fn holdings(&self, deps: Deps, env: &Env) -> StdResult<Vec<Holding>> {
+        let mut targets = self.targets.clone();
+
+        if !targets
+            .iter()
+            .any(|(denom, _)| denom == &self.valuation_denom)
+        {
+            targets.push((self.valuation_denom.clone(), Decimal::zero()));
+        }
+
+        let mut holdings = vec![];
+
+        for (denom, target) in targets {
+            let balance = deps
+                .querier
+                .query_balance(env.contract.address.clone(), denom.clone())?;
+
+            let value = if balance.amount.is_zero() || balance.denom == self.valuation_denom {
+                balance.amount
+            } else {
+                // Value the full balance as if it were sold, regardless of slippage
+                self.quote(deps, env, balance.clone(), &self.valuation_denom, 10_000)?
+                    .map(|quote| quote.state.expected_amount_out.amount)
+                    .ok_or_else(|| {
+                        StdError::generic_err(format!(
+                            "No viable route found to value {} in {}",
+                            denom, self.valuation_denom
+                        ))
+                    })?
+            };
+
+            holdings.push(Holding {
+                denom,
+                balance: balance.amount,
+                value,
+                target,
+            });
+        }
+
+        Ok(holdings)
+    }
+
+    fn execute_unsafe(
+        self,
+        deps: Deps,
+        env: &Env,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let holdings = self.holdings(deps, env)?;
+        let total_value = holdings
+            .iter()
+            .fold(Uint128::zero(), |total, holding| total + holding.value);
+
+        if total_value.is_zero() {
+            return Ok((
+                vec![],
+                vec![RebalanceEvent::SkipRebalance {
+                    reason: "Strategy holds nothing to rebalance".to_string(),
+                }
+                .into()],
+                Action::Rebalance(self),
+            ));
+        }
+
+        let tolerance = Decimal::bps(self.drift_tolerance_bps);
+
+        let drifted = holdings.iter().any(|holding| {
+            Decimal::from_ratio(holding.value, total_value).abs_diff(holding.target) > tolerance
+        });
+
+        if !drifted {
+            return Ok((
+                vec![],
+                vec![RebalanceEvent::SkipRebalance {
+                    reason: "All weights are within drift tolerance".to_string(),
+                }
+                .into()],
+                Action::Rebalance(self),
+            ));
+        }
+
+        let mut surpluses = vec![];
+        let mut deficits = vec![];
+
+        for holding in holdings {
+            let target_value = total_value.mul_floor(holding.target);
+
+            if holding.value > target_value {
+                let surplus = holding.value - target_value;
+                surpluses.push((holding, surplus));
+            } else if target_value > holding.value {
+                deficits.push((holding.denom, target_value - holding.value));
+            }
+        }
+
+        let mut messages = vec![];
+        let mut events = vec![];
+        let mut routes = self.routes.clone();
+
+        // Swap each overweight asset directly into the underweight ones so
+        // that every swap can be funded from balances held right now.
+        for (holding, mut surplus) in surpluses {
+            for (target_denom, deficit) in deficits.iter_mut() {
+                if surplus.is_zero() {
+                    break;
+                }
+
+                let value = min(surplus, *deficit);
+
+                surplus -= value;
+                *deficit -= value;
+
+                let swap_amount = Coin::new(
+                    holding.balance.multiply_ratio(value, holding.value),
+                    holding.denom.clone(),
+                );
+
+                if swap_amount.amount.is_zero() {
+                    continue;
+                }
+
+                let result = self
+                    .quote(
+                        deps,
+                        env,
+                        swap_amount.clone(),
+                        target_denom,
+                        self.maximum_slippage_bps,
+                    )
+                    .and_then(|quote| {
+                        quote.ok_or_else(|| {
+                            StdError::generic_err("No viable swap route found".to_string())
+                        })
+                    })
+                    .and_then(|(index, quote)| {
+                        quote.execute(deps, env).map(|quote| (index, quote))
+                    });
+
+                match result {
+                    Ok((index, quote)) => {
+                        // Cache any route state against the route that was
+                        // used, as several routes may share the same venue
+                        routes[index] = quote.route.clone();
+
+                        messages.extend(quote.swap_messages());
+                    }
+                    Err(err) => events.push(
+                        RebalanceEvent::SkipRebalanceSwap {
+                            swap_amount,
+                            target_denom: target_denom.clone(),
+                            reason: err.to_string(),
+                        }
+                        .into(),
+                    ),
+                }
+            }
+        }
+
+        Ok((
+            messages,
+            events,
+            Action::Rebalance(Rebalance { routes, ..self }),
+        ))
+    }
+}
+
+impl StatelessOperation for Rebalance {
+    fn init(
+        self,
+        _deps: Deps,
+        _env: &Env,
+        _definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        if self.targets.len() < 2 {
+            return Err(StdError::generic_err(
+                "Rebalance must have at least 2 target denoms",
+            ));
+        }
+
+        let mut denoms = HashSet::new();
+
+        for (denom, weight) in self.targets.iter() {
+            if !denoms.insert(denom) {
+                return Err(StdError::generic_err(format!(
+                    "Duplicate target denom: {denom}"
+                )));
+            }
+
+            if weight.is_zero() {
+                return Err(StdError::generic_err(format!(
+                    "Target weight for {denom} cannot be zero"
+                )));
+            }
+        }
+
+        if self
+            .targets
+            .iter()
+            .fold(Decimal::zero(), |total, (_, weight)| total + weight)
+            != Decimal::one()
+        {
+            return Err(StdError::generic_err("Target weights must sum to 1"));
+        }
+
+        if self.drift_tolerance_bps > 10_000 {
+            return Err(StdError::generic_err(
+                "Drift tolerance basis points cannot exceed 10,000",
+            ));
+        }
+
+        if self.maximum_slippage_bps > 10_000 {
+            return Err(StdError::generic_err(
+                "Maximum slippage basis points cannot exceed 10,000",
+            ));
+        }
+
+        if self.routes.is_empty() {
+            return Err(StdError::generic_err("No swap routes provided"));
+        }
+
+        Ok((vec![], vec![], Action::Rebalance(self)))
+    }
+
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        _definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        Ok(match self.clone().execute_unsafe(deps, env) {
+            Ok(result) => result,
+            Err(err) => (
+                vec![],
+                vec![RebalanceEvent::SkipRebalance {
+                    reason: err.to_string(),
+                }
+                .into()],
+                Action::Rebalance(self),
+            ),
+        })
+    }
+
+    fn denoms(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        let mut denoms = self
+            .targets
+            .iter()
+            .map(|(denom, _)| denom.clone())
+            .collect::<HashSet<_>>();
+
+        denoms.insert(self.valuation_denom.clone());
+
+        Ok(denoms)
+    }
+
+    fn escrowed(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        Ok(self
+            .targets
+            .iter()
+            .map(|(denom, _)| denom.clone())
+            .collect())
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Deps, Env, Event, StdError, StdResult};
+
+use crate::{
+    actions::{action::Action, operation::StatelessOperation},
+    strategy::StrategyMsg,
+};
+
+pub type Definitions = Vec<(String, Action)>;
+
+enum ReferenceEvent {
+    SkipReference { name: String, reason: String },
+}
+
+impl From<ReferenceEvent> for Event {
+    fn from(val: ReferenceEvent) -> Self {
+        match val {
+            ReferenceEvent::SkipReference { name, reason } => Event::new("skip_reference")
+                .add_attribute("name", name)
+                .add_attribute("reason", reason),
+        }
+    }
+}
+
+/// A reference to a named action definition of the strategy.
+#[cw_serde]
+pub struct Ref(pub String);
+
+fn load_definition(definitions: &Definitions, name: &str) -> StdResult<Action> {
+    definitions
+        .iter()
+        .find(|(definition_name, _)| definition_name == name)
+        .map(|(_, action)| action.clone())
+        .ok_or_else(|| StdError::generic_err(format!("Unknown action definition: {name}")))
+}
+
+fn save_definition(definitions: &mut Definitions, name: &str, action: Action) {
+    if let Some((_, definition)) = definitions
+        .iter_mut()
+        .find(|(definition_name, _)| definition_name == name)
+    {
+        *definition = action;
+    }
+}
+
+/// Initialises each definition in order. A definition may only reference
+/// definitions declared before it, which rules out reference cycles.
+pub fn init_definitions(
+    definitions: Definitions,
+    deps: Deps,
+    env: &Env,
+) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Definitions)> {
+    let mut messages = vec![];
+    let mut events = vec![];
+    let mut initialised: Definitions = vec![];
+
+    for (name, action) in definitions {
+        if name.is_empty() {
+            return Err(StdError::generic_err(
+                "Action definition name cannot be empty",
+            ));
+        }
+
+        if initialised.iter().any(|(existing, _)| *existing == name) {
+            return Err(StdError::generic_err(format!(
+                "Duplicate action definition: {name}"
+            )));
+        }
+
+        let (action_messages, action_events, action) = action.init(deps, env, &initialised)?;
+
+        messages.extend(action_messages);
+        events.extend(action_events);
+
+        initialised.push((name, action));
+    }
+
+    Ok((messages, events, initialised))
+}
+
+impl StatelessOperation for Ref {
+    fn init(
+        self,
+        _deps: Deps,
+        _env: &Env,
+        definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        load_definition(definitions, &self.0)?;
+        Ok((vec![], vec![], Action::Ref(self)))
+    }
+
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        let definition = match load_definition(definitions, &self.0) {
+            Ok(definition) => definition,
+            Err(err) => {
+                return Ok((
+                    vec![],
+                    vec![ReferenceEvent::SkipReference {
+                        name: self.0.clone(),
+                        reason: err.to_string(),
+                    }
+                    .into()],
+                    Action::Ref(self),
+                ))
+            }
+        };
+
+        // Every reference to a definition shares its state, so the
+        // next reference executed sees the result of this one.
+        let (messages, events, action) = definition.execute(deps, env, definitions)?;
+        save_definition(definitions, &self.0, action);
+
+        Ok((messages, events, Action::Ref(self)))
+    }
+
+    // Denoms, balances and stateful operations are handled once per
+    // definition at the strategy level, however often it is referenced.
+    fn denoms(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        Ok(HashSet::new())
+    }
+
+    fn escrowed(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
+        Ok(HashSet::new())
+    }
+}
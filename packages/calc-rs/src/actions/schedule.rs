@@ -1,16 +1,19 @@
 use std::{cmp::min, collections::HashSet, str::FromStr};
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, Coins, Deps, Env, Event, StdResult};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Coin, Coins, Deps, Env, Event, StdError, StdResult,
+};
 use cron::Schedule as CronSchedule;
 
 use crate::{
     actions::{
         action::Action,
         operation::{StatefulOperation, StatelessOperation},
+        reference::Definitions,
     },
-    cadence::Cadence,
-    conditions::Condition,
+    cadence::{Cadence, Moment},
+    conditions::{Condition, ErrorPolicy},
     core::Contract,
     manager::ManagerExecuteMsg,
     scheduler::{CreateTriggerMsg, SchedulerExecuteMsg},
@@ -20,6 +23,7 @@ use crate::{
 enum ScheduleEvent {
     ExecutionSkipped { reason: String },
     CreateTrigger { condition: Condition },
+    Completed { runs: u64 },
 }
 
 impl From<ScheduleEvent> for Event {
@@ -31,6 +35,9 @@ impl From<ScheduleEvent> for Event {
             ScheduleEvent::CreateTrigger { condition } => {
                 Event::new("create_trigger").add_attribute("condition", format!("{condition:?}"))
             }
+            ScheduleEvent::Completed { runs } => {
+                Event::new("schedule_completed").add_attribute("runs", runs.to_string())
+            }
         }
     }
 }
@@ -43,43 +50,115 @@ pub struct Schedule {
     pub cadence: Cadence,
     pub execution_rebate: Vec<Coin>,
     pub action: Box<Action>,
+    #[serde(default)]
+    pub until: Option<Moment>,
+    #[serde(default)]
+    pub max_runs: Option<u64>,
+    #[serde(default)]
+    pub runs: u64,
+    /// Whether completion has been reported, so it is only reported once.
+    #[serde(default)]
+    pub completed: bool,
 }
 
 impl Schedule {
-    pub fn execute_unsafe(
+    /// Whether the schedule has passed its end bound or run as many
+    /// times as it may.
+    fn is_complete(&self, env: &Env) -> bool {
+        let max_runs = match self.cadence {
+            Cadence::Once { .. } => Some(1),
+            _ => self.max_runs,
+        };
+
+        max_runs.map_or(false, |max_runs| self.runs >= max_runs)
+            || self
+                .until
+                .as_ref()
+                .map_or(false, |until| until.has_passed(env))
+    }
+
+    /// Whether a trigger on the condition could only fire after the end bound.
+    fn outlasts(&self, condition: &Condition) -> bool {
+        match (&self.until, condition) {
+            // Both fire only once the chain has moved past their moment
+            (Some(Moment::Timestamp(until)), Condition::TimestampElapsed(time)) => time >= until,
+            (Some(Moment::Height(until)), Condition::BlocksCompleted(height)) => height >= until,
+            _ => false,
+        }
+    }
+
+    /// Arms the trigger for the next run, advancing the cadence past
+    /// this one if the action ran.
+    fn reschedule(
         self,
         deps: Deps,
         env: &Env,
+        ran: bool,
+        mut messages: Vec<StrategyMsg>,
+        mut events: Vec<Event>,
     ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
-        let mut rebate = Coins::default();
+        let (condition, mut schedule) = if ran {
+            let cadence = self.cadence.clone().next(deps, env)?;
 
-        for amount in self.execution_rebate.iter() {
-            let balance = deps
-                .querier
-                .query_balance(env.contract.address.clone(), amount.denom.clone())?;
+            // Wait out the cooldown from this run rather than
+            // re-arming on a condition that may still hold
+            let condition = match cadence {
+                Cadence::Condition { .. } => cadence.into_condition(deps, env, &self.scheduler)?,
+                _ => self.cadence.into_condition(deps, env, &self.scheduler)?,
+            };
 
-            rebate.add(Coin {
-                denom: amount.denom.clone(),
-                amount: min(amount.amount, balance.amount),
-            })?;
-        }
+            (
+                condition,
+                Schedule {
+                    cadence,
+                    runs: self.runs + 1,
+                    ..self
+                },
+            )
+        } else {
+            let condition = self.cadence.into_condition(deps, env, &self.scheduler)?;
 
-        if self.cadence.is_due(deps, env, &self.scheduler)? {
-            let (mut messages, mut events, action) = self.action.execute(deps, env);
+            events.push(
+                ScheduleEvent::ExecutionSkipped {
+                    reason: format!("Schedule not due: {:?}", self.cadence.clone()),
+                }
+                .into(),
+            );
 
-            let condition = self.cadence.into_condition(deps, env, &self.scheduler)?;
+            (condition, self)
+        };
+
+        // No trigger is created once the schedule has run its course,
+        // so it stops being executed by the scheduler
+        let condition = condition
+            .filter(|condition| !schedule.is_complete(env) && !schedule.outlasts(condition));
+
+        if let Some(condition) = condition {
+            let mut rebate = Coins::default();
+
+            for amount in schedule.execution_rebate.iter() {
+                let balance = deps
+                    .querier
+                    .query_balance(env.contract.address.clone(), amount.denom.clone())?;
+
+                rebate.add(Coin {
+                    denom: amount.denom.clone(),
+                    amount: min(amount.amount, balance.amount),
+                })?;
+            }
 
-            let create_trigger_msg = Contract(self.scheduler.clone()).call(
+            let create_trigger_msg = Contract(schedule.scheduler.clone()).call(
                 to_json_binary(&SchedulerExecuteMsg::Create(CreateTriggerMsg {
                     condition: condition.clone(),
-                    msg: self.msg.clone().unwrap_or(to_json_binary(
+                    msg: schedule.msg.clone().unwrap_or(to_json_binary(
                         &ManagerExecuteMsg::ExecuteStrategy {
                             contract_address: env.contract.address.clone(),
                         },
                     )?),
-                    contract_address: self.contract_address.clone(),
+                    contract_address: schedule.contract_address.clone(),
                     executors: vec![],
-                    jitter: None,
+                    jitter: schedule.cadence.jitter(),
+                    on_error: schedule.cadence.on_error(),
                 }))?,
                 rebate.to_vec(),
             );
@@ -96,79 +175,130 @@ impl Schedule {
             ));
 
             events.push(ScheduleEvent::CreateTrigger { condition }.into());
-
-            Ok((
-                messages,
-                events,
-                Action::Schedule(Schedule {
-                    cadence: self.cadence.next(deps, env)?,
-                    action: Box::new(action),
-                    ..self
-                }),
-            ))
-        } else {
-            let condition = self.cadence.into_condition(deps, env, &self.scheduler)?;
-
-            let create_trigger_msg = Contract(self.scheduler.clone()).call(
-                to_json_binary(&SchedulerExecuteMsg::Create(CreateTriggerMsg {
-                    condition: condition.clone(),
-                    msg: self.msg.clone().unwrap_or(to_json_binary(
-                        &ManagerExecuteMsg::ExecuteStrategy {
-                            contract_address: env.contract.address.clone(),
-                        },
-                    )?),
-                    contract_address: self.contract_address.clone(),
-                    executors: vec![],
-                    jitter: None,
-                }))?,
-                rebate.to_vec(),
+        } else if !schedule.completed {
+            events.push(
+                ScheduleEvent::Completed {
+                    runs: schedule.runs,
+                }
+                .into(),
             );
 
-            let skipped_event = ScheduleEvent::ExecutionSkipped {
-                reason: format!("Schedule not due: {:?}", self.cadence.clone()),
-            };
-
-            let trigger_created_event = ScheduleEvent::CreateTrigger {
-                condition: condition.clone(),
-            };
-
-            Ok((
-                vec![StrategyMsg::with_payload(
-                    create_trigger_msg,
-                    StrategyMsgPayload {
-                        events: vec![ScheduleEvent::CreateTrigger { condition }.into()],
-                        ..StrategyMsgPayload::default()
-                    },
-                )],
-                vec![skipped_event.into(), trigger_created_event.into()],
-                Action::Schedule(self),
-            ))
+            schedule.completed = true;
         }
+
+        Ok((messages, events, Action::Schedule(schedule)))
     }
 }
 
 impl StatelessOperation for Schedule {
-    fn init(self, _deps: Deps, _env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+    fn init(
+        self,
+        deps: Deps,
+        env: &Env,
+        _definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
         if let Cadence::Cron { expr, .. } = self.cadence.clone() {
             CronSchedule::from_str(&expr).map_err(|e| {
                 cosmwasm_std::StdError::generic_err(format!("Invalid cron string: {e}"))
             })?;
         }
 
-        Ok((vec![], vec![], Action::Schedule(self)))
+        if let Cadence::Condition { condition, .. } = &self.cadence {
+            condition.validate()?;
+
+            // We don't care if it's satisfied at init time,
+            // only that the condition itself can be evaluated.
+            condition.is_satisfied(deps, env)?;
+        }
+
+        if self.max_runs == Some(0) {
+            return Err(StdError::generic_err(
+                "Schedule max runs must be greater than zero",
+            ));
+        }
+
+        // A new schedule starts with none of its runs used
+        Ok((
+            vec![],
+            vec![],
+            Action::Schedule(Schedule {
+                runs: 0,
+                completed: false,
+                ..self
+            }),
+        ))
     }
 
-    fn execute(self, deps: Deps, env: &Env) -> (Vec<StrategyMsg>, Vec<Event>, Action) {
-        match self.clone().execute_unsafe(deps, env) {
-            Ok((messages, events, action)) => (messages, events, action),
-            Err(err) => (
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        if self.is_complete(env) {
+            // Only the first execution to find the schedule complete reports it
+            let events = if self.completed {
+                vec![]
+            } else {
+                vec![ScheduleEvent::Completed { runs: self.runs }.into()]
+            };
+
+            return Ok((
+                vec![],
+                events,
+                Action::Schedule(Schedule {
+                    completed: true,
+                    ..self
+                }),
+            ));
+        }
+
+        let skip = |schedule: Schedule, err: StdError| {
+            (
                 vec![],
                 vec![ScheduleEvent::ExecutionSkipped {
                     reason: err.to_string(),
                 }
                 .into()],
-                Action::Schedule(self),
-            ),
+                Action::Schedule(schedule),
+            )
+        };
+
+        let due = match self.cadence.is_due(deps, env, &self.scheduler) {
+            Ok(due) => due,
+            Err(err) if self.cadence.on_error() == ErrorPolicy::Fail => {
+                return Err(StdError::generic_err(format!(
+                    "Failed to evaluate conditions: {err}"
+                )))
+            }
+            Err(err) => return Ok(skip(self, err)),
+        };
+
+        // Definitions executed by a failed run must not keep its state
+        let scope = definitions.clone();
+
+        let (messages, events, schedule) = if due {
+            // Errors from the action itself are not the schedule's to skip
+            let (messages, events, action) = self.action.clone().execute(deps, env, definitions)?;
+
+            (
+                messages,
+                events,
+                Schedule {
+                    action: Box::new(action),
+                    ..self.clone()
+                },
+            )
+        } else {
+            (vec![], vec![], self.clone())
+        };
+
+        match schedule.reschedule(deps, env, due, messages, events) {
+            Ok(result) => Ok(result),
+            Err(err) => {
+                *definitions = scope;
+                Ok(skip(self, err))
+            }
         }
     }
 
@@ -7,6 +7,7 @@ use crate::{
     actions::{
         action::Action,
         operation::StatelessOperation,
+        reference::Definitions,
         swaps::{fin::FinRoute, thor::ThorchainRoute},
     },
     strategy::StrategyMsg,
@@ -46,6 +47,20 @@ pub enum SwapRoute {
     Thorchain(ThorchainRoute),
 }
 
+impl SwapRoute {
+    pub fn with_affiliates(self) -> Self {
+        match self {
+            SwapRoute::Thorchain(thor_route) => SwapRoute::Thorchain(ThorchainRoute {
+                // As per agreement with Rujira
+                affiliate_code: Some("rj".to_string()),
+                affiliate_bps: Some(10),
+                ..thor_route
+            }),
+            _ => self,
+        }
+    }
+}
+
 #[cw_serde]
 pub struct New;
 
@@ -178,15 +193,7 @@ impl Swap {
             routes: self
                 .routes
                 .into_iter()
-                .map(|route| match route {
-                    SwapRoute::Thorchain(thor_route) => SwapRoute::Thorchain(ThorchainRoute {
-                        // As per agreement with Rujira
-                        affiliate_code: Some("rj".to_string()),
-                        affiliate_bps: Some(10),
-                        ..thor_route
-                    }),
-                    _ => route,
-                })
+                .map(SwapRoute::with_affiliates)
                 .collect(),
             ..self
         }
@@ -272,7 +279,12 @@ impl Swap {
 }
 
 impl StatelessOperation for Swap {
-    fn init(self, deps: Deps, _env: &Env) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+    fn init(
+        self,
+        deps: Deps,
+        _env: &Env,
+        _definitions: &Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
         if self.swap_amount.amount.is_zero() {
             return Err(StdError::generic_err("Swap amount cannot be zero"));
         }
@@ -304,9 +316,14 @@ impl StatelessOperation for Swap {
         Ok((vec![], vec![], Action::Swap(self)))
     }
 
-    fn execute(self, deps: Deps, env: &Env) -> (Vec<StrategyMsg>, Vec<Event>, Action) {
-        match self.clone().execute_unsafe(deps, env) {
-            Ok((action, messages, events)) => (action, messages, events),
+    fn execute(
+        self,
+        deps: Deps,
+        env: &Env,
+        _definitions: &mut Definitions,
+    ) -> StdResult<(Vec<StrategyMsg>, Vec<Event>, Action)> {
+        Ok(match self.clone().execute_unsafe(deps, env) {
+            Ok(result) => result,
             Err(err) => (
                 vec![],
                 vec![SwapEvent::SkipSwap {
@@ -315,7 +332,7 @@ impl StatelessOperation for Swap {
                 .into()],
                 Action::Swap(self),
             ),
-        }
+        })
     }
 
     fn denoms(&self, _deps: Deps, _env: &Env) -> StdResult<HashSet<String>> {
@@ -1,5 +1,7 @@
 use std::{
     cmp::{max, min},
+    str::FromStr,
+    time::Duration,
     vec,
 };
 
@@ -10,7 +12,10 @@ use crate::{
     },
     statistics::Statistics,
     strategy::{StrategyMsg, StrategyMsgPayload},
-    thorchain::{MsgDeposit, SwapQuote as ThorchainSwapQuote, SwapQuoteRequest},
+    thorchain::{
+        is_secured_asset, trading_unavailable_reason, Memo, MsgDeposit,
+        SwapQuote as ThorchainSwapQuote, SwapQuoteRequest,
+    },
 };
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Coin, Decimal, Deps, Env, Event, StdError, StdResult, Uint128};
@@ -50,6 +55,11 @@ pub struct StreamingSwap {
     memo: String,
 }
 
+#[cw_serde]
+pub struct TradingCheck {
+    pub max_outbound_delay: Option<Duration>,
+}
+
 #[cw_serde]
 pub struct ThorchainRoute {
     pub streaming_interval: Option<u64>,
@@ -57,10 +67,10 @@ pub struct ThorchainRoute {
     pub affiliate_code: Option<String>,
     pub affiliate_bps: Option<u64>,
     pub latest_swap: Option<StreamingSwap>,
-}
-
-fn is_secured_asset(denom: &str) -> bool {
-    denom.to_lowercase() == "rune" || denom.contains("-")
+    /// Skips the swap when either asset cannot currently be traded,
+    /// rather than depositing funds that Thorchain would refund.
+    #[serde(default)]
+    pub trading_check: Option<TradingCheck>,
 }
 
 impl Quotable for ThorchainRoute {
@@ -114,6 +124,10 @@ impl Quotable for ThorchainRoute {
             }
         }
 
+        if let Some(latest_swap) = &self.latest_swap {
+            parse_swap_memo(&latest_swap.memo)?;
+        }
+
         Ok(())
     }
 
@@ -244,8 +258,41 @@ impl Quotable for ThorchainRoute {
             return Err(StdError::generic_err("Swap amount cannot be zero"));
         }
 
+        if self.trading_check.is_some() {
+            for denom in [
+                &route.swap_amount.denom,
+                &route.minimum_receive_amount.denom,
+            ] {
+                let reason = trading_unavailable_reason(deps.querier, env.block.height, denom)
+                    .map_err(|e| {
+                        StdError::generic_err(format!(
+                            "Failed to check trading availability for {denom}: {e}"
+                        ))
+                    })?;
+
+                if let Some(reason) = reason {
+                    return Err(StdError::generic_err(reason));
+                }
+            }
+        }
+
         let adjusted_quote = get_swap_quote(deps, route)?;
 
+        parse_swap_memo(&adjusted_quote.memo)?;
+
+        if let Some(TradingCheck {
+            max_outbound_delay: Some(max_outbound_delay),
+        }) = &self.trading_check
+        {
+            if adjusted_quote.outbound_delay_seconds > max_outbound_delay.as_secs() {
+                return Err(StdError::generic_err(format!(
+                    "Outbound delay ({}s) exceeds maximum allowed ({}s)",
+                    adjusted_quote.outbound_delay_seconds,
+                    max_outbound_delay.as_secs()
+                )));
+            }
+        }
+
         if let Some(fees) = adjusted_quote.fees {
             if fees.slippage_bps > route.maximum_slippage_bps {
                 return Err(StdError::generic_err(format!(
@@ -380,6 +427,17 @@ impl<S> TryFrom<&SwapQuote<S>> for SwapQuoteRequest {
     }
 }
 
+fn parse_swap_memo(memo: &str) -> StdResult<Memo> {
+    match Memo::from_str(memo)
+        .map_err(|e| StdError::generic_err(format!("Invalid swap memo {memo}: {e}")))?
+    {
+        swap @ Memo::Swap { .. } => Ok(swap),
+        _ => Err(StdError::generic_err(format!(
+            "Expected a swap memo but got {memo}"
+        ))),
+    }
+}
+
 pub fn get_swap_quote<S>(deps: Deps, quote: &SwapQuote<S>) -> StdResult<ThorchainSwapQuote> {
     ThorchainSwapQuote::get(deps.querier, &SwapQuoteRequest::try_from(quote)?).map_err(|e| {
         StdError::generic_err(format!(
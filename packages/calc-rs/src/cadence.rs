@@ -6,21 +6,64 @@ use cosmwasm_std::{Addr, Decimal, Deps, Env, StdError, StdResult, Timestamp};
 use cron::Schedule as CronSchedule;
 use rujira_rs::fin::Side;
 
-use crate::{actions::limit_order::OrderPriceStrategy, conditions::Condition};
+use crate::{
+    actions::limit_order::OrderPriceStrategy,
+    conditions::{Condition, ErrorPolicy},
+    price::PriceSource,
+};
 
+/// A point in a chain's history, as a block time or a block height.
+#[cw_serde]
+pub enum Moment {
+    Timestamp(Timestamp),
+    Height(u64),
+}
+
+impl Moment {
+    /// Whether the chain has moved past the moment, matching when
+    /// the condition it converts into is satisfied.
+    pub fn has_passed(&self, env: &Env) -> bool {
+        match self {
+            Moment::Timestamp(time) => env.block.time > *time,
+            Moment::Height(height) => env.block.height > *height,
+        }
+    }
+
+    pub fn into_condition(&self) -> Condition {
+        match self {
+            Moment::Timestamp(time) => Condition::TimestampElapsed(*time),
+            Moment::Height(height) => Condition::BlocksCompleted(*height),
+        }
+    }
+}
+
+/// Cadences that wait on a time or height can set a `jitter` window,
+/// over which the scheduler randomly delays each trigger so execution
+/// times can't be predicted and front-run.
 #[cw_serde]
 pub enum Cadence {
+    Once {
+        at: Moment,
+        #[serde(default)]
+        jitter: Option<Duration>,
+    },
     Blocks {
         interval: u64,
         previous: Option<u64>,
+        #[serde(default)]
+        jitter: Option<Duration>,
     },
     Time {
         duration: Duration,
         previous: Option<Timestamp>,
+        #[serde(default)]
+        jitter: Option<Duration>,
     },
     Cron {
         expr: String,
         previous: Option<Timestamp>,
+        #[serde(default)]
+        jitter: Option<Duration>,
     },
     LimitOrder {
         pair_address: Addr,
@@ -28,23 +71,78 @@ pub enum Cadence {
         previous: Option<Decimal>,
         strategy: OrderPriceStrategy,
     },
+    /// Due whenever `condition` holds, at most once per `cooldown`.
+    /// Jitter delays the end of the cooldown and any time or height
+    /// the condition waits on. `on_error` applies both when checking
+    /// whether the cadence is due and to the triggers it creates.
+    Condition {
+        condition: Condition,
+        cooldown: Duration,
+        previous: Option<Timestamp>,
+        #[serde(default)]
+        jitter: Option<Duration>,
+        #[serde(default)]
+        on_error: ErrorPolicy,
+    },
 }
 
 impl Cadence {
+    pub fn size(&self) -> usize {
+        match self {
+            Cadence::Condition { condition, .. } => condition.size() + 1,
+            _ => 1,
+        }
+    }
+
+    /// The window over which each trigger for the cadence is randomly delayed.
+    pub fn jitter(&self) -> Option<Duration> {
+        match self {
+            Cadence::Once { jitter, .. }
+            | Cadence::Blocks { jitter, .. }
+            | Cadence::Time { jitter, .. }
+            | Cadence::Cron { jitter, .. }
+            | Cadence::Condition { jitter, .. } => *jitter,
+            Cadence::LimitOrder { .. } => None,
+        }
+    }
+
+    /// What to do if the condition the cadence waits on cannot be evaluated.
+    pub fn on_error(&self) -> ErrorPolicy {
+        match self {
+            Cadence::Condition { on_error, .. } => on_error.clone(),
+            // Keep the schedule moving rather than stalling on a failed check
+            _ => ErrorPolicy::TreatAsTrue,
+        }
+    }
+
+    /// Prices that need to be sampled into strategy storage
+    /// on each execution for this cadence to be evaluated.
+    pub fn price_sources(&self) -> Vec<PriceSource> {
+        match self {
+            Cadence::Condition { condition, .. } => condition.price_sources(),
+            _ => vec![],
+        }
+    }
+
     pub fn is_due(&self, deps: Deps, env: &Env, scheduler: &Addr) -> StdResult<bool> {
         Ok(match self {
-            Cadence::Blocks { interval, previous } => {
-                previous.map_or(true, |previous| env.block.height >= previous + interval)
-            }
-            Cadence::Time { duration, previous } => previous.map_or(true, |previous| {
+            Cadence::Once { at, .. } => at.has_passed(env),
+            Cadence::Blocks {
+                interval, previous, ..
+            } => previous.map_or(true, |previous| env.block.height >= previous + interval),
+            Cadence::Time {
+                duration, previous, ..
+            } => previous.map_or(true, |previous| {
                 env.block.time.seconds() >= previous.seconds() + duration.as_secs()
             }),
             Cadence::Cron { previous, .. } => {
                 if previous.is_none() {
                     true
                 } else {
-                    self.into_condition(deps, env, scheduler)?
-                        .is_satisfied(deps, env)?
+                    match self.into_condition(deps, env, scheduler)? {
+                        Some(condition) => condition.is_satisfied(deps, env)?,
+                        None => false,
+                    }
                 }
             }
             Cadence::LimitOrder {
@@ -83,20 +181,41 @@ impl Cadence {
                     return Ok(true);
                 }
             }
+            Cadence::Condition {
+                condition,
+                cooldown,
+                previous,
+                on_error,
+                ..
+            } => {
+                previous.map_or(true, |previous| {
+                    env.block.time.seconds() >= previous.seconds() + cooldown.as_secs()
+                }) && condition.evaluate(deps, env, on_error)?.0
+            }
         })
     }
 
-    pub fn into_condition(&self, deps: Deps, env: &Env, scheduler: &Addr) -> StdResult<Condition> {
-        Ok(match self {
-            Cadence::Blocks { interval, previous } => Condition::BlocksCompleted(
+    /// The condition under which the cadence is next due, or `None` if it
+    /// will never be due again.
+    pub fn into_condition(
+        &self,
+        deps: Deps,
+        env: &Env,
+        scheduler: &Addr,
+    ) -> StdResult<Option<Condition>> {
+        Ok(Some(match self {
+            Cadence::Once { at, .. } => at.into_condition(),
+            Cadence::Blocks {
+                interval, previous, ..
+            } => Condition::BlocksCompleted(
                 previous.map_or(env.block.height, |previous| previous + interval),
             ),
-            Cadence::Time { duration, previous } => {
-                Condition::TimestampElapsed(previous.map_or(env.block.time, |previous| {
-                    previous.plus_seconds(duration.as_secs())
-                }))
-            }
-            Cadence::Cron { expr, previous } => {
+            Cadence::Time {
+                duration, previous, ..
+            } => Condition::TimestampElapsed(previous.map_or(env.block.time, |previous| {
+                previous.plus_seconds(duration.as_secs())
+            })),
+            Cadence::Cron { expr, previous, .. } => {
                 let schedule = CronSchedule::from_str(expr)
                     .map_err(|e| StdError::generic_err(format!("Invalid cron expression: {e}")))?;
 
@@ -106,11 +225,11 @@ impl Cadence {
                     ))
                     .next();
 
-                if let Some(next) = next {
-                    Condition::TimestampElapsed(Timestamp::from_seconds(next.timestamp() as u64))
-                } else {
-                    // Cron expression has no next occurrence, treat as never due
-                    Condition::BlocksCompleted(u64::MAX)
+                match next {
+                    Some(next) => Condition::TimestampElapsed(Timestamp::from_seconds(
+                        next.timestamp() as u64,
+                    )),
+                    None => return Ok(None),
                 }
             }
             Cadence::LimitOrder {
@@ -132,13 +251,36 @@ impl Cadence {
                     price,
                 }
             }
-        })
+            Cadence::Condition {
+                condition,
+                cooldown,
+                previous,
+                ..
+            } => {
+                let cooldown_end =
+                    previous.map(|previous| previous.plus_seconds(cooldown.as_secs()));
+
+                match cooldown_end {
+                    Some(cooldown_end) if cooldown_end > env.block.time => {
+                        Condition::TimestampElapsed(cooldown_end)
+                    }
+                    // The scheduler evaluates the condition on our behalf
+                    _ => condition.clone().for_strategy(&env.contract.address),
+                }
+            }
+        }))
     }
 
     pub fn next(self, deps: Deps, env: &Env) -> StdResult<Self> {
         Ok(match self {
-            Cadence::Blocks { interval, previous } => Cadence::Blocks {
+            Cadence::Once { at, jitter } => Cadence::Once { at, jitter },
+            Cadence::Blocks {
                 interval,
+                previous,
+                jitter,
+            } => Cadence::Blocks {
+                interval,
+                jitter,
                 previous: Some(previous.map_or(env.block.height, |previous| {
                     let next = previous + interval;
                     if next < env.block.height {
@@ -149,8 +291,13 @@ impl Cadence {
                     }
                 })),
             },
-            Cadence::Time { duration, previous } => Cadence::Time {
+            Cadence::Time {
+                duration,
+                previous,
+                jitter,
+            } => Cadence::Time {
                 duration,
+                jitter,
                 previous: Some(previous.map_or(env.block.time, |previous| {
                     let duration = duration.as_secs();
                     let next = previous.plus_seconds(duration);
@@ -162,7 +309,11 @@ impl Cadence {
                     }
                 })),
             },
-            Cadence::Cron { expr, previous } => {
+            Cadence::Cron {
+                expr,
+                previous,
+                jitter,
+            } => {
                 let schedule = CronSchedule::from_str(&expr).map_err(|e| {
                     cosmwasm_std::StdError::generic_err(format!("Invalid cron expression: {e}"))
                 })?;
@@ -175,17 +326,22 @@ impl Cadence {
                     ))
                     .next();
 
-                if let Some(next) = next {
-                    Cadence::Cron {
+                match next {
+                    Some(next) => Cadence::Cron {
                         expr,
                         previous: Some(Timestamp::from_seconds(next.timestamp() as u64)),
-                    }
-                } else {
-                    // Cron expression has no next occurrence, treat as never due
-                    Cadence::Cron {
+                        jitter,
+                    },
+                    // No occurrences remain after this one, so anchoring here
+                    // leaves the cadence without a next condition
+                    None => Cadence::Cron {
                         expr,
-                        previous: Some(Timestamp::from_seconds(u64::MAX)),
-                    }
+                        previous: Some(
+                            previous
+                                .map_or(env.block.time, |previous| max(previous, env.block.time)),
+                        ),
+                        jitter,
+                    },
                 }
             }
             Cadence::LimitOrder {
@@ -220,6 +376,19 @@ impl Cadence {
                     }
                 }
             }
+            Cadence::Condition {
+                condition,
+                cooldown,
+                jitter,
+                on_error,
+                ..
+            } => Cadence::Condition {
+                condition,
+                cooldown,
+                previous: Some(env.block.time),
+                jitter,
+                on_error,
+            },
         })
     }
 }
@@ -249,52 +418,60 @@ mod tests {
         assert_eq!(
             Cadence::Blocks {
                 interval: 10,
-                previous: None
+                previous: None,
+                jitter: None,
             }
             .next(deps.as_ref(), &env)
             .unwrap(),
             Cadence::Blocks {
                 interval: 10,
-                previous: Some(env.block.height)
+                previous: Some(env.block.height),
+                jitter: None,
             }
         );
 
         assert_eq!(
             Cadence::Blocks {
                 interval: 10,
-                previous: Some(env.block.height - 5)
+                previous: Some(env.block.height - 5),
+                jitter: None,
             }
             .next(deps.as_ref(), &env)
             .unwrap(),
             Cadence::Blocks {
                 interval: 10,
-                previous: Some(env.block.height - 5 + 10)
+                previous: Some(env.block.height - 5 + 10),
+                jitter: None,
             }
         );
 
         assert_eq!(
             Cadence::Blocks {
                 interval: 10,
-                previous: Some(env.block.height - 15)
+                previous: Some(env.block.height - 15),
+                jitter: None,
             }
             .next(deps.as_ref(), &env)
             .unwrap(),
             Cadence::Blocks {
                 interval: 10,
-                previous: Some(env.block.height + 5)
+                previous: Some(env.block.height + 5),
+                jitter: None,
             }
         );
 
         assert_eq!(
             Cadence::Blocks {
                 interval: 10,
-                previous: Some(env.block.height - 155)
+                previous: Some(env.block.height - 155),
+                jitter: None,
             }
             .next(deps.as_ref(), &env)
             .unwrap(),
             Cadence::Blocks {
                 interval: 10,
-                previous: Some(env.block.height + 5)
+                previous: Some(env.block.height + 5),
+                jitter: None,
             }
         );
     }
@@ -307,52 +484,60 @@ mod tests {
         assert_eq!(
             Cadence::Time {
                 duration: std::time::Duration::from_secs(10),
-                previous: None
+                previous: None,
+                jitter: None,
             }
             .next(deps.as_ref(), &env)
             .unwrap(),
             Cadence::Time {
                 duration: std::time::Duration::from_secs(10),
-                previous: Some(env.block.time)
+                previous: Some(env.block.time),
+                jitter: None,
             }
         );
 
         assert_eq!(
             Cadence::Time {
                 duration: Duration::from_secs(10),
-                previous: Some(env.block.time.minus_seconds(5))
+                previous: Some(env.block.time.minus_seconds(5)),
+                jitter: None,
             }
             .next(deps.as_ref(), &env)
             .unwrap(),
             Cadence::Time {
                 duration: Duration::from_secs(10),
-                previous: Some(env.block.time.plus_seconds(5))
+                previous: Some(env.block.time.plus_seconds(5)),
+                jitter: None,
             }
         );
 
         assert_eq!(
             Cadence::Time {
                 duration: Duration::from_secs(10),
-                previous: Some(env.block.time.minus_seconds(15))
+                previous: Some(env.block.time.minus_seconds(15)),
+                jitter: None,
             }
             .next(deps.as_ref(), &env)
             .unwrap(),
             Cadence::Time {
                 duration: Duration::from_secs(10),
-                previous: Some(env.block.time.plus_seconds(5))
+                previous: Some(env.block.time.plus_seconds(5)),
+                jitter: None,
             }
         );
 
         assert_eq!(
             Cadence::Time {
                 duration: Duration::from_secs(10),
-                previous: Some(env.block.time.minus_seconds(155))
+                previous: Some(env.block.time.minus_seconds(155)),
+                jitter: None,
             }
             .next(deps.as_ref(), &env)
             .unwrap(),
             Cadence::Time {
                 duration: Duration::from_secs(10),
-                previous: Some(env.block.time.plus_seconds(5))
+                previous: Some(env.block.time.plus_seconds(5)),
+                jitter: None,
             }
         );
     }
@@ -367,7 +552,8 @@ mod tests {
         assert_eq!(
             Cadence::Cron {
                 expr: cron.to_string(),
-                previous: None
+                previous: None,
+                jitter: None,
             }
             .next(deps.as_ref(), &env)
             .unwrap(),
@@ -375,14 +561,16 @@ mod tests {
                 expr: cron.to_string(),
                 previous: Some(Timestamp::from_seconds(
                     env.block.time.seconds() - env.block.time.seconds() % 10 + 10
-                ))
+                )),
+                jitter: None,
             }
         );
 
         assert_eq!(
             Cadence::Cron {
                 expr: cron.to_string(),
-                previous: Some(Timestamp::from_seconds(0))
+                previous: Some(Timestamp::from_seconds(0)),
+                jitter: None,
             }
             .next(deps.as_ref(), &env)
             .unwrap(),
@@ -390,14 +578,16 @@ mod tests {
                 expr: cron.to_string(),
                 previous: Some(Timestamp::from_seconds(
                     env.block.time.seconds() - env.block.time.seconds() % 10 + 10
-                ))
+                )),
+                jitter: None,
             }
         );
 
         assert_eq!(
             Cadence::Cron {
                 expr: cron.to_string(),
-                previous: Some(env.block.time)
+                previous: Some(env.block.time),
+                jitter: None,
             }
             .next(deps.as_ref(), &env)
             .unwrap(),
@@ -405,14 +595,16 @@ mod tests {
                 expr: cron.to_string(),
                 previous: Some(Timestamp::from_seconds(
                     env.block.time.seconds() - env.block.time.seconds() % 10 + 10
-                ))
+                )),
+                jitter: None,
             }
         );
 
         assert_eq!(
             Cadence::Cron {
                 expr: cron.to_string(),
-                previous: Some(env.block.time.plus_seconds(10))
+                previous: Some(env.block.time.plus_seconds(10)),
+                jitter: None,
             }
             .next(deps.as_ref(), &env)
             .unwrap(),
@@ -420,7 +612,8 @@ mod tests {
                 expr: cron.to_string(),
                 previous: Some(Timestamp::from_seconds(
                     env.block.time.seconds() - env.block.time.seconds() % 10 + 20
-                ))
+                )),
+                jitter: None,
             }
         );
     }
@@ -516,9 +709,11 @@ mod tests {
         assert_eq!(
             Cadence::Blocks {
                 interval: 10,
-                previous: None
+                previous: None,
+                jitter: None,
             }
             .into_condition(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
+            .unwrap()
             .unwrap(),
             Condition::BlocksCompleted(env.block.height)
         );
@@ -526,9 +721,11 @@ mod tests {
         assert_eq!(
             Cadence::Blocks {
                 interval: 10,
-                previous: Some(env.block.height)
+                previous: Some(env.block.height),
+                jitter: None,
             }
             .into_condition(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
+            .unwrap()
             .unwrap(),
             Condition::BlocksCompleted(env.block.height + 10)
         );
@@ -536,9 +733,11 @@ mod tests {
         assert_eq!(
             Cadence::Blocks {
                 interval: 10,
-                previous: Some(env.block.height - 5)
+                previous: Some(env.block.height - 5),
+                jitter: None,
             }
             .into_condition(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
+            .unwrap()
             .unwrap(),
             Condition::BlocksCompleted(env.block.height - 5 + 10)
         );
@@ -552,9 +751,11 @@ mod tests {
         assert_eq!(
             Cadence::Time {
                 duration: Duration::from_secs(10),
-                previous: None
+                previous: None,
+                jitter: None,
             }
             .into_condition(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
+            .unwrap()
             .unwrap(),
             Condition::TimestampElapsed(env.block.time)
         );
@@ -562,9 +763,11 @@ mod tests {
         assert_eq!(
             Cadence::Time {
                 duration: Duration::from_secs(10),
-                previous: Some(env.block.time)
+                previous: Some(env.block.time),
+                jitter: None,
             }
             .into_condition(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
+            .unwrap()
             .unwrap(),
             Condition::TimestampElapsed(env.block.time.plus_seconds(10))
         );
@@ -572,9 +775,11 @@ mod tests {
         assert_eq!(
             Cadence::Time {
                 duration: Duration::from_secs(10),
-                previous: Some(env.block.time.minus_seconds(5))
+                previous: Some(env.block.time.minus_seconds(5)),
+                jitter: None,
             }
             .into_condition(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
+            .unwrap()
             .unwrap(),
             Condition::TimestampElapsed(env.block.time.plus_seconds(10 - 5))
         );
@@ -582,9 +787,11 @@ mod tests {
         assert_eq!(
             Cadence::Time {
                 duration: Duration::from_secs(10),
-                previous: Some(env.block.time.minus_seconds(155))
+                previous: Some(env.block.time.minus_seconds(155)),
+                jitter: None,
             }
             .into_condition(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
+            .unwrap()
             .unwrap(),
             Condition::TimestampElapsed(env.block.time.minus_seconds(155 - 10))
         );
@@ -599,8 +806,10 @@ mod tests {
             Cadence::Cron {
                 expr: "*/30 * * * * *".to_string(),
                 previous: None,
+                jitter: None,
             }
             .into_condition(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
+            .unwrap()
             .unwrap(),
             Condition::TimestampElapsed(Timestamp::from_seconds(
                 env.block.time.seconds() - env.block.time.seconds() % 30 + 30,
@@ -613,8 +822,10 @@ mod tests {
             Cadence::Cron {
                 expr: "*/30 * * * * *".to_string(),
                 previous: Some(previous),
+                jitter: None,
             }
             .into_condition(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
+            .unwrap()
             .unwrap(),
             Condition::TimestampElapsed(Timestamp::from_seconds(
                 previous.seconds() - previous.seconds() % 30 + 30,
@@ -624,6 +835,7 @@ mod tests {
         assert!(Cadence::Cron {
             expr: "bad cron".to_string(),
             previous: None,
+            jitter: None,
         }
         .into_condition(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap_err()
@@ -648,6 +860,7 @@ mod tests {
                 strategy: fixed_strategy.clone()
             }
             .into_condition(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
+            .unwrap()
             .unwrap(),
             Condition::LimitOrderFilled {
                 owner: Addr::unchecked("scheduler"),
@@ -687,6 +900,7 @@ mod tests {
                 strategy: offset_strategy.clone()
             }
             .into_condition(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
+            .unwrap()
             .unwrap(),
             Condition::LimitOrderFilled {
                 owner: Addr::unchecked("scheduler"),
@@ -704,28 +918,32 @@ mod tests {
 
         assert!(Cadence::Blocks {
             interval: 10,
-            previous: None
+            previous: None,
+            jitter: None,
         }
         .is_due(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap());
 
         assert!(!Cadence::Blocks {
             interval: 5,
-            previous: Some(env.block.height - 4)
+            previous: Some(env.block.height - 4),
+            jitter: None,
         }
         .is_due(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap());
 
         assert!(Cadence::Blocks {
             interval: 5,
-            previous: Some(env.block.height - 5)
+            previous: Some(env.block.height - 5),
+            jitter: None,
         }
         .is_due(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap());
 
         assert!(Cadence::Blocks {
             interval: 5,
-            previous: Some(env.block.height - 6)
+            previous: Some(env.block.height - 6),
+            jitter: None,
         }
         .is_due(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap());
@@ -738,28 +956,32 @@ mod tests {
 
         assert!(Cadence::Time {
             duration: Duration::from_secs(10),
-            previous: None
+            previous: None,
+            jitter: None,
         }
         .is_due(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap());
 
         assert!(!Cadence::Time {
             duration: Duration::from_secs(6),
-            previous: Some(env.block.time.minus_seconds(5))
+            previous: Some(env.block.time.minus_seconds(5)),
+            jitter: None,
         }
         .is_due(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap());
 
         assert!(Cadence::Time {
             duration: Duration::from_secs(5),
-            previous: Some(env.block.time.minus_seconds(5))
+            previous: Some(env.block.time.minus_seconds(5)),
+            jitter: None,
         }
         .is_due(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap());
 
         assert!(Cadence::Time {
             duration: Duration::from_secs(4),
-            previous: Some(env.block.time.minus_seconds(5))
+            previous: Some(env.block.time.minus_seconds(5)),
+            jitter: None,
         }
         .is_due(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap());
@@ -773,21 +995,24 @@ mod tests {
 
         assert!(Cadence::Cron {
             expr: cron.to_string(),
-            previous: None
+            previous: None,
+            jitter: None,
         }
         .is_due(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap());
 
         assert!(!Cadence::Cron {
             expr: cron.to_string(),
-            previous: Some(env.block.time.minus_seconds(5))
+            previous: Some(env.block.time.minus_seconds(5)),
+            jitter: None,
         }
         .is_due(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap());
 
         assert!(Cadence::Cron {
             expr: cron.to_string(),
-            previous: Some(env.block.time.minus_seconds(15))
+            previous: Some(env.block.time.minus_seconds(15)),
+            jitter: None,
         }
         .is_due(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap());
@@ -1031,4 +1256,210 @@ mod tests {
         .is_due(deps.as_ref(), &env, &Addr::unchecked("scheduler"))
         .unwrap());
     }
+
+    #[test]
+    fn once_schedule_is_due_after_its_moment() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+        let scheduler = Addr::unchecked("scheduler");
+
+        assert!(Cadence::Once {
+            at: Moment::Timestamp(env.block.time.minus_seconds(1)),
+            jitter: None,
+        }
+        .is_due(deps.as_ref(), &env, &scheduler)
+        .unwrap());
+
+        // Neither the cadence nor the trigger it creates fire at the moment itself
+        for at in [
+            Moment::Timestamp(env.block.time),
+            Moment::Height(env.block.height),
+        ] {
+            assert!(!Cadence::Once {
+                at: at.clone(),
+                jitter: None,
+            }
+            .is_due(deps.as_ref(), &env, &scheduler)
+            .unwrap());
+
+            assert!(!at
+                .into_condition()
+                .is_satisfied(deps.as_ref(), &env)
+                .unwrap());
+        }
+
+        assert!(!Cadence::Once {
+            at: Moment::Height(env.block.height + 1),
+            jitter: None,
+        }
+        .is_due(deps.as_ref(), &env, &scheduler)
+        .unwrap());
+
+        assert_eq!(
+            Cadence::Once {
+                at: Moment::Height(env.block.height + 1),
+                jitter: None,
+            }
+            .into_condition(deps.as_ref(), &env, &scheduler)
+            .unwrap(),
+            Some(Condition::BlocksCompleted(env.block.height + 1))
+        );
+
+        assert_eq!(
+            Cadence::Once {
+                at: Moment::Timestamp(env.block.time.plus_seconds(10)),
+                jitter: None,
+            }
+            .next(deps.as_ref(), &env)
+            .unwrap(),
+            Cadence::Once {
+                at: Moment::Timestamp(env.block.time.plus_seconds(10)),
+                jitter: None,
+            }
+        );
+    }
+
+    #[test]
+    fn exhausted_cron_has_no_next_condition() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+        let scheduler = Addr::unchecked("scheduler");
+
+        // Only fires in the year 2000, before the mock block time
+        let cron = "0 0 0 1 1 * 2000";
+
+        let cadence = Cadence::Cron {
+            expr: cron.to_string(),
+            previous: None,
+            jitter: None,
+        }
+        .next(deps.as_ref(), &env)
+        .unwrap();
+
+        assert_eq!(
+            cadence,
+            Cadence::Cron {
+                expr: cron.to_string(),
+                previous: Some(env.block.time),
+                jitter: None,
+            }
+        );
+
+        assert_eq!(
+            cadence
+                .into_condition(deps.as_ref(), &env, &scheduler)
+                .unwrap(),
+            None
+        );
+
+        assert!(!cadence.is_due(deps.as_ref(), &env, &scheduler).unwrap());
+    }
+
+    #[test]
+    fn keeps_jitter_when_updating_to_next() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+        let jitter = Some(Duration::from_secs(30));
+
+        let cadences = [
+            Cadence::Once {
+                at: Moment::Height(env.block.height),
+                jitter,
+            },
+            Cadence::Blocks {
+                interval: 5,
+                previous: None,
+                jitter,
+            },
+            Cadence::Time {
+                duration: Duration::from_secs(60),
+                previous: None,
+                jitter,
+            },
+            Cadence::Cron {
+                expr: "0 0 * * * *".to_string(),
+                previous: None,
+                jitter,
+            },
+            Cadence::Condition {
+                condition: Condition::BlocksCompleted(env.block.height),
+                cooldown: Duration::from_secs(60),
+                previous: None,
+                jitter,
+                on_error: ErrorPolicy::default(),
+            },
+        ];
+
+        for cadence in cadences {
+            assert_eq!(cadence.jitter(), jitter);
+            assert_eq!(cadence.next(deps.as_ref(), &env).unwrap().jitter(), jitter);
+        }
+    }
+
+    #[test]
+    fn condition_schedule_waits_out_cooldown() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+        let scheduler = Addr::unchecked("scheduler");
+
+        let cadence = |condition: Condition, previous: Option<Timestamp>| Cadence::Condition {
+            condition,
+            cooldown: Duration::from_secs(3_600),
+            previous,
+            jitter: None,
+            on_error: ErrorPolicy::default(),
+        };
+
+        let satisfied = Condition::BlocksCompleted(env.block.height - 1);
+
+        assert!(cadence(satisfied.clone(), None)
+            .is_due(deps.as_ref(), &env, &scheduler)
+            .unwrap());
+
+        assert!(
+            !cadence(Condition::BlocksCompleted(env.block.height + 1), None)
+                .is_due(deps.as_ref(), &env, &scheduler)
+                .unwrap()
+        );
+
+        assert!(
+            !cadence(satisfied.clone(), Some(env.block.time.minus_seconds(10)))
+                .is_due(deps.as_ref(), &env, &scheduler)
+                .unwrap()
+        );
+
+        assert_eq!(
+            cadence(satisfied.clone(), Some(env.block.time.minus_seconds(10)))
+                .into_condition(deps.as_ref(), &env, &scheduler)
+                .unwrap(),
+            Some(Condition::TimestampElapsed(
+                env.block.time.plus_seconds(3_600 - 10)
+            ))
+        );
+
+        assert_eq!(
+            cadence(
+                Condition::ExecutionCount {
+                    min: Some(1),
+                    max: None,
+                    strategy: None,
+                },
+                Some(env.block.time.minus_seconds(3_600))
+            )
+            .into_condition(deps.as_ref(), &env, &scheduler)
+            .unwrap(),
+            Some(Condition::ExecutionCount {
+                min: Some(1),
+                max: None,
+                strategy: Some(env.contract.address.clone()),
+            })
+        );
+
+        assert_eq!(
+            cadence(satisfied.clone(), None)
+                .next(deps.as_ref(), &env)
+                .unwrap(),
+            cadence(satisfied, Some(env.block.time))
+        );
+    }
 }
@@ -1,24 +1,65 @@
 use std::{
     hash::{DefaultHasher, Hasher},
+    str::FromStr,
+    time::Duration,
     vec,
 };
 
+use chrono::{DateTime, FixedOffset};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    to_json_binary, Addr, Coin, Decimal, Deps, Env, StdError, StdResult, Timestamp,
-};
-use rujira_rs::{
-    fin::{OrderResponse, Price, QueryMsg, Side},
-    query::Pool,
-    Layer1Asset,
+    to_json_binary, Addr, Coin, Decimal, Deps, Env, Event, StdError, StdResult, Timestamp, Uint128,
 };
+use cron::Schedule as CronSchedule;
+use rujira_rs::fin::{OrderResponse, Price, QueryMsg, Side};
 
 use crate::{
-    actions::{limit_order::Direction, swaps::swap::Swap},
+    actions::{distribution::Recipient, limit_order::Direction, swaps::swap::Swap},
+    constants::{PRICE_HISTORY_CAPACITY, PRICE_SAMPLE_INTERVAL_SECONDS},
     core::Threshold,
+    heartbeat::load_heartbeat,
     manager::{ManagerQueryMsg, StrategyHandle, StrategyStatus},
+    price::{fin_book_depth, fin_price, oracle_price, BookSide, PriceSource, Valuation},
+    price_history::{average_price, load_price_series, price_at, PriceSample},
+    query::{query_json_path, validate_query, Comparator},
+    statistics::load_statistics,
+    thorchain::{outbound_delay_seconds, pool_depth, trading_unavailable_reason},
 };
 
+enum ConditionEvent {
+    EvaluationFailed { policy: ErrorPolicy, error: String },
+}
+
+impl From<ConditionEvent> for Event {
+    fn from(val: ConditionEvent) -> Self {
+        match val {
+            ConditionEvent::EvaluationFailed { policy, error } => {
+                Event::new("condition_evaluation_failed")
+                    .add_attribute("policy", format!("{policy:?}"))
+                    .add_attribute("error", error)
+            }
+        }
+    }
+}
+
+/// What to do when a condition cannot be evaluated, e.g. because
+/// a contract it queries is unavailable.
+#[cw_serde]
+#[derive(Default)]
+pub enum ErrorPolicy {
+    #[default]
+    TreatAsFalse,
+    TreatAsTrue,
+    Fail,
+}
+
+/// The longest Thorchain may take to pay out a swap of `swap_amount`.
+#[cw_serde]
+pub struct OutboundDelay {
+    pub swap_amount: Coin,
+    pub max_delay: Duration,
+}
+
 #[cw_serde]
 pub struct CompositeCondition {
     pub conditions: Vec<Condition>,
@@ -29,6 +70,14 @@ pub struct CompositeCondition {
 pub enum Condition {
     TimestampElapsed(Timestamp),
     BlocksCompleted(u64),
+    /// Satisfied while the block time, shifted by `utc_offset` seconds,
+    /// falls on one of the `days` and `hours` given in cron field syntax
+    /// (e.g. `Mon-Fri` and `13-20` for weekdays 13:00 to 21:00).
+    TimeWindow {
+        days: String,
+        hours: String,
+        utc_offset: Option<i32>,
+    },
     CanSwap(Swap),
     LimitOrderFilled {
         owner: Addr,
@@ -43,6 +92,16 @@ pub enum Condition {
     StrategyBalanceAvailable {
         amount: Coin,
     },
+    /// Compares the value of one balance divided by another, held by
+    /// `address` or the strategy itself, against `ratio`.
+    BalanceRatio {
+        address: Option<Addr>,
+        numerator_denom: String,
+        denominator_denom: String,
+        valuation: Valuation,
+        direction: Direction,
+        ratio: Decimal,
+    },
     StrategyStatus {
         manager_contract: Addr,
         contract_address: Addr,
@@ -53,6 +112,77 @@ pub enum Condition {
         direction: Direction,
         rate: Decimal,
     },
+    FinPrice {
+        pair_address: Addr,
+        side: BookSide,
+        direction: Direction,
+        price: Decimal,
+    },
+    ThorchainTradingAvailable {
+        asset: String,
+        #[serde(default)]
+        max_outbound_delay: Option<OutboundDelay>,
+    },
+    PoolDepth {
+        asset: String,
+        min_depth: Uint128,
+    },
+    FinBookDepth {
+        pair_address: Addr,
+        side: Side,
+        band_bps: u64,
+        min_depth: Uint128,
+    },
+    PriceChange {
+        asset: String,
+        window: Duration,
+        direction: Direction,
+        bps: u64,
+        strategy: Option<Addr>,
+    },
+    /// Satisfied when the fast moving average of the sampled price has
+    /// crossed the slow one in `direction` since the previous sample.
+    MovingAverageCross {
+        source: PriceSource,
+        fast_window: Duration,
+        slow_window: Duration,
+        direction: Direction,
+        strategy: Option<Addr>,
+    },
+    TotalDebited {
+        amount: Coin,
+        strategy: Option<Addr>,
+    },
+    TotalCredited {
+        recipient: Recipient,
+        amount: Coin,
+        strategy: Option<Addr>,
+    },
+    ExecutionCount {
+        min: Option<u64>,
+        max: Option<u64>,
+        strategy: Option<Addr>,
+    },
+    /// Satisfied once the strategy owner has gone longer than `period`
+    /// without sending a heartbeat (or creating the strategy).
+    OwnerInactive {
+        period: Duration,
+        strategy: Option<Addr>,
+    },
+    /// Smart queries `contract` with `msg` and compares the value found
+    /// at the JSON pointer `path` (e.g. `/share_price`) against `value`,
+    /// numerically when both are decimals and as strings otherwise.
+    /// Responses larger than `max_response_bytes` (capped at
+    /// `MAX_QUERY_RESPONSE_BYTES`, the default) are rejected.
+    Query {
+        contract: Addr,
+        msg: String,
+        path: String,
+        comparator: Comparator,
+        value: String,
+        #[serde(default)]
+        max_response_bytes: Option<usize>,
+    },
     Not(Box<Condition>),
     Composite(CompositeCondition),
 }
@@ -62,12 +192,25 @@ impl Condition {
         match self {
             Condition::TimestampElapsed(_) => 1,
             Condition::BlocksCompleted(_) => 1,
+            Condition::TimeWindow { .. } => 1,
             Condition::CanSwap { .. } => 2,
             Condition::LimitOrderFilled { .. } => 2,
             Condition::BalanceAvailable { .. } => 1,
             Condition::StrategyBalanceAvailable { .. } => 1,
+            Condition::BalanceRatio { .. } => 2,
             Condition::StrategyStatus { .. } => 2,
             Condition::OraclePrice { .. } => 2,
+            Condition::FinPrice { .. } => 2,
+            Condition::ThorchainTradingAvailable { .. } => 2,
+            Condition::PoolDepth { .. } => 2,
+            Condition::FinBookDepth { .. } => 2,
+            Condition::PriceChange { .. } => 2,
+            Condition::MovingAverageCross { .. } => 2,
+            Condition::TotalDebited { .. } => 1,
+            Condition::TotalCredited { .. } => 1,
+            Condition::ExecutionCount { .. } => 1,
+            Condition::OwnerInactive { .. } => 1,
+            Condition::Query { .. } => 2,
             Condition::Not(condition) => condition.size(),
             Condition::Composite(CompositeCondition {
                 conditions,
@@ -83,10 +226,288 @@ impl Condition {
         Ok(hash.finish())
     }
 
+    /// Prices that need to be sampled into strategy storage
+    /// on each execution for this condition to be evaluated.
+    pub fn price_sources(&self) -> Vec<PriceSource> {
+        match self {
+            Condition::PriceChange { asset, .. } => vec![PriceSource::Oracle {
+                asset: asset.clone(),
+            }],
+            Condition::MovingAverageCross { source, .. } => vec![source.clone()],
+            Condition::Not(condition) => condition.price_sources(),
+            Condition::Composite(CompositeCondition { conditions, .. }) => conditions
+                .iter()
+                .flat_map(|condition| condition.price_sources())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Names `strategy` in every condition that would otherwise read from
+    /// whichever contract evaluates it, so it can be evaluated elsewhere
+    /// (e.g. by the scheduler) with the same result.
+    pub fn for_strategy(self, strategy: &Addr) -> Condition {
+        match self {
+            Condition::StrategyBalanceAvailable { amount } => Condition::BalanceAvailable {
+                address: strategy.clone(),
+                amount,
+            },
+            Condition::BalanceRatio {
+                address,
+                numerator_denom,
+                denominator_denom,
+                valuation,
+                direction,
+                ratio,
+            } => Condition::BalanceRatio {
+                address: address.or(Some(strategy.clone())),
+                numerator_denom,
+                denominator_denom,
+                valuation,
+                direction,
+                ratio,
+            },
+            Condition::PriceChange {
+                asset,
+                window,
+                direction,
+                bps,
+                strategy: local,
+            } => Condition::PriceChange {
+                asset,
+                window,
+                direction,
+                bps,
+                strategy: local.or(Some(strategy.clone())),
+            },
+            Condition::MovingAverageCross {
+                source,
+                fast_window,
+                slow_window,
+                direction,
+                strategy: local,
+            } => Condition::MovingAverageCross {
+                source,
+                fast_window,
+                slow_window,
+                direction,
+                strategy: local.or(Some(strategy.clone())),
+            },
+            Condition::TotalDebited {
+                amount,
+                strategy: local,
+            } => Condition::TotalDebited {
+                amount,
+                strategy: local.or(Some(strategy.clone())),
+            },
+            Condition::TotalCredited {
+                recipient,
+                amount,
+                strategy: local,
+            } => Condition::TotalCredited {
+                recipient,
+                amount,
+                strategy: local.or(Some(strategy.clone())),
+            },
+            Condition::ExecutionCount {
+                min,
+                max,
+                strategy: local,
+            } => Condition::ExecutionCount {
+                min,
+                max,
+                strategy: local.or(Some(strategy.clone())),
+            },
+            Condition::OwnerInactive {
+                period,
+                strategy: local,
+            } => Condition::OwnerInactive {
+                period,
+                strategy: local.or(Some(strategy.clone())),
+            },
+            Condition::Not(condition) => Condition::Not(Box::new(condition.for_strategy(strategy))),
+            Condition::Composite(CompositeCondition {
+                conditions,
+                threshold,
+            }) => Condition::Composite(CompositeCondition {
+                conditions: conditions
+                    .into_iter()
+                    .map(|condition| condition.for_strategy(strategy))
+                    .collect(),
+                threshold,
+            }),
+            condition => condition,
+        }
+    }
+
+    /// Checks the condition is well formed without evaluating it.
+    pub fn validate(&self) -> StdResult<()> {
+        match self {
+            Condition::TimeWindow {
+                days,
+                hours,
+                utc_offset,
+            } => {
+                time_window_schedule(days, hours)?;
+                time_window_offset(utc_offset)?;
+            }
+            Condition::ThorchainTradingAvailable {
+                asset,
+                max_outbound_delay: Some(OutboundDelay { swap_amount, .. }),
+            } => {
+                if swap_amount.amount.is_zero() || swap_amount.denom == *asset {
+                    return Err(StdError::generic_err(
+                        "Outbound delay swap amount must be non-zero and in a different denom",
+                    ));
+                }
+            }
+            Condition::PriceChange { window, bps, .. } => {
+                if window.as_secs() == 0 || *bps == 0 {
+                    return Err(StdError::generic_err(
+                        "Price change window and basis points must be greater than zero",
+                    ));
+                }
+
+                // The history spans one interval fewer than it holds samples
+                if window.as_secs()
+                    > (PRICE_HISTORY_CAPACITY - 1) as u64 * PRICE_SAMPLE_INTERVAL_SECONDS
+                {
+                    return Err(StdError::generic_err(
+                        "Price change window exceeds the price history retained",
+                    ));
+                }
+            }
+            Condition::MovingAverageCross {
+                fast_window,
+                slow_window,
+                ..
+            } => {
+                if fast_window.as_secs() == 0 || fast_window >= slow_window {
+                    return Err(StdError::generic_err(
+                        "Fast window must be greater than zero and shorter than the slow window",
+                    ));
+                }
+
+                // The history spans one interval fewer than it holds samples,
+                // and the cross needs a sample more for the previous average
+                if slow_window.as_secs()
+                    > (PRICE_HISTORY_CAPACITY - 2) as u64 * PRICE_SAMPLE_INTERVAL_SECONDS
+                {
+                    return Err(StdError::generic_err(
+                        "Slow window exceeds the price history retained",
+                    ));
+                }
+            }
+            Condition::BalanceRatio {
+                numerator_denom,
+                denominator_denom,
+                ..
+            } => {
+                if numerator_denom == denominator_denom {
+                    return Err(StdError::generic_err(
+                        "Balance ratio must compare two different denoms",
+                    ));
+                }
+            }
+            Condition::FinBookDepth { band_bps, .. } => {
+                if *band_bps == 0 || *band_bps > 10_000 {
+                    return Err(StdError::generic_err(
+                        "Book depth band must be between 1 and 10,000 basis points",
+                    ));
+                }
+            }
+            Condition::ExecutionCount {
+                min: Some(min),
+                max: Some(max),
+                ..
+            } => {
+                if min > max {
+                    return Err(StdError::generic_err(
+                        "Execution count minimum cannot be greater than maximum",
+                    ));
+                }
+            }
+            Condition::OwnerInactive { period, .. } => {
+                if period.as_secs() == 0 {
+                    return Err(StdError::generic_err(
+                        "Owner inactivity period must be greater than zero",
+                    ));
+                }
+            }
+            Condition::Query {
+                msg,
+                path,
+                max_response_bytes,
+                ..
+            } => validate_query(msg, path, *max_response_bytes)?,
+            Condition::Not(condition) => condition.validate()?,
+            Condition::Composite(CompositeCondition {
+                conditions,
+                threshold,
+            }) => {
+                if conditions.is_empty() {
+                    return Err(StdError::generic_err(
+                        "Composite condition must contain at least one condition",
+                    ));
+                }
+
+                let unreachable = match threshold {
+                    Threshold::AtLeast(n) => *n == 0 || *n as usize > conditions.len(),
+                    Threshold::Exactly(n) => *n as usize > conditions.len(),
+                    Threshold::All | Threshold::Any => false,
+                };
+
+                if unreachable {
+                    return Err(StdError::generic_err(format!(
+                        "Threshold {threshold:?} cannot be met by {} conditions",
+                        conditions.len()
+                    )));
+                }
+
+                for condition in conditions {
+                    condition.validate()?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
     pub fn is_satisfied(&self, deps: Deps, env: &Env) -> StdResult<bool> {
+        self.check(deps, env, &mut None)
+    }
+
+    /// Checks the condition, recording the values it compares against
+    /// its parameters into `observed` as they are fetched, if given.
+    fn check(
+        &self,
+        deps: Deps,
+        env: &Env,
+        observed: &mut Option<&mut Vec<(String, String)>>,
+    ) -> StdResult<bool> {
         Ok(match self {
-            Condition::TimestampElapsed(timestamp) => env.block.time > *timestamp,
-            Condition::BlocksCompleted(height) => env.block.height > *height,
+            Condition::TimestampElapsed(timestamp) => {
+                observe(observed, "block_time", env.block.time.seconds());
+                env.block.time > *timestamp
+            }
+            Condition::BlocksCompleted(height) => {
+                observe(observed, "block_height", env.block.height);
+                env.block.height > *height
+            }
+            Condition::TimeWindow {
+                days,
+                hours,
+                utc_offset,
+            } => {
+                observe(observed, "block_time", env.block.time.seconds());
+
+                let time = DateTime::from_timestamp(env.block.time.seconds() as i64, 0)
+                    .ok_or_else(|| StdError::generic_err("Block time out of range"))?;
+
+                time_window_schedule(days, hours)?
+                    .includes(time.with_timezone(&time_window_offset(utc_offset)?))
+            }
             Condition::LimitOrderFilled {
                 owner,
                 pair_address,
@@ -102,19 +523,70 @@ impl Condition {
                     )),
                 )?;
 
+                observe(observed, "remaining", order.remaining);
                 order.remaining.is_zero()
             }
-            Condition::CanSwap(swap) => swap.best_route(deps, env)?.is_some(),
+            Condition::CanSwap(swap) => match swap.best_route(deps, env)? {
+                Some(quote) => {
+                    observe(
+                        observed,
+                        "expected_amount_out",
+                        quote.state.expected_amount_out,
+                    );
+                    true
+                }
+                None => false,
+            },
             Condition::BalanceAvailable { address, amount } => {
                 let balance = deps.querier.query_balance(address, amount.denom.clone())?;
+                observe(observed, "balance", &balance);
                 balance.amount >= amount.amount
             }
             Condition::StrategyBalanceAvailable { amount } => {
                 let balance = deps
                     .querier
                     .query_balance(&env.contract.address, amount.denom.clone())?;
+                observe(observed, "balance", &balance);
                 balance.amount >= amount.amount
             }
+            Condition::BalanceRatio {
+                address,
+                numerator_denom,
+                denominator_denom,
+                valuation,
+                direction,
+                ratio,
+            } => {
+                let address = address.as_ref().unwrap_or(&env.contract.address);
+
+                let numerator = valuation
+                    .value(deps, &deps.querier.query_balance(address, numerator_denom)?)?;
+                observe(observed, "numerator_value", numerator);
+
+                let denominator = valuation.value(
+                    deps,
+                    &deps.querier.query_balance(address, denominator_denom)?,
+                )?;
+                observe(observed, "denominator_value", denominator);
+
+                if denominator.is_zero() {
+                    // Any holding at all outweighs nothing
+                    match direction {
+                        Direction::Above => !numerator.is_zero(),
+                        Direction::Below => false,
+                    }
+                } else {
+                    let current =
+                        Decimal::checked_from_ratio(numerator, denominator).map_err(|e| {
+                            StdError::generic_err(format!("Failed to calculate balance ratio: {e}"))
+                        })?;
+
+                    match direction {
+                        Direction::Above => current > *ratio,
+                        Direction::Below => current < *ratio,
+                    }
+                }
+            }
             Condition::StrategyStatus {
                 manager_contract,
                 contract_address,
@@ -126,6 +598,8 @@ impl Condition {
                         address: contract_address.clone(),
                     },
                 )?;
+
+                observe(observed, "status", format!("{:?}", strategy.status));
                 strategy.status == *status
             }
             Condition::OraclePrice {
@@ -133,64 +607,351 @@ impl Condition {
                 direction,
                 rate,
             } => {
-                let layer_1_asset = Layer1Asset::from_native(asset.clone()).map_err(|e| {
-                    StdError::generic_err(format!(
-                        "Denom ({asset}) not a secured asset, error: {e}"
-                    ))
-                })?;
+                let price = oracle_price(deps, asset)?;
+                observe(observed, "price", price);
+
+                match direction {
+                    Direction::Above => price > *rate,
+                    Direction::Below => price < *rate,
+                }
+            }
+            Condition::FinPrice {
+                pair_address,
+                side,
+                direction,
+                price,
+            } => {
+                let book_price = fin_price(deps, pair_address, side)?;
+                observe(observed, "price", book_price);
 
-                let oracle_price = Pool::load(deps.querier, &layer_1_asset)
+                match direction {
+                    Direction::Above => book_price > *price,
+                    Direction::Below => book_price < *price,
+                }
+            }
+            Condition::ThorchainTradingAvailable {
+                asset,
+                max_outbound_delay,
+            } => {
+                let unavailable = trading_unavailable_reason(deps.querier, env.block.height, asset)
                     .map_err(|e| {
                         StdError::generic_err(format!(
-                            "Failed to load oracle price for {asset}, error: {e}"
+                            "Failed to check trading availability for {asset}: {e}"
                         ))
-                    })?
-                    .asset_tor_price;
+                    })?;
 
-                match direction {
-                    Direction::Above => oracle_price > *rate,
-                    Direction::Below => oracle_price < *rate,
+                if let Some(reason) = &unavailable {
+                    observe(observed, "unavailable_reason", reason);
+                }
+
+                unavailable.is_none()
+                    && match max_outbound_delay {
+                        Some(OutboundDelay {
+                            swap_amount,
+                            max_delay,
+                        }) => {
+                            let delay = outbound_delay_seconds(deps.querier, swap_amount, asset)
+                                .map_err(|e| {
+                                    StdError::generic_err(format!(
+                                        "Failed to quote outbound delay for {asset}: {e}"
+                                    ))
+                                })?;
+
+                            observe(observed, "outbound_delay_seconds", delay);
+                            delay <= max_delay.as_secs()
+                        }
+                        None => true,
+                    }
+            }
+            Condition::PoolDepth { asset, min_depth } => {
+                let depth = pool_depth(deps.querier, asset)?;
+                observe(observed, "depth", depth);
+                depth >= *min_depth
+            }
+            Condition::FinBookDepth {
+                pair_address,
+                side,
+                band_bps,
+                min_depth,
+            } => {
+                let depth = fin_book_depth(deps, pair_address, side, *band_bps)?;
+                observe(observed, "depth", depth);
+                depth >= *min_depth
+            }
+            Condition::PriceChange {
+                asset,
+                window,
+                direction,
+                bps,
+                strategy,
+            } => {
+                let price = oracle_price(deps, asset)?;
+                observe(observed, "price", price);
+
+                let series = load_price_series(
+                    deps,
+                    env,
+                    strategy,
+                    &PriceSource::Oracle {
+                        asset: asset.clone(),
+                    },
+                )?;
+
+                // Until the history reaches back over the whole
+                // window there is nothing to compare against.
+                match price_at(&series, env.block.time.minus_seconds(window.as_secs())) {
+                    Some(reference) => {
+                        observe(observed, "reference_price", reference);
+
+                        let change = reference * Decimal::bps(*bps);
+
+                        match direction {
+                            Direction::Above => price >= reference + change,
+                            Direction::Below => price <= reference.saturating_sub(change),
+                        }
+                    }
+                    None => false,
+                }
+            }
+            Condition::MovingAverageCross {
+                source,
+                fast_window,
+                slow_window,
+                direction,
+                strategy,
+            } => {
+                let series = load_price_series(deps, env, strategy, source)?;
+
+                let averages = |series: &[PriceSample], at: Timestamp| {
+                    Some((
+                        average_price(series, at.minus_seconds(fast_window.as_secs()))?,
+                        average_price(series, at.minus_seconds(slow_window.as_secs()))?,
+                    ))
+                };
+
+                let fast =
+                    average_price(&series, env.block.time.minus_seconds(fast_window.as_secs()));
+                let slow =
+                    average_price(&series, env.block.time.minus_seconds(slow_window.as_secs()));
+
+                if let Some(fast) = fast {
+                    observe(observed, "fast_average", fast);
+                }
+
+                if let Some(slow) = slow {
+                    observe(observed, "slow_average", slow);
+                }
+
+                // Compare against the averages as of the previous sample,
+                // so the condition only holds on the sample that crosses
+                let previous = series
+                    .split_last()
+                    .and_then(|(_, earlier)| averages(earlier, earlier.last()?.time));
+
+                match (fast.zip(slow), previous) {
+                    (Some((fast, slow)), Some((previous_fast, previous_slow))) => match direction {
+                        Direction::Above => fast > slow && previous_fast <= previous_slow,
+                        Direction::Below => fast < slow && previous_fast >= previous_slow,
+                    },
+                    _ => false,
                 }
             }
+            Condition::TotalDebited { amount, strategy } => {
+                let debited = load_statistics(deps, env, strategy)?.debited_amount(&amount.denom);
+                observe(observed, "total_debited", &debited);
+                debited.amount >= amount.amount
+            }
+            Condition::TotalCredited {
+                recipient,
+                amount,
+                strategy,
+            } => {
+                let credited =
+                    load_statistics(deps, env, strategy)?.credited_amount(recipient, &amount.denom);
+                observe(observed, "total_credited", &credited);
+                credited.amount >= amount.amount
+            }
+            Condition::ExecutionCount { min, max, strategy } => {
+                let executions = load_statistics(deps, env, strategy)?.executions;
+                observe(observed, "executions", executions);
+
+                min.map_or(true, |min| executions >= min)
+                    && max.map_or(true, |max| executions <= max)
+            }
+            Condition::OwnerInactive { period, strategy } => {
+                observe(observed, "block_time", env.block.time.seconds());
+
+                let heartbeat = load_heartbeat(deps, env, strategy)?;
+                observe(observed, "last_heartbeat", heartbeat.seconds());
+
+                env.block.time > heartbeat.plus_seconds(period.as_secs())
+            }
+            Condition::Query {
+                contract,
+                msg,
+                path,
+                comparator,
+                value,
+                max_response_bytes,
+            } => {
+                let actual = query_json_path(deps, contract, msg, path, *max_response_bytes)?;
+                observe(observed, "value", &actual);
+                comparator.compare(&actual, value)
+            }
             Condition::Not(condition) => !condition.is_satisfied(deps, env)?,
             Condition::Composite(CompositeCondition {
                 conditions,
                 threshold,
-            }) => match threshold {
-                Threshold::All => conditions
-                    .iter()
-                    .map(|c| c.is_satisfied(deps, env))
-                    .collect::<StdResult<Vec<bool>>>()?
-                    .into_iter()
-                    .all(|b| b),
-                Threshold::Any => conditions
+            }) => threshold.outcome(
+                conditions
                     .iter()
-                    .map(|c| c.is_satisfied(deps, env))
-                    .collect::<StdResult<Vec<bool>>>()?
-                    .into_iter()
-                    .any(|b| b),
-            },
+                    .map(|condition| condition.is_satisfied(deps, env)),
+            )?,
         })
     }
+
+    /// Evaluates the condition, resolving any error with `policy`. Unless
+    /// the policy is to fail, the error is reported in the returned event.
+    pub fn evaluate(
+        &self,
+        deps: Deps,
+        env: &Env,
+        policy: &ErrorPolicy,
+    ) -> StdResult<(bool, Option<Event>)> {
+        match self.is_satisfied(deps, env) {
+            Ok(satisfied) => Ok((satisfied, None)),
+            Err(err) => {
+                let satisfied = match policy {
+                    ErrorPolicy::TreatAsFalse => false,
+                    ErrorPolicy::TreatAsTrue => true,
+                    ErrorPolicy::Fail => return Err(err),
+                };
+
+                Ok((
+                    satisfied,
+                    Some(
+                        ConditionEvent::EvaluationFailed {
+                            policy: policy.clone(),
+                            error: err.to_string(),
+                        }
+                        .into(),
+                    ),
+                ))
+            }
+        }
+    }
+
+    /// Evaluates every node of the condition tree once, recording what
+    /// each one observed and any error that would otherwise be swallowed.
+    pub fn trace(&self, deps: Deps, env: &Env) -> ConditionTrace {
+        match self {
+            Condition::Not(condition) => {
+                let child = condition.trace(deps, env);
+
+                ConditionTrace {
+                    satisfied: child.error.is_none() && !child.satisfied,
+                    error: child.error.clone(),
+                    observed: vec![],
+                    children: vec![child],
+                }
+            }
+            Condition::Composite(CompositeCondition {
+                conditions,
+                threshold,
+            }) => {
+                // Unlike evaluation, every child is traced so none are hidden
+                // by short circuiting, but the outcome is decided the same way
+                let children = conditions
+                    .iter()
+                    .map(|condition| condition.trace(deps, env))
+                    .collect::<Vec<_>>();
+
+                let outcome = threshold.outcome(children.iter().map(|child| match &child.error {
+                    Some(error) => Err(error.clone()),
+                    None => Ok(child.satisfied),
+                }));
+
+                let (satisfied, error) = match outcome {
+                    Ok(satisfied) => (satisfied, None),
+                    Err(error) => (false, Some(error)),
+                };
+
+                ConditionTrace {
+                    satisfied,
+                    error,
+                    observed: vec![],
+                    children,
+                }
+            }
+            _ => {
+                let mut observed = vec![];
+
+                let (satisfied, error) = match self.check(deps, env, &mut Some(&mut observed)) {
+                    Ok(satisfied) => (satisfied, None),
+                    Err(err) => (false, Some(err.to_string())),
+                };
+
+                ConditionTrace {
+                    satisfied,
+                    error,
+                    observed,
+                    children: vec![],
+                }
+            }
+        }
+    }
+}
+
+fn observe(observed: &mut Option<&mut Vec<(String, String)>>, label: &str, value: impl ToString) {
+    if let Some(observed) = observed {
+        observed.push((label.to_string(), value.to_string()));
+    }
+}
+
+/// The outcome of evaluating a condition, mirroring the shape of the
+/// condition tree. Errored conditions are reported as not satisfied.
+#[cw_serde]
+pub struct ConditionTrace {
+    pub satisfied: bool,
+    pub error: Option<String>,
+    pub observed: Vec<(String, String)>,
+    pub children: Vec<ConditionTrace>,
+}
+
+fn time_window_schedule(days: &str, hours: &str) -> StdResult<CronSchedule> {
+    CronSchedule::from_str(&format!("* * {hours} * * {days}"))
+        .map_err(|e| StdError::generic_err(format!("Invalid time window: {e}")))
+}
+
+fn time_window_offset(utc_offset: &Option<i32>) -> StdResult<FixedOffset> {
+    FixedOffset::east_opt(utc_offset.unwrap_or_default())
+        .ok_or_else(|| StdError::generic_err("UTC offset must be less than a day"))
 }
 
 #[cfg(test)]
 mod conditions_tests {
     use super::*;
-    use std::str::FromStr;
+    use std::{cell::Cell, rc::Rc, str::FromStr};
 
     use cosmwasm_std::{
         testing::{mock_dependencies, mock_env},
-        to_json_binary, Addr, Coin, ContractResult, Decimal, SystemResult, Timestamp, Uint128,
+        to_json_binary, Addr, Binary, Coin, ContractResult, Decimal, SystemResult, Timestamp,
+        Uint128,
+    };
+    use rujira_rs::fin::{
+        BookItemResponse, BookResponse, OrderResponse, Price, Side, SimulationResponse,
     };
-    use rujira_rs::fin::{OrderResponse, Price, Side, SimulationResponse};
 
     use crate::{
         actions::{
             swaps::fin::FinRoute,
             swaps::swap::{SwapAmountAdjustment, SwapRoute},
         },
+        constants::MAX_QUERY_RESPONSE_BYTES,
+        heartbeat::HEARTBEAT,
         manager::{StrategyHandle, StrategyStatus},
+        price_history::PriceSample,
+        statistics::{Statistics, STATISTICS},
     };
 
     #[test]
@@ -230,6 +991,45 @@ mod conditions_tests {
             .unwrap());
     }
 
+    #[test]
+    fn time_window_check() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+
+        // Mock block time is Wednesday 2019-10-23 02:23:39 UTC
+        let window = |days: &str, hours: &str, utc_offset: Option<i32>| Condition::TimeWindow {
+            days: days.to_string(),
+            hours: hours.to_string(),
+            utc_offset,
+        };
+
+        assert!(window("Mon-Fri", "0-3", None)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(window("*", "2", None)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!window("Mon-Fri", "13-20", None)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!window("Sat,Sun", "*", None)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(window("Mon-Fri", "13-20", Some(11 * 3_600))
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(window("Tue", "*", Some(-3 * 3_600))
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+
+        assert!(window("Mon-Fri", "25", None).validate().is_err());
+        assert!(window("Someday", "*", None).validate().is_err());
+        assert!(window("*", "*", Some(86_400)).validate().is_err());
+        assert!(window("Mon-Fri", "13-20", Some(-5 * 3_600))
+            .validate()
+            .is_ok());
+    }
+
     #[test]
     fn balance_available_check() {
         let mut deps = mock_dependencies();
@@ -277,18 +1077,81 @@ mod conditions_tests {
     }
 
     #[test]
-    fn can_swap_check() {
+    fn balance_ratio_check() {
         let mut deps = mock_dependencies();
         let env = mock_env();
 
-        deps.querier.update_wasm(|_| {
-            SystemResult::Ok(ContractResult::Ok(
-                to_json_binary(&SimulationResponse {
-                    returned: Uint128::new(100),
-                    fee: Uint128::new(1),
-                })
-                .unwrap(),
-            ))
+        let ratio = |direction: Direction, ratio: &str| Condition::BalanceRatio {
+            address: None,
+            numerator_denom: "rune".to_string(),
+            denominator_denom: "x/ruji".to_string(),
+            valuation: Valuation::Units,
+            direction,
+            ratio: Decimal::from_str(ratio).unwrap(),
+        };
+
+        assert!(!ratio(Direction::Above, "0")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!ratio(Direction::Below, "1")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+
+        deps.querier.bank.update_balance(
+            env.contract.address.clone(),
+            vec![Coin::new(300u128, "rune"), Coin::new(200u128, "x/ruji")],
+        );
+
+        assert!(ratio(Direction::Above, "1.4")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!ratio(Direction::Above, "1.5")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(ratio(Direction::Below, "1.6")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!ratio(Direction::Below, "1.5")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+
+        deps.querier.bank.update_balance(
+            env.contract.address.clone(),
+            vec![Coin::new(300u128, "rune")],
+        );
+
+        assert!(ratio(Direction::Above, "1000")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!ratio(Direction::Below, "1000")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+
+        assert!(Condition::BalanceRatio {
+            address: None,
+            numerator_denom: "rune".to_string(),
+            denominator_denom: "rune".to_string(),
+            valuation: Valuation::Units,
+            direction: Direction::Above,
+            ratio: Decimal::one(),
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn can_swap_check() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&SimulationResponse {
+                    returned: Uint128::new(100),
+                    fee: Uint128::new(1),
+                })
+                .unwrap(),
+            ))
         });
 
         assert!(!Condition::CanSwap(Swap {
@@ -424,6 +1287,439 @@ mod conditions_tests {
         .unwrap());
     }
 
+    #[test]
+    fn fin_price_check() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&BookResponse {
+                    base: vec![BookItemResponse {
+                        price: Decimal::from_str("1.45").unwrap(),
+                        total: Uint128::new(1_000_000),
+                    }],
+                    quote: vec![BookItemResponse {
+                        price: Decimal::from_str("1.35").unwrap(),
+                        total: Uint128::new(1_000_000),
+                    }],
+                })
+                .unwrap(),
+            ))
+        });
+
+        let condition = |side: BookSide, direction: Direction, price: &str| Condition::FinPrice {
+            pair_address: Addr::unchecked("pair"),
+            side,
+            direction,
+            price: Decimal::from_str(price).unwrap(),
+        };
+
+        assert!(condition(BookSide::Mid, Direction::Above, "1.39")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!condition(BookSide::Mid, Direction::Above, "1.40")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(condition(BookSide::Ask, Direction::Above, "1.44")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!condition(BookSide::Ask, Direction::Below, "1.45")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(condition(BookSide::Bid, Direction::Below, "1.36")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!condition(BookSide::Bid, Direction::Above, "1.35")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&BookResponse {
+                    base: vec![],
+                    quote: vec![BookItemResponse {
+                        price: Decimal::from_str("1.35").unwrap(),
+                        total: Uint128::new(1_000_000),
+                    }],
+                })
+                .unwrap(),
+            ))
+        });
+
+        assert!(condition(BookSide::Mid, Direction::Above, "1.0")
+            .is_satisfied(deps.as_ref(), &env)
+            .is_err());
+        assert!(condition(BookSide::Bid, Direction::Above, "1.0")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+    }
+
+    #[test]
+    fn fin_book_depth_check() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&BookResponse {
+                    base: vec![
+                        BookItemResponse {
+                            price: Decimal::from_str("1.01").unwrap(),
+                            total: Uint128::new(1_000),
+                        },
+                        BookItemResponse {
+                            price: Decimal::from_str("1.05").unwrap(),
+                            total: Uint128::new(5_000),
+                        },
+                    ],
+                    quote: vec![
+                        BookItemResponse {
+                            price: Decimal::from_str("0.99").unwrap(),
+                            total: Uint128::new(2_000),
+                        },
+                        BookItemResponse {
+                            price: Decimal::from_str("0.90").unwrap(),
+                            total: Uint128::new(9_000),
+                        },
+                    ],
+                })
+                .unwrap(),
+            ))
+        });
+
+        let depth = |side: Side, band_bps: u64, min_depth: u128| Condition::FinBookDepth {
+            pair_address: Addr::unchecked("pair"),
+            side,
+            band_bps,
+            min_depth: Uint128::new(min_depth),
+        };
+
+        // Mid price is 1.00
+        assert!(depth(Side::Base, 100, 1_000)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!depth(Side::Base, 100, 1_001)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(depth(Side::Base, 500, 6_000)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!depth(Side::Quote, 500, 2_001)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(depth(Side::Quote, 1_000, 11_000)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+
+        assert!(depth(Side::Base, 0, 1).validate().is_err());
+        assert!(depth(Side::Base, 10_001, 1).validate().is_err());
+    }
+
+    #[test]
+    fn moving_average_cross_check() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let now = env.block.time;
+
+        let condition = |direction: Direction| Condition::MovingAverageCross {
+            source: PriceSource::Oracle {
+                asset: "btc-btc".to_string(),
+            },
+            fast_window: Duration::from_secs(3_600),
+            slow_window: Duration::from_secs(3 * 3_600),
+            direction,
+            strategy: Some(Addr::unchecked("strategy")),
+        };
+
+        deps.querier.update_wasm(move |_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&vec![PriceSample {
+                    time: now.minus_seconds(3_600),
+                    price: Decimal::one(),
+                }])
+                .unwrap(),
+            ))
+        });
+
+        // History does not yet cover the slow window
+        assert!(!condition(Direction::Above)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!condition(Direction::Below)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+
+        let history = |prices: [u64; 5]| {
+            prices
+                .into_iter()
+                .enumerate()
+                .map(|(i, price)| PriceSample {
+                    time: now.minus_seconds((4 - i as u64) * 3_600),
+                    price: Decimal::percent(price),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // The fast average moves above the slow one on the latest sample
+        let crossing = history([100, 100, 100, 100, 200]);
+
+        deps.querier.update_wasm(move |_| {
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&crossing).unwrap()))
+        });
+
+        assert!(condition(Direction::Above)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!condition(Direction::Below)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+
+        // The fast average was already above the slow one a sample ago
+        let crossed = history([100, 100, 100, 200, 200]);
+
+        deps.querier.update_wasm(move |_| {
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&crossed).unwrap()))
+        });
+
+        assert!(!condition(Direction::Above)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!condition(Direction::Below)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+    }
+
+    #[test]
+    fn price_change_rejects_invalid_windows() {
+        let condition = |window: u64, bps: u64| Condition::PriceChange {
+            asset: "btc-btc".to_string(),
+            window: Duration::from_secs(window),
+            direction: Direction::Above,
+            bps,
+            strategy: None,
+        };
+
+        assert!(condition(0, 100).validate().is_err());
+        assert!(condition(3_600, 0).validate().is_err());
+        assert!(condition(
+            (PRICE_HISTORY_CAPACITY - 1) as u64 * PRICE_SAMPLE_INTERVAL_SECONDS,
+            100
+        )
+        .validate()
+        .is_ok());
+        assert!(condition(
+            (PRICE_HISTORY_CAPACITY - 1) as u64 * PRICE_SAMPLE_INTERVAL_SECONDS + 1,
+            100
+        )
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn moving_average_cross_rejects_invalid_windows() {
+        let condition = |fast: u64, slow: u64| Condition::MovingAverageCross {
+            source: PriceSource::Oracle {
+                asset: "btc-btc".to_string(),
+            },
+            fast_window: Duration::from_secs(fast),
+            slow_window: Duration::from_secs(slow),
+            direction: Direction::Above,
+            strategy: None,
+        };
+
+        assert!(condition(0, 3_600).validate().is_err());
+        assert!(condition(3_600, 3_600).validate().is_err());
+        assert!(condition(
+            3_600,
+            (PRICE_HISTORY_CAPACITY - 2) as u64 * PRICE_SAMPLE_INTERVAL_SECONDS
+        )
+        .validate()
+        .is_ok());
+        assert!(condition(
+            3_600,
+            (PRICE_HISTORY_CAPACITY - 2) as u64 * PRICE_SAMPLE_INTERVAL_SECONDS + 1
+        )
+        .validate()
+        .is_err());
+        assert!(condition(3_600, 7_200).validate().is_ok());
+    }
+
+    #[test]
+    fn statistics_conditions_check() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let recipient = Recipient::Bank {
+            address: Addr::unchecked("recipient"),
+        };
+
+        let total_debited = |amount: u128| Condition::TotalDebited {
+            amount: Coin::new(amount, "rune"),
+            strategy: None,
+        };
+
+        let total_credited = |amount: u128| Condition::TotalCredited {
+            recipient: recipient.clone(),
+            amount: Coin::new(amount, "rune"),
+            strategy: None,
+        };
+
+        let execution_count = |min: Option<u64>, max: Option<u64>| Condition::ExecutionCount {
+            min,
+            max,
+            strategy: None,
+        };
+
+        assert!(total_debited(0).is_satisfied(deps.as_ref(), &env).unwrap());
+        assert!(!total_debited(1).is_satisfied(deps.as_ref(), &env).unwrap());
+
+        STATISTICS
+            .save(
+                deps.as_mut().storage,
+                &Statistics {
+                    debited: vec![Coin::new(100u128, "rune")],
+                    credited: vec![(recipient.clone(), vec![Coin::new(50u128, "rune")])],
+                    executions: 3,
+                    ..Statistics::default()
+                },
+            )
+            .unwrap();
+
+        assert!(total_debited(100)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!total_debited(101)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+
+        assert!(total_credited(50)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!total_credited(51)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!Condition::TotalCredited {
+            recipient: Recipient::Bank {
+                address: Addr::unchecked("other"),
+            },
+            amount: Coin::new(1u128, "rune"),
+            strategy: None,
+        }
+        .is_satisfied(deps.as_ref(), &env)
+        .unwrap());
+
+        assert!(execution_count(Some(3), None)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!execution_count(Some(4), None)
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(execution_count(None, Some(3))
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!execution_count(None, Some(2))
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(execution_count(Some(4), Some(2)).validate().is_err());
+    }
+
+    #[test]
+    fn owner_inactive_check() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let inactive = |period: u64| Condition::OwnerInactive {
+            period: Duration::from_secs(period),
+            strategy: None,
+        };
+
+        assert!(inactive(60).is_satisfied(deps.as_ref(), &env).is_err());
+
+        HEARTBEAT
+            .save(deps.as_mut().storage, &env.block.time.minus_seconds(60))
+            .unwrap();
+
+        assert!(inactive(59).is_satisfied(deps.as_ref(), &env).unwrap());
+        assert!(!inactive(60).is_satisfied(deps.as_ref(), &env).unwrap());
+        assert!(inactive(0).validate().is_err());
+    }
+
+    #[test]
+    fn query_condition_check() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(Binary::from(
+                br#"{"vault":{"share_price":"1.05","paused":false}}"#,
+            )))
+        });
+
+        let query = |path: &str, comparator: Comparator, value: &str| Condition::Query {
+            contract: Addr::unchecked("vault"),
+            msg: r#"{"vault":{}}"#.to_string(),
+            path: path.to_string(),
+            comparator,
+            value: value.to_string(),
+            max_response_bytes: None,
+        };
+
+        assert!(query("/vault/share_price", Comparator::GreaterThan, "1")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!query("/vault/share_price", Comparator::LessThan, "1.05")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(query("/vault/paused", Comparator::Equal, "false")
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(query("/vault/missing", Comparator::Equal, "1")
+            .is_satisfied(deps.as_ref(), &env)
+            .is_err());
+        assert!(query("/vault", Comparator::Equal, "1")
+            .is_satisfied(deps.as_ref(), &env)
+            .is_err());
+
+        assert!(query("vault", Comparator::Equal, "1").validate().is_err());
+        assert!(Condition::Query {
+            contract: Addr::unchecked("vault"),
+            msg: "{not json".to_string(),
+            path: "".to_string(),
+            comparator: Comparator::Equal,
+            value: "1".to_string(),
+            max_response_bytes: None,
+        }
+        .validate()
+        .is_err());
+
+        let limited = |max_response_bytes: usize| Condition::Query {
+            contract: Addr::unchecked("vault"),
+            msg: r#"{"vault":{}}"#.to_string(),
+            path: "/vault/share_price".to_string(),
+            comparator: Comparator::GreaterThan,
+            value: "1".to_string(),
+            max_response_bytes: Some(max_response_bytes),
+        };
+
+        assert!(limited(64).is_satisfied(deps.as_ref(), &env).unwrap());
+        assert!(limited(16).is_satisfied(deps.as_ref(), &env).is_err());
+        assert!(limited(0).validate().is_err());
+        assert!(limited(MAX_QUERY_RESPONSE_BYTES + 1).validate().is_err());
+
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(Binary::new(vec![
+                b' ';
+                MAX_QUERY_RESPONSE_BYTES
+                    + 1
+            ])))
+        });
+
+        assert!(query("", Comparator::Equal, "1")
+            .is_satisfied(deps.as_ref(), &env)
+            .is_err());
+    }
+
     #[test]
     fn not_satisfied_check() {
         let deps = mock_dependencies();
@@ -441,6 +1737,139 @@ mod conditions_tests {
         );
     }
 
+    #[test]
+    fn evaluate_applies_error_policy() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+
+        let failing = Condition::Query {
+            contract: Addr::unchecked("missing"),
+            msg: "{}".to_string(),
+            path: "/value".to_string(),
+            comparator: Comparator::Equal,
+            value: "1".to_string(),
+            max_response_bytes: None,
+        };
+
+        let (satisfied, event) = failing
+            .evaluate(deps.as_ref(), &env, &ErrorPolicy::TreatAsFalse)
+            .unwrap();
+
+        assert!(!satisfied);
+        assert!(event
+            .unwrap()
+            .attributes
+            .iter()
+            .any(|attribute| attribute.key == "policy" && attribute.value == "TreatAsFalse"));
+
+        let (satisfied, event) = failing
+            .evaluate(deps.as_ref(), &env, &ErrorPolicy::TreatAsTrue)
+            .unwrap();
+
+        assert!(satisfied);
+        assert!(event.is_some());
+
+        assert!(failing
+            .evaluate(deps.as_ref(), &env, &ErrorPolicy::Fail)
+            .is_err());
+
+        assert_eq!(
+            Condition::BlocksCompleted(env.block.height - 1)
+                .evaluate(deps.as_ref(), &env, &ErrorPolicy::Fail)
+                .unwrap(),
+            (true, None)
+        );
+    }
+
+    #[test]
+    fn trace_queries_each_node_once() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let queries = Rc::new(Cell::new(0));
+        let counter = queries.clone();
+
+        deps.querier.update_wasm(move |_| {
+            counter.set(counter.get() + 1);
+            SystemResult::Ok(ContractResult::Ok(Binary::from(br#"{"value":"2"}"#)))
+        });
+
+        let query = |value: &str| Condition::Query {
+            contract: Addr::unchecked("contract"),
+            msg: "{}".to_string(),
+            path: "/value".to_string(),
+            comparator: Comparator::GreaterThan,
+            value: value.to_string(),
+            max_response_bytes: None,
+        };
+
+        let trace = Condition::Not(Box::new(Condition::Composite(CompositeCondition {
+            conditions: vec![query("1"), query("3")],
+            threshold: Threshold::Any,
+        })))
+        .trace(deps.as_ref(), &env);
+
+        assert_eq!(queries.get(), 2);
+        assert!(!trace.satisfied);
+        assert_eq!(trace.error, None);
+        assert!(trace.children[0].satisfied);
+        assert!(trace.children[0].children[0].satisfied);
+        assert!(!trace.children[0].children[1].satisfied);
+        assert_eq!(
+            trace.children[0].children[1].observed,
+            vec![("value".to_string(), "2".to_string())]
+        );
+    }
+
+    #[test]
+    fn trace_reports_each_node() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+
+        let trace = Condition::Composite(CompositeCondition {
+            conditions: vec![
+                Condition::BlocksCompleted(env.block.height - 1),
+                Condition::Query {
+                    contract: Addr::unchecked("missing"),
+                    msg: "{}".to_string(),
+                    path: "/value".to_string(),
+                    comparator: Comparator::Equal,
+                    value: "1".to_string(),
+                    max_response_bytes: None,
+                },
+                Condition::Not(Box::new(Condition::TimestampElapsed(env.block.time))),
+            ],
+            threshold: Threshold::All,
+        })
+        .trace(deps.as_ref(), &env);
+
+        assert!(!trace.satisfied);
+        assert!(trace.error.is_some());
+        assert_eq!(trace.children.len(), 3);
+
+        assert!(trace.children[0].satisfied);
+        assert_eq!(trace.children[0].error, None);
+        assert_eq!(
+            trace.children[0].observed,
+            vec![("block_height".to_string(), env.block.height.to_string())]
+        );
+
+        assert!(!trace.children[1].satisfied);
+        assert!(trace.children[1].error.is_some());
+        assert!(trace.children[1].observed.is_empty());
+
+        assert!(trace.children[2].satisfied);
+        assert_eq!(trace.children[2].children.len(), 1);
+        assert!(!trace.children[2].children[0].satisfied);
+        assert_eq!(
+            trace.children[2].children[0].observed,
+            vec![(
+                "block_time".to_string(),
+                env.block.time.seconds().to_string()
+            )]
+        );
+    }
+
     #[test]
     fn composite_condition_check() {
         let deps = mock_dependencies();
@@ -466,4 +1895,161 @@ mod conditions_tests {
         .is_satisfied(deps.as_ref(), &env)
         .unwrap());
     }
+
+    #[test]
+    fn threshold_composite_condition_check() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+
+        let composite = |threshold: Threshold| {
+            Condition::Composite(CompositeCondition {
+                conditions: vec![
+                    Condition::BlocksCompleted(env.block.height - 1),
+                    Condition::BlocksCompleted(env.block.height),
+                    Condition::TimestampElapsed(env.block.time.minus_seconds(1)),
+                ],
+                threshold,
+            })
+        };
+
+        assert!(composite(Threshold::AtLeast(1))
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(composite(Threshold::AtLeast(2))
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!composite(Threshold::AtLeast(3))
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+
+        assert!(!composite(Threshold::Exactly(0))
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!composite(Threshold::Exactly(1))
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(composite(Threshold::Exactly(2))
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+        assert!(!composite(Threshold::Exactly(3))
+            .is_satisfied(deps.as_ref(), &env)
+            .unwrap());
+    }
+
+    #[test]
+    fn composite_condition_short_circuits() {
+        let deps = mock_dependencies();
+        let env = mock_env();
+
+        // Querying an order on a pair that doesn't exist errors
+        let failing = Condition::LimitOrderFilled {
+            owner: Addr::unchecked("owner"),
+            pair_address: Addr::unchecked("pair"),
+            side: Side::Base,
+            price: Decimal::one(),
+        };
+
+        assert!(failing.is_satisfied(deps.as_ref(), &env).is_err());
+
+        let composite = |threshold: Threshold, first: Condition| {
+            Condition::Composite(CompositeCondition {
+                conditions: vec![first, failing.clone()],
+                threshold,
+            })
+        };
+
+        assert!(composite(
+            Threshold::Any,
+            Condition::BlocksCompleted(env.block.height - 1)
+        )
+        .is_satisfied(deps.as_ref(), &env)
+        .unwrap());
+        assert!(
+            !composite(Threshold::All, Condition::BlocksCompleted(env.block.height))
+                .is_satisfied(deps.as_ref(), &env)
+                .unwrap()
+        );
+        assert!(composite(
+            Threshold::AtLeast(1),
+            Condition::BlocksCompleted(env.block.height - 1)
+        )
+        .is_satisfied(deps.as_ref(), &env)
+        .unwrap());
+        assert!(composite(
+            Threshold::AtLeast(2),
+            Condition::BlocksCompleted(env.block.height - 1)
+        )
+        .is_satisfied(deps.as_ref(), &env)
+        .is_err());
+    }
+
+    #[test]
+    fn composite_condition_validates_threshold() {
+        let composite = |threshold: Threshold, count: u64| {
+            Condition::Composite(CompositeCondition {
+                conditions: (0..count).map(Condition::BlocksCompleted).collect(),
+                threshold,
+            })
+        };
+
+        assert!(composite(Threshold::All, 0).validate().is_err());
+        assert!(composite(Threshold::AtLeast(0), 2).validate().is_err());
+        assert!(composite(Threshold::AtLeast(3), 2).validate().is_err());
+        assert!(composite(Threshold::Exactly(3), 2).validate().is_err());
+        assert!(composite(Threshold::AtLeast(2), 2).validate().is_ok());
+        assert!(composite(Threshold::Exactly(0), 2).validate().is_ok());
+
+        assert!(
+            Condition::Not(Box::new(composite(Threshold::AtLeast(3), 2)))
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn for_strategy_names_strategy_in_local_conditions() {
+        let strategy = Addr::unchecked("strategy");
+        let other = Addr::unchecked("other");
+
+        assert_eq!(
+            Condition::Composite(CompositeCondition {
+                conditions: vec![
+                    Condition::StrategyBalanceAvailable {
+                        amount: Coin::new(100u128, "rune"),
+                    },
+                    Condition::Not(Box::new(Condition::ExecutionCount {
+                        min: Some(3),
+                        max: None,
+                        strategy: None,
+                    })),
+                    Condition::TotalDebited {
+                        amount: Coin::new(100u128, "rune"),
+                        strategy: Some(other.clone()),
+                    },
+                    Condition::BlocksCompleted(10),
+                ],
+                threshold: Threshold::All,
+            })
+            .for_strategy(&strategy),
+            Condition::Composite(CompositeCondition {
+                conditions: vec![
+                    Condition::BalanceAvailable {
+                        address: strategy.clone(),
+                        amount: Coin::new(100u128, "rune"),
+                    },
+                    Condition::Not(Box::new(Condition::ExecutionCount {
+                        min: Some(3),
+                        max: None,
+                        strategy: Some(strategy.clone()),
+                    })),
+                    Condition::TotalDebited {
+                        amount: Coin::new(100u128, "rune"),
+                        strategy: Some(other),
+                    },
+                    Condition::BlocksCompleted(10),
+                ],
+                threshold: Threshold::All,
+            })
+        );
+    }
 }
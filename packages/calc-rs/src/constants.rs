@@ -37,20 +37,64 @@ pub const MAX_TOTAL_AFFILIATE_BPS: u64 = 200;
  * - ThorSwap: 4
  * - OptimalSwap: number of routes * 4
  * - LimitOrder: 4
- * - Schedule: size of scheduled action + 1
+ * - MarketMaker: 8
+ * - Grid: 6
+ * - Rebalance: number of routes * 4 + number of targets + 1
+ * - Basket: number of routes across all legs * 2 + 1
+ * - Liquidity: 2
+ * - ProtectiveExit: number of routes * 4 + number of take profits + 2
+ * - Call: number of funds templates + 2
+ * - Schedule: size of scheduled action + 1 (+ size of condition for condition cadences)
  * - Conditional: size of action + size of condition + 1
  * - Many: sum of sizes of actions + 1
+ * - Ref: 1 (each strategy definition is counted once, however often it is referenced)
  *
  * Condition sizes:
  * - Timestamp elapsed: 1
  * - Blocks completed: 1
+ * - Time window: 1
  * - Can swap: 2
  * - Limit order filled: 2
  * - Balance available: 1
  * - Strategy balance available: 1
+ * - Balance ratio: 2
  * - Strategy in status: 2
  * - Oracle price: 2
+ * - FIN price: 2
+ * - Thorchain trading available: 2
+ * - Pool depth: 2
+ * - FIN book depth: 2
+ * - Price change: 2
+ * - Moving average cross: 2
+ * - Total debited: 1
+ * - Total credited: 1
+ * - Execution count: 1
+ * - Owner inactive: 1
+ * - Query: 2
  * - Not: size of condition
  * - Composite: sum of sizes of conditions + 1
  */
 pub const MAX_STRATEGY_SIZE: usize = 35;
+
+/**
+ * Price history sampled by strategies for price history conditions.
+ * Samples are taken at most once per interval, so the ring buffer
+ * covers at least a week of prices before overwriting the oldest.
+ */
+pub const PRICE_HISTORY_CAPACITY: usize = 168;
+pub const PRICE_SAMPLE_INTERVAL_SECONDS: u64 = 3_600;
+
+/**
+ * Limits on query conditions, which run arbitrary smart queries on
+ * every evaluation. Oversized responses are rejected before parsing
+ * so a third party contract can't make evaluation prohibitively costly.
+ */
+pub const MAX_QUERY_MSG_BYTES: usize = 1_024;
+pub const MAX_QUERY_RESPONSE_BYTES: usize = 16_384;
+
+/**
+ * Assumed block time when spreading block height triggers over a
+ * jitter window, which is given as a duration. Thorchain targets
+ * roughly six second blocks.
+ */
+pub const JITTER_SECONDS_PER_BLOCK: u64 = 6;
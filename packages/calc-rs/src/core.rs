@@ -61,4 +61,48 @@ impl Contract {
 pub enum Threshold {
     All,
     Any,
+    AtLeast(u8),
+    Exactly(u8),
+}
+
+impl Threshold {
+    /// The minimum and maximum number of `count` items that may be satisfied.
+    pub fn bounds(&self, count: usize) -> (usize, usize) {
+        match self {
+            Threshold::All => (count, count),
+            Threshold::Any => (1, count),
+            Threshold::AtLeast(n) => (*n as usize, count),
+            Threshold::Exactly(n) => (*n as usize, *n as usize),
+        }
+    }
+
+    /// Whether the number of satisfied `results` falls within bounds.
+    /// Stops pulling results as soon as the remaining ones can no longer
+    /// change the outcome, so errors past that point are never seen.
+    pub fn outcome<E>(
+        &self,
+        results: impl ExactSizeIterator<Item = Result<bool, E>>,
+    ) -> Result<bool, E> {
+        let count = results.len();
+        let (min, max) = self.bounds(count);
+        let mut satisfied = 0;
+
+        for (i, result) in results.enumerate() {
+            if result? {
+                satisfied += 1;
+            }
+
+            let remaining = count - i - 1;
+
+            if satisfied > max || satisfied + remaining < min {
+                return Ok(false);
+            }
+
+            if satisfied >= min && satisfied + remaining <= max {
+                return Ok(true);
+            }
+        }
+
+        Ok(satisfied >= min && satisfied <= max)
+    }
 }
@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_string, Addr, Coin, Event};
+use cosmwasm_std::{to_json_string, Addr, Coin, Event, Timestamp};
 use rujira_rs::fin::{Price, Side};
 
 use crate::{statistics::Statistics, strategy::StrategyConfig};
@@ -23,6 +23,11 @@ pub enum DomainEvent {
         to: Addr,
         funds: Vec<Coin>,
     },
+    HeartbeatRecorded {
+        contract_address: Addr,
+        owner: Addr,
+        time: Timestamp,
+    },
     ExecutionAttempted {
         contract_address: Addr,
         pair_address: Addr,
@@ -96,6 +101,14 @@ impl From<DomainEvent> for Event {
                     "funds",
                     to_json_string(&funds).expect("Failed to serialize withdrawn funds"),
                 ),
+            DomainEvent::HeartbeatRecorded {
+                contract_address,
+                owner,
+                time,
+            } => Event::new("heartbeat_recorded")
+                .add_attribute("contract_address", contract_address.as_str())
+                .add_attribute("owner", owner.as_str())
+                .add_attribute("time", time.seconds().to_string()),
             DomainEvent::ExecutionAttempted {
                 contract_address,
                 pair_address,
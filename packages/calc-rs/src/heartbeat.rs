@@ -0,0 +1,23 @@
+use cosmwasm_std::{Addr, Deps, Env, StdResult, Storage, Timestamp};
+use cw_storage_plus::Item;
+
+use crate::strategy::StrategyQueryMsg;
+
+/// The last time the strategy owner proved they are still around,
+/// either by creating the strategy or by sending a heartbeat.
+pub const HEARTBEAT: Item<Timestamp> = Item::new("heartbeat");
+
+pub fn record_heartbeat(storage: &mut dyn Storage, env: &Env) -> StdResult<()> {
+    HEARTBEAT.save(storage, &env.block.time)
+}
+
+/// Loads the last heartbeat of `strategy`, or of the current
+/// contract when it is the strategy being evaluated.
+pub fn load_heartbeat(deps: Deps, env: &Env, strategy: &Option<Addr>) -> StdResult<Timestamp> {
+    match strategy {
+        Some(strategy) if *strategy != env.contract.address => deps
+            .querier
+            .query_wasm_smart(strategy, &StrategyQueryMsg::Heartbeat {}),
+        _ => HEARTBEAT.load(deps.storage),
+    }
+}
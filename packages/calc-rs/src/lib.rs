@@ -4,7 +4,11 @@ pub mod conditions;
 pub mod constants;
 pub mod core;
 pub mod events;
+pub mod heartbeat;
 pub mod manager;
+pub mod price;
+pub mod price_history;
+pub mod query;
 pub mod scheduler;
 pub mod statistics;
 pub mod strategy;
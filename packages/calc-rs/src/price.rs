@@ -0,0 +1,149 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Decimal, Deps, StdError, StdResult, Uint128};
+use rujira_rs::{
+    fin::{BookResponse, QueryMsg, Side},
+    query::Pool,
+    Layer1Asset,
+};
+
+#[cw_serde]
+pub enum PriceSource {
+    Oracle { asset: String },
+    Fin { pair_address: Addr },
+}
+
+impl PriceSource {
+    pub fn price(&self, deps: Deps) -> StdResult<Decimal> {
+        match self {
+            PriceSource::Oracle { asset } => oracle_price(deps, asset),
+            PriceSource::Fin { pair_address } => fin_mid_price(deps, pair_address),
+        }
+    }
+
+    pub fn key(&self) -> String {
+        match self {
+            PriceSource::Oracle { asset } => format!("oracle:{asset}"),
+            PriceSource::Fin { pair_address } => format!("fin:{pair_address}"),
+        }
+    }
+}
+
+pub fn oracle_price(deps: Deps, asset: &str) -> StdResult<Decimal> {
+    let layer_1_asset = Layer1Asset::from_native(asset.to_string()).map_err(|e| {
+        StdError::generic_err(format!("Denom ({asset}) not a secured asset, error: {e}"))
+    })?;
+
+    Ok(Pool::load(deps.querier, &layer_1_asset)
+        .map_err(|e| {
+            StdError::generic_err(format!(
+                "Failed to load oracle price for {asset}, error: {e}"
+            ))
+        })?
+        .asset_tor_price)
+}
+
+#[cw_serde]
+pub enum BookSide {
+    Mid,
+    Bid,
+    Ask,
+}
+
+/// How balances are valued when comparing them against each other.
+#[cw_serde]
+pub enum Valuation {
+    /// Raw balances, e.g. for denoms pegged to the same asset
+    Units,
+    /// Balances at their Thorchain oracle price, for secured assets only
+    Oracle,
+}
+
+impl Valuation {
+    pub fn value(&self, deps: Deps, balance: &Coin) -> StdResult<Uint128> {
+        match self {
+            Valuation::Units => Ok(balance.amount),
+            Valuation::Oracle => balance
+                .amount
+                .checked_mul_floor(oracle_price(deps, &balance.denom)?)
+                .map_err(|e| {
+                    StdError::generic_err(format!("Failed to value {}: {e}", balance.denom))
+                }),
+        }
+    }
+}
+
+pub fn fin_mid_price(deps: Deps, pair_address: &Addr) -> StdResult<Decimal> {
+    fin_price(deps, pair_address, &BookSide::Mid)
+}
+
+/// Reads the top of a FIN pair's order book. Asks sit on the base
+/// side of the book and bids on the quote side.
+pub fn fin_price(deps: Deps, pair_address: &Addr, side: &BookSide) -> StdResult<Decimal> {
+    let book = deps.querier.query_wasm_smart::<BookResponse>(
+        pair_address,
+        &QueryMsg::Book {
+            limit: Some(1),
+            offset: None,
+        },
+    )?;
+
+    let ask = book.base.first().map(|ask| ask.price);
+    let bid = book.quote.first().map(|bid| bid.price);
+
+    match (side, ask, bid) {
+        (BookSide::Mid, Some(ask), Some(bid)) => {
+            Ok((ask + bid) / Decimal::from_ratio(2u128, 1u128))
+        }
+        (BookSide::Ask, Some(ask), _) => Ok(ask),
+        (BookSide::Bid, _, Some(bid)) => Ok(bid),
+        _ => Err(StdError::generic_err(format!(
+            "Order book for pair {pair_address} does not have liquidity for {side:?} price"
+        ))),
+    }
+}
+
+/// Sums the liquidity resting on one `side` of a FIN pair's book at
+/// prices within `band_bps` of the mid price. Base side levels are
+/// denominated in the base denom and quote side levels in the quote denom.
+pub fn fin_book_depth(
+    deps: Deps,
+    pair_address: &Addr,
+    side: &Side,
+    band_bps: u64,
+) -> StdResult<Uint128> {
+    let book = deps.querier.query_wasm_smart::<BookResponse>(
+        pair_address,
+        &QueryMsg::Book {
+            // Levels beyond the first 50 on each side are not counted
+            limit: Some(50),
+            offset: None,
+        },
+    )?;
+
+    let (ask, bid) = match (book.base.first(), book.quote.first()) {
+        (Some(ask), Some(bid)) => (ask.price, bid.price),
+        _ => {
+            return Err(StdError::generic_err(format!(
+                "Order book for pair {pair_address} does not have liquidity on both sides"
+            )))
+        }
+    };
+
+    let mid = (ask + bid) / Decimal::from_ratio(2u128, 1u128);
+    let band = mid * Decimal::bps(band_bps);
+
+    Ok(match side {
+        Side::Base => book
+            .base
+            .iter()
+            .filter(|level| level.price <= mid + band)
+            .map(|level| level.total)
+            .sum(),
+        Side::Quote => book
+            .quote
+            .iter()
+            .filter(|level| level.price >= mid.saturating_sub(band))
+            .map(|level| level.total)
+            .sum(),
+    })
+}
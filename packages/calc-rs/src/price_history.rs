@@ -0,0 +1,255 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Deps, DepsMut, Env, StdResult, Timestamp};
+use cw_storage_plus::Map;
+
+use crate::{
+    constants::{PRICE_HISTORY_CAPACITY, PRICE_SAMPLE_INTERVAL_SECONDS},
+    price::PriceSource,
+    strategy::StrategyQueryMsg,
+};
+
+const PRICE_HISTORY: Map<String, PriceHistory> = Map::new("price_history");
+
+#[cw_serde]
+pub struct PriceSample {
+    pub time: Timestamp,
+    pub price: Decimal,
+}
+
+/// Fixed capacity ring buffer of price samples. Once full, `head`
+/// points at the oldest sample, which is the next to be overwritten.
+#[cw_serde]
+#[derive(Default)]
+pub struct PriceHistory {
+    samples: Vec<(u64, Decimal)>,
+    head: usize,
+}
+
+impl PriceHistory {
+    fn latest(&self) -> Option<&(u64, Decimal)> {
+        if self.samples.len() < PRICE_HISTORY_CAPACITY {
+            self.samples.last()
+        } else {
+            self.samples
+                .get((self.head + PRICE_HISTORY_CAPACITY - 1) % PRICE_HISTORY_CAPACITY)
+        }
+    }
+
+    pub fn record(&mut self, time: Timestamp, price: Decimal) {
+        if let Some((latest, _)) = self.latest() {
+            if time.seconds() < latest + PRICE_SAMPLE_INTERVAL_SECONDS {
+                return;
+            }
+        }
+
+        if self.samples.len() < PRICE_HISTORY_CAPACITY {
+            self.samples.push((time.seconds(), price));
+        } else {
+            self.samples[self.head] = (time.seconds(), price);
+            self.head = (self.head + 1) % PRICE_HISTORY_CAPACITY;
+        }
+    }
+
+    /// Samples in chronological order.
+    pub fn series(&self) -> Vec<PriceSample> {
+        self.samples[self.head..]
+            .iter()
+            .chain(self.samples[..self.head].iter())
+            .map(|(time, price)| PriceSample {
+                time: Timestamp::from_seconds(*time),
+                price: *price,
+            })
+            .collect()
+    }
+}
+
+/// The most recent price recorded at or before `time`.
+pub fn price_at(series: &[PriceSample], time: Timestamp) -> Option<Decimal> {
+    series
+        .iter()
+        .rev()
+        .find(|sample| sample.time <= time)
+        .map(|sample| sample.price)
+}
+
+/// The mean of the prices recorded at or after `since`, provided
+/// the series reaches back far enough to cover the whole period.
+pub fn average_price(series: &[PriceSample], since: Timestamp) -> Option<Decimal> {
+    if series.first()?.time > since {
+        return None;
+    }
+
+    let prices = series
+        .iter()
+        .filter(|sample| sample.time >= since)
+        .map(|sample| sample.price)
+        .collect::<Vec<_>>();
+
+    if prices.is_empty() {
+        return None;
+    }
+
+    prices
+        .iter()
+        .try_fold(Decimal::zero(), |total, price| total.checked_add(*price))
+        .ok()?
+        .checked_div(Decimal::from_ratio(prices.len() as u128, 1u128))
+        .ok()
+}
+
+pub fn load_price_history(deps: Deps, source: &PriceSource) -> StdResult<PriceHistory> {
+    Ok(PRICE_HISTORY
+        .may_load(deps.storage, source.key())?
+        .unwrap_or_default())
+}
+
+/// Loads the sampled price series for `source`. Strategies read their own
+/// storage, while anything else (e.g. the scheduler evaluating a trigger)
+/// has to name the strategy that records the history.
+pub fn load_price_series(
+    deps: Deps,
+    env: &Env,
+    strategy: &Option<Addr>,
+    source: &PriceSource,
+) -> StdResult<Vec<PriceSample>> {
+    match strategy {
+        Some(strategy) if *strategy != env.contract.address => deps.querier.query_wasm_smart(
+            strategy,
+            &StrategyQueryMsg::PriceHistory {
+                source: source.clone(),
+            },
+        ),
+        _ => Ok(load_price_history(deps, source)?.series()),
+    }
+}
+
+/// Samples the current price of each source into strategy storage. A source
+/// that cannot be priced right now is skipped rather than failing execution.
+pub fn record_prices(deps: DepsMut, env: &Env, sources: Vec<PriceSource>) -> StdResult<()> {
+    for source in sources {
+        if let Ok(price) = source.price(deps.as_ref()) {
+            let mut history = load_price_history(deps.as_ref(), &source)?;
+            history.record(env.block.time, price);
+            PRICE_HISTORY.save(deps.storage, source.key(), &history)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod price_history_tests {
+    use super::*;
+
+    #[test]
+    fn records_samples_no_closer_than_the_sample_interval() {
+        let mut history = PriceHistory::default();
+        let start = Timestamp::from_seconds(1_000_000);
+
+        history.record(start, Decimal::one());
+        history.record(start.plus_seconds(1), Decimal::percent(200));
+        history.record(
+            start.plus_seconds(PRICE_SAMPLE_INTERVAL_SECONDS),
+            Decimal::percent(300),
+        );
+
+        assert_eq!(
+            history.series(),
+            vec![
+                PriceSample {
+                    time: start,
+                    price: Decimal::one(),
+                },
+                PriceSample {
+                    time: start.plus_seconds(PRICE_SAMPLE_INTERVAL_SECONDS),
+                    price: Decimal::percent(300),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn overwrites_oldest_samples_once_full() {
+        let mut history = PriceHistory::default();
+        let start = Timestamp::from_seconds(1_000_000);
+        let total = PRICE_HISTORY_CAPACITY + 3;
+
+        for i in 0..total {
+            history.record(
+                start.plus_seconds(i as u64 * PRICE_SAMPLE_INTERVAL_SECONDS),
+                Decimal::from_ratio(i as u128, 1u128),
+            );
+        }
+
+        let series = history.series();
+
+        assert_eq!(series.len(), PRICE_HISTORY_CAPACITY);
+        assert_eq!(
+            series.first().unwrap().price,
+            Decimal::from_ratio(3u128, 1u128)
+        );
+        assert_eq!(
+            series.last().unwrap().price,
+            Decimal::from_ratio(total as u128 - 1, 1u128)
+        );
+        assert!(series.windows(2).all(|pair| pair[0].time < pair[1].time));
+    }
+
+    #[test]
+    fn finds_latest_price_at_or_before_time() {
+        let series = vec![
+            PriceSample {
+                time: Timestamp::from_seconds(100),
+                price: Decimal::one(),
+            },
+            PriceSample {
+                time: Timestamp::from_seconds(200),
+                price: Decimal::percent(200),
+            },
+        ];
+
+        assert_eq!(price_at(&series, Timestamp::from_seconds(99)), None);
+        assert_eq!(
+            price_at(&series, Timestamp::from_seconds(100)),
+            Some(Decimal::one())
+        );
+        assert_eq!(
+            price_at(&series, Timestamp::from_seconds(199)),
+            Some(Decimal::one())
+        );
+        assert_eq!(
+            price_at(&series, Timestamp::from_seconds(250)),
+            Some(Decimal::percent(200))
+        );
+    }
+
+    #[test]
+    fn averages_prices_over_covered_period() {
+        let series = vec![
+            PriceSample {
+                time: Timestamp::from_seconds(100),
+                price: Decimal::one(),
+            },
+            PriceSample {
+                time: Timestamp::from_seconds(200),
+                price: Decimal::percent(200),
+            },
+            PriceSample {
+                time: Timestamp::from_seconds(300),
+                price: Decimal::percent(600),
+            },
+        ];
+
+        assert_eq!(average_price(&series, Timestamp::from_seconds(99)), None);
+        assert_eq!(
+            average_price(&series, Timestamp::from_seconds(100)),
+            Some(Decimal::percent(300))
+        );
+        assert_eq!(
+            average_price(&series, Timestamp::from_seconds(150)),
+            Some(Decimal::percent(400))
+        );
+        assert_eq!(average_price(&series, Timestamp::from_seconds(301)), None);
+        assert_eq!(average_price(&[], Timestamp::from_seconds(0)), None);
+    }
+}
@@ -0,0 +1,283 @@
+use std::{cmp::Ordering, collections::BTreeMap, fmt, str::FromStr};
+
+use cosmwasm_schema::{
+    cw_serde,
+    serde::{
+        de::{IgnoredAny, MapAccess, SeqAccess, Visitor},
+        Deserialize, Deserializer,
+    },
+};
+use cosmwasm_std::{
+    from_json, to_json_vec, Addr, ContractResult, Deps, Empty, QueryRequest, SignedDecimal256,
+    StdError, StdResult, SystemResult, WasmQuery,
+};
+
+use crate::constants::{MAX_QUERY_MSG_BYTES, MAX_QUERY_RESPONSE_BYTES};
+
+#[cw_serde]
+pub enum Comparator {
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+}
+
+impl Comparator {
+    fn matches(&self, ordering: Ordering) -> bool {
+        match self {
+            Comparator::Equal => ordering == Ordering::Equal,
+            Comparator::NotEqual => ordering != Ordering::Equal,
+            Comparator::GreaterThan => ordering == Ordering::Greater,
+            Comparator::GreaterThanOrEqual => ordering != Ordering::Less,
+            Comparator::LessThan => ordering == Ordering::Less,
+            Comparator::LessThanOrEqual => ordering != Ordering::Greater,
+        }
+    }
+
+    /// Compares numerically when both sides parse as (possibly negative)
+    /// decimals, and as strings otherwise.
+    pub fn compare(&self, actual: &str, expected: &str) -> bool {
+        let ordering = match (
+            SignedDecimal256::from_str(actual),
+            SignedDecimal256::from_str(expected),
+        ) {
+            (Ok(actual), Ok(expected)) => actual.cmp(&expected),
+            _ => actual.cmp(expected),
+        };
+
+        self.matches(ordering)
+    }
+}
+
+/// Just enough of a JSON value to follow a pointer into a query
+/// response. Contracts can't use floats, so numbers are integers.
+enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(BTreeMap<String, JsonValue>),
+}
+
+struct JsonValueVisitor;
+
+impl<'de> Visitor<'de> for JsonValueVisitor {
+    type Value = JsonValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<JsonValue, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<JsonValue, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<JsonValue, E> {
+        Ok(JsonValue::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Number(v.to_string()))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Number(v.to_string()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<JsonValue, E> {
+        Ok(JsonValue::String(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<JsonValue, E> {
+        Ok(JsonValue::String(v))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<JsonValue, A::Error> {
+        let mut values = vec![];
+
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+
+        Ok(JsonValue::Array(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<JsonValue, A::Error> {
+        let mut values = BTreeMap::new();
+
+        while let Some((key, value)) = map.next_entry()? {
+            values.insert(key, value);
+        }
+
+        Ok(JsonValue::Object(values))
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(JsonValueVisitor)
+    }
+}
+
+impl JsonValue {
+    /// Resolves an RFC 6901 JSON pointer, e.g. `/config/share_price`.
+    fn pointer(&self, path: &str) -> Option<&JsonValue> {
+        if path.is_empty() {
+            return Some(self);
+        }
+
+        path.strip_prefix('/')?
+            .split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .try_fold(self, |value, token| match value {
+                JsonValue::Object(values) => values.get(&token),
+                JsonValue::Array(values) => values.get(token.parse::<usize>().ok()?),
+                _ => None,
+            })
+    }
+}
+
+pub fn validate_query(msg: &str, path: &str, max_response_bytes: Option<usize>) -> StdResult<()> {
+    if msg.len() > MAX_QUERY_MSG_BYTES {
+        return Err(StdError::generic_err(format!(
+            "Query msg exceeds maximum size of {MAX_QUERY_MSG_BYTES} bytes"
+        )));
+    }
+
+    from_json::<IgnoredAny>(msg.as_bytes())
+        .map_err(|e| StdError::generic_err(format!("Query msg is not valid JSON: {e}")))?;
+
+    if !path.is_empty() && !path.starts_with('/') {
+        return Err(StdError::generic_err(format!(
+            "Query path must be a JSON pointer starting with '/': {path}"
+        )));
+    }
+
+    if let Some(max_response_bytes) = max_response_bytes {
+        if max_response_bytes == 0 || max_response_bytes > MAX_QUERY_RESPONSE_BYTES {
+            return Err(StdError::generic_err(format!(
+                "Query response limit must be between 1 and {MAX_QUERY_RESPONSE_BYTES} bytes"
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Smart queries `contract` and returns the scalar found at `path`
+/// in the response. Responses over `max_response_bytes` (never more
+/// than `MAX_QUERY_RESPONSE_BYTES`) are rejected before being parsed,
+/// so a misbehaving contract can't burn unbounded gas.
+pub fn query_json_path(
+    deps: Deps,
+    contract: &Addr,
+    msg: &str,
+    path: &str,
+    max_response_bytes: Option<usize>,
+) -> StdResult<String> {
+    let request: QueryRequest<Empty> = QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: contract.to_string(),
+        msg: msg.as_bytes().into(),
+    });
+
+    let response = match deps.querier.raw_query(&to_json_vec(&request)?) {
+        SystemResult::Ok(ContractResult::Ok(response)) => response,
+        SystemResult::Ok(ContractResult::Err(err)) => {
+            return Err(StdError::generic_err(format!(
+                "Query to {contract} failed: {err}"
+            )))
+        }
+        SystemResult::Err(err) => {
+            return Err(StdError::generic_err(format!(
+                "Query to {contract} failed: {err}"
+            )))
+        }
+    };
+
+    let limit = max_response_bytes
+        .unwrap_or(MAX_QUERY_RESPONSE_BYTES)
+        .min(MAX_QUERY_RESPONSE_BYTES);
+
+    if response.len() > limit {
+        return Err(StdError::generic_err(format!(
+            "Query response exceeds maximum size of {limit} bytes"
+        )));
+    }
+
+    let value = from_json::<JsonValue>(&response)?;
+
+    match value.pointer(path) {
+        Some(JsonValue::Null) => Ok("null".to_string()),
+        Some(JsonValue::Bool(value)) => Ok(value.to_string()),
+        Some(JsonValue::Number(value)) | Some(JsonValue::String(value)) => Ok(value.clone()),
+        Some(JsonValue::Array(_)) | Some(JsonValue::Object(_)) => Err(StdError::generic_err(
+            format!("Query path {path} does not point to a scalar value"),
+        )),
+        None => Err(StdError::generic_err(format!(
+            "Query path {path} not found in response"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod query_tests {
+    use super::*;
+
+    #[test]
+    fn resolves_json_pointers() {
+        let value = from_json::<JsonValue>(
+            br#"{"vault":{"share_price":"1.05","users":[{"a/b":7}]},"paused":false}"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            value.pointer("/vault/share_price"),
+            Some(JsonValue::String(price)) if price == "1.05"
+        ));
+        assert!(matches!(
+            value.pointer("/vault/users/0/a~1b"),
+            Some(JsonValue::Number(number)) if number == "7"
+        ));
+        assert!(matches!(
+            value.pointer("/paused"),
+            Some(JsonValue::Bool(false))
+        ));
+        assert!(matches!(value.pointer(""), Some(JsonValue::Object(_))));
+        assert!(value.pointer("/vault/users/1").is_none());
+        assert!(value.pointer("/missing").is_none());
+        assert!(value.pointer("vault").is_none());
+    }
+
+    #[test]
+    fn compares_numerically_when_possible() {
+        assert!(Comparator::GreaterThan.compare("10", "9"));
+        assert!(Comparator::Equal.compare("1.50", "1.5"));
+        assert!(Comparator::LessThanOrEqual.compare("0.99", "1"));
+        assert!(Comparator::NotEqual.compare("abc", "abd"));
+        assert!(Comparator::LessThan.compare("abc", "abd"));
+        assert!(Comparator::Equal.compare("true", "true"));
+    }
+
+    #[test]
+    fn compares_negative_numbers_numerically() {
+        assert!(Comparator::LessThan.compare("-5", "1"));
+        assert!(Comparator::LessThan.compare("-1.5", "-1"));
+        assert!(Comparator::GreaterThan.compare("-2", "-10"));
+        assert!(Comparator::Equal.compare("-0.50", "-0.5"));
+    }
+
+    #[test]
+    fn validates_response_limit() {
+        assert!(validate_query("{}", "/value", None).is_ok());
+        assert!(validate_query("{}", "/value", Some(MAX_QUERY_RESPONSE_BYTES)).is_ok());
+        assert!(validate_query("{}", "/value", Some(0)).is_err());
+        assert!(validate_query("{}", "/value", Some(MAX_QUERY_RESPONSE_BYTES + 1)).is_err());
+    }
+}
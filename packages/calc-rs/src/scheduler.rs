@@ -6,7 +6,7 @@ use std::{
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, Decimal, StdResult, Timestamp, Uint64};
 
-use crate::conditions::Condition;
+use crate::conditions::{Condition, ConditionTrace, ErrorPolicy};
 
 #[cw_serde]
 pub struct Trigger {
@@ -17,6 +17,8 @@ pub struct Trigger {
     pub executors: Vec<Addr>,
     pub execution_rebate: Vec<Coin>,
     pub jitter: Option<Duration>,
+    #[serde(default = "legacy_error_policy")]
+    pub on_error: ErrorPolicy,
 }
 
 #[cw_serde]
@@ -29,6 +31,15 @@ pub struct CreateTriggerMsg {
     pub contract_address: Addr,
     pub executors: Vec<Addr>,
     pub jitter: Option<Duration>,
+    #[serde(default = "legacy_error_policy")]
+    pub on_error: ErrorPolicy,
+}
+
+/// Triggers stored (or requested) before error policies existed were
+/// executed whenever their condition failed to evaluate, so they keep
+/// doing so rather than picking up `ErrorPolicy::default()`.
+fn legacy_error_policy() -> ErrorPolicy {
+    ErrorPolicy::TreatAsTrue
 }
 
 impl CreateTriggerMsg {
@@ -61,6 +72,14 @@ pub enum ConditionFilter {
         price_range: Option<(Decimal, Decimal)>,
         start_after: Option<u64>,
     },
+    FinPrice {
+        pair_address: Addr,
+        start_after: Option<u64>,
+    },
+    OraclePrice {
+        asset: String,
+        start_after: Option<u64>,
+    },
 }
 
 #[cw_serde]
@@ -73,4 +92,8 @@ pub enum SchedulerQueryMsg {
     },
     #[returns(bool)]
     CanExecute(Uint64),
+    #[returns(ConditionTrace)]
+    TraceTrigger(Uint64),
+    #[returns(ConditionTrace)]
+    TraceCondition { condition: Condition },
 }
@@ -1,15 +1,22 @@
 use std::collections::HashMap;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Coin, Coins, StdResult};
+use cosmwasm_std::{Addr, Coin, Coins, Deps, Env, StdResult, Uint128};
+use cw_storage_plus::Item;
 
-use crate::actions::distribution::Recipient;
+use crate::{actions::distribution::Recipient, strategy::StrategyQueryMsg};
+
+pub const STATISTICS: Item<Statistics> = Item::new("stats");
 
 #[cw_serde]
 #[derive(Default)]
 pub struct Statistics {
     pub debited: Vec<Coin>,
     pub credited: Vec<(Recipient, Vec<Coin>)>,
+    #[serde(default)]
+    pub deposited: Vec<(String, Vec<Coin>)>,
+    #[serde(default)]
+    pub executions: u64,
 }
 
 impl Statistics {
@@ -47,9 +54,63 @@ impl Statistics {
             distributed.push((recipient.clone(), coins.into_vec()));
         }
 
+        let mut deposited_map: HashMap<String, Coins> = HashMap::new();
+
+        for (pool, amounts) in self.deposited.into_iter().chain(other.deposited) {
+            let coins = deposited_map.entry(pool).or_default();
+
+            for amount in amounts {
+                coins.add(amount)?;
+            }
+        }
+
+        let mut deposited = deposited_map
+            .into_iter()
+            .map(|(pool, coins)| (pool, coins.into_vec()))
+            .collect::<Vec<_>>();
+
+        deposited.sort_by(|a, b| a.0.cmp(&b.0));
+
         Ok(Statistics {
             debited: outgoing.into_vec(),
             credited: distributed,
+            deposited,
+            executions: self.executions + other.executions,
         })
     }
+
+    pub fn debited_amount(&self, denom: &str) -> Coin {
+        Coin::new(
+            self.debited
+                .iter()
+                .filter(|amount| amount.denom == denom)
+                .map(|amount| amount.amount)
+                .sum::<Uint128>(),
+            denom,
+        )
+    }
+
+    pub fn credited_amount(&self, recipient: &Recipient, denom: &str) -> Coin {
+        Coin::new(
+            self.credited
+                .iter()
+                .filter(|(credited, _)| credited.key() == recipient.key())
+                .flat_map(|(_, amounts)| amounts)
+                .filter(|amount| amount.denom == denom)
+                .map(|amount| amount.amount)
+                .sum::<Uint128>(),
+            denom,
+        )
+    }
+}
+
+/// Loads the statistics of `strategy`, or of the current
+/// contract when it is the strategy being evaluated.
+pub fn load_statistics(deps: Deps, env: &Env, strategy: &Option<Addr>) -> StdResult<Statistics> {
+    match strategy {
+        Some(strategy) if *strategy != env.contract.address => deps
+            .querier
+            .query_wasm_smart(strategy, &StrategyQueryMsg::Statistics {}),
+        _ => Ok(STATISTICS.may_load(deps.storage)?.unwrap_or_default()),
+    }
 }
@@ -7,17 +7,21 @@ use std::{
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
     instantiate2_address, to_json_binary, Addr, Binary, Coin, Coins, CosmosMsg, Deps, DepsMut, Env,
-    Event, MessageInfo, Response, StdError, StdResult, Storage, SubMsg, WasmMsg,
+    Event, MessageInfo, Response, StdError, StdResult, Storage, SubMsg, Timestamp, WasmMsg,
 };
 
 use crate::{
     actions::{
         action::Action,
         operation::{StatefulOperation, StatelessOperation},
+        reference::{init_definitions, Definitions},
     },
+    conditions::{Condition, ConditionTrace},
     constants::{LOG_ERRORS_REPLY_ID, MAX_STRATEGY_SIZE, PROCESS_PAYLOAD_REPLY_ID},
     core::Contract,
     manager::{Affiliate, StrategyStatus},
+    price::PriceSource,
+    price_history::PriceSample,
     statistics::Statistics,
 };
 
@@ -37,6 +41,7 @@ pub enum StrategyExecuteMsg {
     UpdateStatus(StrategyStatus),
     Commit,
     Clear,
+    Heartbeat,
 }
 
 #[cw_serde]
@@ -48,30 +53,78 @@ pub enum StrategyQueryMsg {
     Statistics {},
     #[returns(Vec<Coin>)]
     Balances(HashSet<String>),
+    #[returns(Vec<PriceSample>)]
+    PriceHistory { source: PriceSource },
+    #[returns(Timestamp)]
+    Heartbeat {},
+    #[returns(ConditionTrace)]
+    TraceCondition { condition: Condition },
 }
 
 #[cw_serde]
 pub struct Strategy<S> {
     pub owner: Addr,
     pub action: Action,
+    #[serde(default)]
+    pub definitions: Definitions,
     pub state: S,
 }
 
 impl<S> Strategy<S> {
     pub fn size(&self) -> usize {
-        self.action.size()
+        self.definitions
+            .iter()
+            .map(|(_, action)| action.size())
+            .sum::<usize>()
+            + self.action.size()
     }
 
     pub fn denoms(&self, deps: Deps, env: &Env) -> StdResult<HashSet<String>> {
-        self.action.denoms(deps, env)
+        let mut denoms = self.action.denoms(deps, env)?;
+
+        for (_, action) in self.definitions.iter() {
+            denoms.extend(action.denoms(deps, env)?);
+        }
+
+        Ok(denoms)
     }
 
     pub fn escrowed(&self, deps: Deps, env: &Env) -> StdResult<HashSet<String>> {
-        self.action.escrowed(deps, env)
+        let mut escrowed = self.action.escrowed(deps, env)?;
+
+        for (_, action) in self.definitions.iter() {
+            escrowed.extend(action.escrowed(deps, env)?);
+        }
+
+        Ok(escrowed)
+    }
+
+    pub fn price_sources(&self) -> Vec<PriceSource> {
+        let mut sources: Vec<PriceSource> = vec![];
+
+        for source in self.action.price_sources().into_iter().chain(
+            self.definitions
+                .iter()
+                .flat_map(|(_, action)| action.price_sources()),
+        ) {
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+
+        sources
     }
 
     pub fn balances(&self, deps: Deps, env: &Env, denoms: &HashSet<String>) -> StdResult<Coins> {
-        self.action.balances(deps, env, denoms)
+        let mut balances = self.action.balances(deps, env, denoms)?;
+
+        for (_, action) in self.definitions.iter() {
+            for balance in action.balances(deps, env, denoms)? {
+                balances.add(balance)?;
+            }
+        }
+
+        Ok(balances)
     }
 }
 
@@ -166,6 +219,11 @@ impl Strategy<Json> {
         Ok(Strategy {
             owner: self.owner,
             action: self.action.add_affiliates(affiliates)?,
+            definitions: self
+                .definitions
+                .into_iter()
+                .map(|(name, action)| Ok((name, action.add_affiliates(affiliates)?)))
+                .collect::<StdResult<Definitions>>()?,
             state: Indexable,
         })
     }
@@ -205,6 +263,7 @@ impl Strategy<Indexable> {
         let instantiable_strategy = Strategy {
             owner: self.owner.clone(),
             action: self.action.clone(),
+            definitions: self.definitions.clone(),
             state: Instantiable {
                 contract_address,
                 label,
@@ -230,6 +289,7 @@ impl Strategy<Indexable> {
         let indexed_strategy = Strategy {
             owner: self.owner.clone(),
             action: self.action.clone(),
+            definitions: self.definitions.clone(),
             state: Updatable { contract_address },
         };
 
@@ -249,6 +309,7 @@ impl Strategy<Instantiable> {
             msg: to_json_binary(&Strategy {
                 owner: self.owner,
                 action: self.action,
+                definitions: self.definitions,
                 state: Indexed {
                     contract_address: self.state.contract_address.clone(),
                 },
@@ -265,6 +326,7 @@ impl Strategy<Updatable> {
             to_json_binary(&StrategyExecuteMsg::Update(Strategy {
                 owner: self.owner,
                 action: self.action,
+                definitions: self.definitions,
                 state: Indexed {
                     contract_address: self.state.contract_address,
                 },
@@ -285,13 +347,21 @@ impl Strategy<Indexed> {
             )));
         }
 
-        let (messages, events, action) = self.action.init(deps.as_ref(), env)?;
+        let (mut messages, mut events, definitions) =
+            init_definitions(self.definitions, deps.as_ref(), env)?;
+
+        let (action_messages, action_events, action) =
+            self.action.init(deps.as_ref(), env, &definitions)?;
+
+        messages.extend(action_messages);
+        events.extend(action_events);
 
         save(
             deps.storage,
             Strategy {
                 owner: self.owner,
                 action,
+                definitions,
                 state: Committed {},
             },
         )?;
@@ -307,6 +377,7 @@ impl Strategy<Committed> {
         Strategy {
             owner: self.owner,
             action: self.action,
+            definitions: self.definitions,
             state: Active,
         }
     }
@@ -314,11 +385,13 @@ impl Strategy<Committed> {
 
 impl Strategy<Active> {
     pub fn prepare_to_execute(self, deps: Deps, env: &Env) -> StdResult<Strategy<Executable>> {
-        let (messages, events, action) = self.action.execute(deps, env);
+        let mut definitions = self.definitions;
+        let (messages, events, action) = self.action.execute(deps, env, &mut definitions)?;
 
         Ok(Strategy {
             owner: self.owner,
             action,
+            definitions,
             state: Executable { messages, events },
         })
     }
@@ -329,31 +402,58 @@ impl Strategy<Active> {
         env: &Env,
         desired: &HashSet<String>,
     ) -> StdResult<Strategy<Executable>> {
-        let (messages, events, action) = self.action.withdraw(deps, env, desired)?;
+        let (mut messages, mut events, action) = self.action.withdraw(deps, env, desired)?;
+        let mut definitions = vec![];
+
+        for (name, action) in self.definitions {
+            let (action_messages, action_events, action) = action.withdraw(deps, env, desired)?;
+            messages.extend(action_messages);
+            events.extend(action_events);
+            definitions.push((name, action));
+        }
 
         Ok(Strategy {
             owner: self.owner,
             action,
+            definitions,
             state: Executable { messages, events },
         })
     }
 
     pub fn prepare_to_cancel(self, deps: Deps, env: &Env) -> StdResult<Strategy<Executable>> {
-        let (messages, events, action) = self.action.cancel(deps, env)?;
+        let (mut messages, mut events, action) = self.action.cancel(deps, env)?;
+        let mut definitions = vec![];
+
+        for (name, action) in self.definitions {
+            let (action_messages, action_events, action) = action.cancel(deps, env)?;
+            messages.extend(action_messages);
+            events.extend(action_events);
+            definitions.push((name, action));
+        }
 
         Ok(Strategy {
             owner: self.owner,
             action,
+            definitions,
             state: Executable { messages, events },
         })
     }
 
     pub fn prepare_to_commit(self, deps: Deps, env: &Env) -> StdResult<Strategy<Committable>> {
-        let (messages, events, action) = self.action.commit(deps, env)?;
+        let (mut messages, mut events, action) = self.action.commit(deps, env)?;
+        let mut definitions = vec![];
+
+        for (name, action) in self.definitions {
+            let (action_messages, action_events, action) = action.commit(deps, env)?;
+            messages.extend(action_messages);
+            events.extend(action_events);
+            definitions.push((name, action));
+        }
 
         Ok(Strategy {
             owner: self.owner,
             action,
+            definitions,
             state: Committable { messages, events },
         })
     }
@@ -369,6 +469,7 @@ impl Strategy<Committable> {
             Strategy {
                 owner: self.owner,
                 action: self.action,
+                definitions: self.definitions,
                 state: Committed,
             },
         )?;
@@ -399,6 +500,7 @@ impl Strategy<Executable> {
             Strategy {
                 owner: self.owner,
                 action: self.action,
+                definitions: self.definitions,
                 state: Active,
             },
         )?;
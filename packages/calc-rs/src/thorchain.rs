@@ -1,4 +1,5 @@
 use std::{
+    fmt::Display,
     num::{ParseIntError, TryFromIntError},
     ops::Div,
     str::FromStr,
@@ -12,7 +13,8 @@ use cosmwasm_std::{
 };
 use prost::{DecodeError, EncodeError, Message};
 use rujira_rs::proto::types::{
-    QueryNetworkRequest, QueryNetworkResponse, QueryQuoteSwapRequest, QueryQuoteSwapResponse,
+    QueryMimirWithKeyRequest, QueryMimirWithKeyResponse, QueryNetworkRequest, QueryNetworkResponse,
+    QueryPoolRequest, QueryPoolResponse, QueryQuoteSwapRequest, QueryQuoteSwapResponse,
     QuoteFees as QuerySwapQuoteResponseFees,
 };
 use thiserror::Error;
@@ -192,6 +194,7 @@ pub struct SwapQuote {
     pub expected_amount_out: Uint128,
     pub max_streaming_quantity: u64,
     pub streaming_swap_blocks: u64,
+    pub outbound_delay_seconds: u64,
 }
 
 impl TryFrom<QuerySwapQuoteResponseFees> for QuoteFees {
@@ -226,6 +229,7 @@ impl TryFrom<QueryQuoteSwapResponse> for SwapQuote {
             expected_amount_out: Uint128::from_str(value.expected_amount_out.as_str())?,
             max_streaming_quantity: u64::try_from(value.streaming_swap_blocks).unwrap_or(1),
             streaming_swap_blocks: u64::try_from(value.streaming_swap_blocks).unwrap_or(1),
+            outbound_delay_seconds: u64::try_from(value.outbound_delay_seconds)?,
         })
     }
 }
@@ -313,6 +317,124 @@ impl Network {
     }
 }
 
+impl QueryablePair for QueryPoolResponse {
+    type Request = QueryPoolRequest;
+    type Response = QueryPoolResponse;
+
+    fn grpc_path() -> &'static str {
+        "/types.Query/Pool"
+    }
+}
+
+impl QueryablePair for QueryMimirWithKeyResponse {
+    type Request = QueryMimirWithKeyRequest;
+    type Response = QueryMimirWithKeyResponse;
+
+    fn grpc_path() -> &'static str {
+        "/types.Query/MimirWithKey"
+    }
+}
+
+/// Mimir halts are set to the height they take effect from.
+fn is_halted(q: QuerierWrapper, key: &str, height: u64) -> Result<bool, QueryError> {
+    let halt = QueryMimirWithKeyResponse::get(
+        q,
+        QueryMimirWithKeyRequest {
+            key: key.to_string(),
+            height: "0".to_string(),
+        },
+    )?
+    .value;
+
+    Ok(halt > 0 && halt as u64 <= height)
+}
+
+/// Checks the trading halts and pool status that would cause a swap
+/// into or out of `denom` to be refunded, returning the reason if so.
+pub fn trading_unavailable_reason(
+    q: QuerierWrapper,
+    height: u64,
+    denom: &str,
+) -> Result<Option<String>, QueryError> {
+    if is_halted(q, "HALTTRADING", height)? {
+        return Ok(Some("Trading is halted".to_string()));
+    }
+
+    let pool = match secured_asset_pool(denom) {
+        Some(pool) => pool,
+        None => return Ok(None),
+    };
+
+    let chain = pool
+        .split_once('.')
+        .map_or(pool.as_str(), |(chain, _)| chain);
+
+    for key in [
+        format!("HALT{chain}TRADING"),
+        format!("HALT{chain}CHAIN"),
+        format!("SOLVENCYHALT{chain}CHAIN"),
+    ] {
+        if is_halted(q, &key, height)? {
+            return Ok(Some(format!("{chain} trading is halted ({key})")));
+        }
+    }
+
+    let status = QueryPoolResponse::get(
+        q,
+        QueryPoolRequest {
+            asset: pool.clone(),
+            height: "0".to_string(),
+        },
+    )?
+    .status;
+
+    if status != "Available" {
+        return Ok(Some(format!("{pool} pool is {status}")));
+    }
+
+    Ok(None)
+}
+
+/// How long Thorchain expects to take to pay out a swap of `amount` into `denom`.
+pub fn outbound_delay_seconds(
+    q: QuerierWrapper,
+    amount: &Coin,
+    denom: &str,
+) -> Result<u64, SwapQuoteError> {
+    Ok(SwapQuote::get(
+        q,
+        &SwapQuoteRequest {
+            from_asset: amount.denom.clone(),
+            to_asset: denom.to_string(),
+            amount: amount.amount,
+            streaming_interval: Uint128::zero(),
+            streaming_quantity: Uint128::zero(),
+            destination: String::new(),
+            refund_address: String::new(),
+            affiliate: vec![],
+            affiliate_bps: vec![],
+        },
+    )?
+    .outbound_delay_seconds)
+}
+
+/// The RUNE side of the Thorchain pool for `denom`.
+pub fn pool_depth(q: QuerierWrapper, denom: &str) -> StdResult<Uint128> {
+    let pool = secured_asset_pool(denom)
+        .ok_or_else(|| StdError::generic_err(format!("No Thorchain pool for {denom}")))?;
+
+    let response = QueryPoolResponse::get(
+        q,
+        QueryPoolRequest {
+            asset: pool.clone(),
+            height: "0".to_string(),
+        },
+    )
+    .map_err(|e| StdError::generic_err(format!("Failed to load pool {pool}: {e}")))?;
+
+    Uint128::from_str(&response.balance_rune)
+}
+
 #[derive(Error, Debug)]
 pub enum TryFromNetworkError {
     #[error("{0}")]
@@ -323,6 +445,439 @@ pub enum TryFromNetworkError {
     Query(#[from] QueryError),
 }
 
+/// Thorchain rejects memos longer than this many bytes
+pub const MAX_MEMO_LENGTH: usize = 250;
+
+#[cw_serde]
+pub enum Memo {
+    Swap {
+        asset: String,
+        destination: Option<String>,
+        limit: Option<Uint128>,
+        interval: Option<u64>,
+        quantity: Option<u64>,
+        affiliates: Vec<String>,
+        affiliate_bps: Vec<u64>,
+    },
+    AddLiquidity {
+        pool: String,
+        paired_address: Option<String>,
+    },
+    WithdrawLiquidity {
+        pool: String,
+        basis_points: u64,
+        asset: Option<String>,
+    },
+    DepositSavers {
+        vault: String,
+    },
+    WithdrawSavers {
+        vault: String,
+        basis_points: u64,
+    },
+    Bond {
+        node_address: String,
+        provider: Option<String>,
+        fee_bps: Option<u64>,
+    },
+    Unbond {
+        node_address: String,
+        amount: Uint128,
+        provider: Option<String>,
+    },
+    TradeDeposit {
+        address: String,
+    },
+    TradeWithdraw {
+        address: String,
+    },
+    SecureDeposit {
+        address: String,
+    },
+    SecureWithdraw {
+        address: String,
+    },
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MemoError {
+    #[error("Memo cannot be empty")]
+    Empty,
+
+    #[error("Memo exceeds maximum length of {MAX_MEMO_LENGTH} bytes")]
+    TooLong,
+
+    #[error("Unknown memo type {0}")]
+    UnknownType(String),
+
+    #[error("Memo is missing {0}")]
+    Missing(&'static str),
+
+    #[error("Memo has invalid {0}: {1}")]
+    Invalid(&'static str, String),
+}
+
+impl From<MemoError> for StdError {
+    fn from(err: MemoError) -> Self {
+        StdError::generic_err(err.to_string())
+    }
+}
+
+fn optional(field: Option<&&str>) -> Option<String> {
+    field.filter(|f| !f.is_empty()).map(|f| f.to_string())
+}
+
+fn required(field: Option<&&str>, name: &'static str) -> Result<String, MemoError> {
+    optional(field).ok_or(MemoError::Missing(name))
+}
+
+fn parse<T: FromStr>(field: &str, name: &'static str) -> Result<T, MemoError> {
+    field
+        .parse::<T>()
+        .map_err(|_| MemoError::Invalid(name, field.to_string()))
+}
+
+/// Parses a swap limit, which Thorchain's quote endpoint may write in
+/// e-notation (e.g. `1423175e3`) to keep the memo short.
+fn parse_limit(field: &str, name: &'static str) -> Result<u128, MemoError> {
+    let invalid = || MemoError::Invalid(name, field.to_string());
+
+    match field.split_once(|c| c == 'e' || c == 'E') {
+        Some((mantissa, exponent)) => {
+            let mantissa = mantissa.parse::<u128>().map_err(|_| invalid())?;
+            let exponent = exponent.parse::<u32>().map_err(|_| invalid())?;
+
+            10u128
+                .checked_pow(exponent)
+                .and_then(|scale| mantissa.checked_mul(scale))
+                .ok_or_else(invalid)
+        }
+        None => parse::<u128>(field, name),
+    }
+}
+
+fn parse_list<T: FromStr>(field: Option<&&str>, name: &'static str) -> Result<Vec<T>, MemoError> {
+    optional(field).map_or(Ok(vec![]), |f| {
+        f.split('/').map(|item| parse::<T>(item, name)).collect()
+    })
+}
+
+fn basis_points(field: Option<&&str>) -> Result<u64, MemoError> {
+    parse::<u64>(&required(field, "basis points")?, "basis points")
+}
+
+fn join(list: &[impl ToString]) -> String {
+    list.iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+impl Memo {
+    pub fn validate(&self) -> Result<(), MemoError> {
+        match self {
+            Memo::Swap {
+                asset,
+                affiliates,
+                affiliate_bps,
+                ..
+            } => {
+                if asset.is_empty() {
+                    return Err(MemoError::Missing("asset"));
+                }
+
+                if affiliates.len() != affiliate_bps.len() && affiliate_bps.len() > 1 {
+                    return Err(MemoError::Invalid(
+                        "affiliate fees",
+                        "must match the number of affiliates".to_string(),
+                    ));
+                }
+
+                if let Some(bps) = affiliate_bps.iter().find(|bps| **bps > 10_000) {
+                    return Err(MemoError::Invalid("affiliate fee", bps.to_string()));
+                }
+            }
+            Memo::AddLiquidity { pool, .. } | Memo::WithdrawLiquidity { pool, .. } => {
+                if !pool.contains('.') || pool.contains('/') {
+                    return Err(MemoError::Invalid("pool", pool.clone()));
+                }
+            }
+            Memo::DepositSavers { vault } | Memo::WithdrawSavers { vault, .. } => {
+                if !vault.contains('/') {
+                    return Err(MemoError::Invalid("savers vault", vault.clone()));
+                }
+            }
+            Memo::Bond {
+                node_address,
+                fee_bps,
+                ..
+            } => {
+                if node_address.is_empty() {
+                    return Err(MemoError::Missing("node address"));
+                }
+
+                if let Some(fee_bps) = fee_bps.filter(|bps| *bps > 10_000) {
+                    return Err(MemoError::Invalid("operator fee", fee_bps.to_string()));
+                }
+            }
+            Memo::Unbond {
+                node_address,
+                amount,
+                ..
+            } => {
+                if node_address.is_empty() {
+                    return Err(MemoError::Missing("node address"));
+                }
+
+                if amount.is_zero() {
+                    return Err(MemoError::Invalid("unbond amount", amount.to_string()));
+                }
+            }
+            Memo::TradeDeposit { address }
+            | Memo::TradeWithdraw { address }
+            | Memo::SecureDeposit { address }
+            | Memo::SecureWithdraw { address } => {
+                if address.is_empty() {
+                    return Err(MemoError::Missing("address"));
+                }
+            }
+        }
+
+        if let Memo::WithdrawLiquidity { basis_points, .. }
+        | Memo::WithdrawSavers { basis_points, .. } = self
+        {
+            if *basis_points == 0 || *basis_points > 10_000 {
+                return Err(MemoError::Invalid("basis points", basis_points.to_string()));
+            }
+        }
+
+        if self.to_string().len() > MAX_MEMO_LENGTH {
+            return Err(MemoError::TooLong);
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Memo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields = match self {
+            Memo::Swap {
+                asset,
+                destination,
+                limit,
+                interval,
+                quantity,
+                affiliates,
+                affiliate_bps,
+            } => {
+                let limit = if interval.is_some() || quantity.is_some() {
+                    format!(
+                        "{}/{}/{}",
+                        limit.unwrap_or_default(),
+                        interval.unwrap_or_default(),
+                        quantity.unwrap_or_default()
+                    )
+                } else {
+                    limit.map(|l| l.to_string()).unwrap_or_default()
+                };
+
+                vec![
+                    "=".to_string(),
+                    asset.clone(),
+                    destination.clone().unwrap_or_default(),
+                    limit,
+                    join(affiliates),
+                    join(affiliate_bps),
+                ]
+            }
+            Memo::AddLiquidity {
+                pool,
+                paired_address,
+            } => vec![
+                "+".to_string(),
+                pool.clone(),
+                paired_address.clone().unwrap_or_default(),
+            ],
+            Memo::WithdrawLiquidity {
+                pool,
+                basis_points,
+                asset,
+            } => vec![
+                "-".to_string(),
+                pool.clone(),
+                basis_points.to_string(),
+                asset.clone().unwrap_or_default(),
+            ],
+            Memo::DepositSavers { vault } => vec!["+".to_string(), vault.clone()],
+            Memo::WithdrawSavers {
+                vault,
+                basis_points,
+            } => vec!["-".to_string(), vault.clone(), basis_points.to_string()],
+            Memo::Bond {
+                node_address,
+                provider,
+                fee_bps,
+            } => vec![
+                "BOND".to_string(),
+                node_address.clone(),
+                provider.clone().unwrap_or_default(),
+                fee_bps.map(|bps| bps.to_string()).unwrap_or_default(),
+            ],
+            Memo::Unbond {
+                node_address,
+                amount,
+                provider,
+            } => vec![
+                "UNBOND".to_string(),
+                node_address.clone(),
+                amount.to_string(),
+                provider.clone().unwrap_or_default(),
+            ],
+            Memo::TradeDeposit { address } => vec!["TRADE+".to_string(), address.clone()],
+            Memo::TradeWithdraw { address } => vec!["TRADE-".to_string(), address.clone()],
+            Memo::SecureDeposit { address } => vec!["SECURE+".to_string(), address.clone()],
+            Memo::SecureWithdraw { address } => vec!["SECURE-".to_string(), address.clone()],
+        };
+
+        // Thorchain treats missing trailing fields as empty
+        let length = fields
+            .iter()
+            .rposition(|field| !field.is_empty())
+            .map_or(0, |i| i + 1);
+
+        write!(f, "{}", fields[..length].join(":"))
+    }
+}
+
+impl FromStr for Memo {
+    type Err = MemoError;
+
+    fn from_str(memo: &str) -> Result<Self, Self::Err> {
+        if memo.trim().is_empty() {
+            return Err(MemoError::Empty);
+        }
+
+        if memo.len() > MAX_MEMO_LENGTH {
+            return Err(MemoError::TooLong);
+        }
+
+        let fields = memo.split(':').collect::<Vec<_>>();
+
+        let parsed = match fields[0].to_uppercase().as_str() {
+            "=" | "S" | "SWAP" => {
+                let limits = optional(fields.get(3)).map_or(Ok(vec![]), |limits| {
+                    limits
+                        .split('/')
+                        .map(|l| parse_limit(l, "swap limit"))
+                        .collect::<Result<Vec<_>, _>>()
+                })?;
+
+                Memo::Swap {
+                    asset: required(fields.get(1), "asset")?,
+                    destination: optional(fields.get(2)),
+                    limit: limits.first().map(|l| Uint128::new(*l)),
+                    interval: limits
+                        .get(1)
+                        .map(|i| {
+                            u64::try_from(*i)
+                                .map_err(|_| MemoError::Invalid("swap interval", i.to_string()))
+                        })
+                        .transpose()?,
+                    quantity: limits
+                        .get(2)
+                        .map(|q| {
+                            u64::try_from(*q)
+                                .map_err(|_| MemoError::Invalid("swap quantity", q.to_string()))
+                        })
+                        .transpose()?,
+                    affiliates: optional(fields.get(4))
+                        .map(|a| a.split('/').map(|a| a.to_string()).collect())
+                        .unwrap_or_default(),
+                    affiliate_bps: parse_list::<u64>(fields.get(5), "affiliate fee")?,
+                }
+            }
+            "+" | "A" | "ADD" => {
+                let pool = required(fields.get(1), "pool")?;
+
+                if pool.contains('/') {
+                    Memo::DepositSavers { vault: pool }
+                } else {
+                    Memo::AddLiquidity {
+                        pool,
+                        paired_address: optional(fields.get(2)),
+                    }
+                }
+            }
+            "-" | "WD" | "WITHDRAW" => {
+                let pool = required(fields.get(1), "pool")?;
+
+                if pool.contains('/') {
+                    Memo::WithdrawSavers {
+                        vault: pool,
+                        basis_points: basis_points(fields.get(2))?,
+                    }
+                } else {
+                    Memo::WithdrawLiquidity {
+                        pool,
+                        basis_points: basis_points(fields.get(2))?,
+                        asset: optional(fields.get(3)),
+                    }
+                }
+            }
+            "BOND" => Memo::Bond {
+                node_address: required(fields.get(1), "node address")?,
+                provider: optional(fields.get(2)),
+                fee_bps: optional(fields.get(3))
+                    .map(|fee| parse::<u64>(&fee, "operator fee"))
+                    .transpose()?,
+            },
+            "UNBOND" => Memo::Unbond {
+                node_address: required(fields.get(1), "node address")?,
+                amount: parse::<Uint128>(&required(fields.get(2), "amount")?, "amount")?,
+                provider: optional(fields.get(3)),
+            },
+            "TRADE+" => Memo::TradeDeposit {
+                address: required(fields.get(1), "address")?,
+            },
+            "TRADE-" => Memo::TradeWithdraw {
+                address: required(fields.get(1), "address")?,
+            },
+            "SECURE+" => Memo::SecureDeposit {
+                address: required(fields.get(1), "address")?,
+            },
+            // Older clients put the direction before the memo type
+            "SECURE-" | "-SECURE" => Memo::SecureWithdraw {
+                address: required(fields.get(1), "address")?,
+            },
+            memo_type => return Err(MemoError::UnknownType(memo_type.to_string())),
+        };
+
+        parsed.validate()?;
+
+        Ok(parsed)
+    }
+}
+
+pub fn is_secured_asset(denom: &str) -> bool {
+    denom.to_lowercase() == "rune" || denom.contains("-")
+}
+
+pub fn secured_asset_pool(denom: &str) -> Option<String> {
+    if denom.to_lowercase() == "rune" {
+        return None;
+    }
+
+    denom
+        .split_once("-")
+        .map(|(chain, symbol)| format!("{chain}.{symbol}").to_uppercase())
+}
+
+pub fn pool_secured_asset(pool: &str) -> Option<String> {
+    pool.split_once(".")
+        .map(|(chain, symbol)| format!("{chain}-{symbol}").to_lowercase())
+}
+
 #[cfg(test)]
 mod msg_deposit_tests {
     use anybuf::Anybuf;
@@ -437,3 +992,219 @@ mod msg_deposit_tests {
         deposit_msg.into_cosmos_msg().unwrap();
     }
 }
+
+#[cfg(test)]
+mod memo_tests {
+    use std::str::FromStr;
+
+    use cosmwasm_std::Uint128;
+
+    use crate::thorchain::{pool_secured_asset, secured_asset_pool, Memo, MemoError};
+
+    #[test]
+    fn encodes_add_liquidity_memo() {
+        assert_eq!(
+            Memo::AddLiquidity {
+                pool: "BTC.BTC".to_string(),
+                paired_address: None,
+            }
+            .to_string(),
+            "+:BTC.BTC"
+        );
+    }
+
+    #[test]
+    fn encodes_withdraw_liquidity_memo() {
+        assert_eq!(
+            Memo::WithdrawLiquidity {
+                pool: "BTC.BTC".to_string(),
+                basis_points: 5_000,
+                asset: None,
+            }
+            .to_string(),
+            "-:BTC.BTC:5000"
+        );
+    }
+
+    #[test]
+    fn encodes_swap_memo_with_empty_destination() {
+        assert_eq!(
+            Memo::Swap {
+                asset: "ETH.ETH".to_string(),
+                destination: None,
+                limit: Some(Uint128::zero()),
+                interval: Some(3),
+                quantity: Some(0),
+                affiliates: vec!["rj".to_string()],
+                affiliate_bps: vec![10],
+            }
+            .to_string(),
+            "=:ETH.ETH::0/3/0:rj:10"
+        );
+    }
+
+    #[test]
+    fn parses_swap_memo() {
+        assert_eq!(
+            Memo::from_str("=:thor.rune:sthor17pfp4qvy5vrmtjar7kntachm0cfm9m9azl3jka:1/5/5:rj:10")
+                .unwrap(),
+            Memo::Swap {
+                asset: "thor.rune".to_string(),
+                destination: Some("sthor17pfp4qvy5vrmtjar7kntachm0cfm9m9azl3jka".to_string()),
+                limit: Some(Uint128::one()),
+                interval: Some(5),
+                quantity: Some(5),
+                affiliates: vec!["rj".to_string()],
+                affiliate_bps: vec![10],
+            }
+        );
+    }
+
+    #[test]
+    fn parses_swap_memo_with_e_notation_limit() {
+        // In the form returned by the quote endpoint
+        assert_eq!(
+            Memo::from_str(
+                "=:ETH.ETH:0x3021c479f7f8c9f1d5c7d8523ba5e22c0bcb5430:1423175e3/1/0:t:30"
+            )
+            .unwrap(),
+            Memo::Swap {
+                asset: "ETH.ETH".to_string(),
+                destination: Some("0x3021c479f7f8c9f1d5c7d8523ba5e22c0bcb5430".to_string()),
+                limit: Some(Uint128::new(1_423_175_000)),
+                interval: Some(1),
+                quantity: Some(0),
+                affiliates: vec!["t".to_string()],
+                affiliate_bps: vec![30],
+            }
+        );
+        assert_eq!(
+            Memo::from_str("=:ETH.ETH::1e6").unwrap(),
+            Memo::from_str("=:ETH.ETH::1000000").unwrap()
+        );
+        assert_eq!(
+            Memo::from_str("=:ETH.ETH::1e39"),
+            Err(MemoError::Invalid("swap limit", "1e39".to_string()))
+        );
+        assert_eq!(
+            Memo::from_str("=:ETH.ETH::1e"),
+            Err(MemoError::Invalid("swap limit", "1e".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_memo_aliases() {
+        assert_eq!(
+            Memo::from_str("SWAP:BTC.BTC").unwrap(),
+            Memo::from_str("s:BTC.BTC").unwrap()
+        );
+        assert_eq!(
+            Memo::from_str("add:BTC.BTC").unwrap(),
+            Memo::from_str("+:BTC.BTC").unwrap()
+        );
+        assert_eq!(
+            Memo::from_str("wd:BTC.BTC:100").unwrap(),
+            Memo::from_str("-:BTC.BTC:100").unwrap()
+        );
+    }
+
+    #[test]
+    fn parses_legacy_secure_withdraw_memo() {
+        assert_eq!(
+            Memo::from_str("-secure:eth-usdc").unwrap(),
+            Memo::SecureWithdraw {
+                address: "eth-usdc".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn parses_savers_memos() {
+        assert_eq!(
+            Memo::from_str("+:BTC/BTC").unwrap(),
+            Memo::DepositSavers {
+                vault: "BTC/BTC".to_string()
+            }
+        );
+        assert_eq!(
+            Memo::from_str("-:BTC/BTC:10000").unwrap(),
+            Memo::WithdrawSavers {
+                vault: "BTC/BTC".to_string(),
+                basis_points: 10_000
+            }
+        );
+    }
+
+    #[test]
+    fn round_trips_memos() {
+        for memo in [
+            "=:ETH.ETH:0xabc:100/3/0:rj/ts:10/5",
+            "+:BTC.BTC:bc1qxyz",
+            "-:BTC.BTC:2500:BTC.BTC",
+            "+:BTC/BTC",
+            "-:BTC/BTC:10000",
+            "BOND:thor1node:thor1provider:200",
+            "UNBOND:thor1node:1000",
+            "TRADE+:thor1address",
+            "TRADE-:bc1qaddress",
+            "SECURE+:thor1address",
+            "SECURE-:0xaddress",
+        ] {
+            assert_eq!(Memo::from_str(memo).unwrap().to_string(), memo);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_memos() {
+        assert_eq!(Memo::from_str(""), Err(MemoError::Empty));
+        assert_eq!(
+            Memo::from_str("secure:eth-usdc"),
+            Err(MemoError::UnknownType("SECURE".to_string()))
+        );
+        assert_eq!(
+            Memo::from_str("-:BTC.BTC"),
+            Err(MemoError::Missing("basis points"))
+        );
+        assert_eq!(
+            Memo::from_str("-:BTC.BTC:10001"),
+            Err(MemoError::Invalid("basis points", "10001".to_string()))
+        );
+        assert_eq!(
+            Memo::from_str("=:ETH.ETH::abc"),
+            Err(MemoError::Invalid("swap limit", "abc".to_string()))
+        );
+        assert_eq!(
+            Memo::from_str("=:ETH.ETH::0/18446744073709551616/0"),
+            Err(MemoError::Invalid(
+                "swap interval",
+                "18446744073709551616".to_string()
+            ))
+        );
+        assert_eq!(
+            Memo::from_str("+:BTC"),
+            Err(MemoError::Invalid("pool", "BTC".to_string()))
+        );
+        assert_eq!(Memo::from_str("TRADE+"), Err(MemoError::Missing("address")));
+        assert_eq!(
+            Memo::from_str(&format!("=:ETH.ETH:{}", "a".repeat(250))),
+            Err(MemoError::TooLong)
+        );
+    }
+
+    #[test]
+    fn maps_secured_assets_to_pools() {
+        assert_eq!(secured_asset_pool("btc-btc"), Some("BTC.BTC".to_string()));
+        assert_eq!(
+            secured_asset_pool("eth-usdc-0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+            Some("ETH.USDC-0XA0B86991C6218B36C1D19D4A2E9EB0CE3606EB48".to_string())
+        );
+        assert_eq!(secured_asset_pool("rune"), None);
+        assert_eq!(secured_asset_pool("x/ruji"), None);
+    }
+
+    #[test]
+    fn maps_pools_to_secured_assets() {
+        assert_eq!(pool_secured_asset("BTC.BTC"), Some("btc-btc".to_string()));
+        assert_eq!(pool_secured_asset("THOR"), None);
+    }
+}